@@ -7,10 +7,10 @@ use tokio_io::codec::{Encoder, Decoder};
 use fut::ActorFuture;
 use message::Response;
 use arbiter::Arbiter;
-use address::ActorAddress;
-use context::{Context, ActorFutureCell, ActorStreamCell};
+use address::{Address, ActorAddress};
+use context::{Context, AsyncContextApi, ActorFutureCell, ActorStreamCell};
 use framed::FramedContext;
-use utils::{TimerFunc, TimeoutWrapper};
+use utils::{IntervalFunc, TimerFunc, TimeoutWrapper};
 
 
 #[allow(unused_variables)]
@@ -81,6 +81,14 @@ pub trait Actor: Sized + 'static {
     /// after this call actor get dropped.
     fn stopped(&mut self, ctx: &mut Self::Context) {}
 
+    /// Human readable actor name.
+    ///
+    /// Name is used in log messages and lifecycle events instead of actor's
+    /// type name. By default it returns `None` and type name is used.
+    fn name() -> Option<&'static str> {
+        None
+    }
+
     /// Start new asynchronous actor, returns address of newly created actor.
     ///
     /// # Examples
@@ -130,19 +138,68 @@ pub trait Actor: Sized + 'static {
         where Self: Actor<Context=Context<Self>> + ActorAddress<Self, Addr>,
               F: FnOnce(&mut Context<Self>) -> Self + 'static
     {
-        let mut ctx = Context::new(unsafe{std::mem::uninitialized()});
+        let mut ctx = Context::empty();
         let addr =  <Self as ActorAddress<Self, Addr>>::get(&mut ctx);
 
         Arbiter::handle().spawn_fn(move || {
             let srv = f(&mut ctx);
-            let old = ctx.replace_actor(srv);
-            std::mem::forget(old);
+            ctx.set_actor(srv);
             ctx.run(Arbiter::handle());
             future::ok(())
         });
         addr
     }
 
+    /// Start new actor, actor is created by factory function.
+    ///
+    /// Unlike `create`, factory function is called immediately. Context already
+    /// has actor's address, so factory could use `ctx.address()`, add streams
+    /// or start timers before actor get started.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use actix::*;
+    ///
+    /// // initialize system
+    /// System::new("test");
+    ///
+    /// struct MyActor{addr: Address<MyActor>};
+    /// impl Actor for MyActor {
+    ///     type Context = Context<Self>;
+    /// }
+    ///
+    /// let addr = MyActor::create_with_context(|ctx| MyActor{addr: ctx.address()});
+    /// ```
+    fn create_with_context<F>(f: F) -> Address<Self>
+        where Self: Actor<Context=Context<Self>>,
+              F: FnOnce(&mut Context<Self>) -> Self
+    {
+        let mut ctx = Context::empty();
+        let addr = ctx.address_cell().unsync_address();
+
+        let act = f(&mut ctx);
+        ctx.set_actor(act);
+        ctx.run(Arbiter::handle());
+        addr
+    }
+
+    /// Replace actor behind `old` address with this actor.
+    ///
+    /// New actor takes over context of the old actor. Messages queued in old
+    /// actor's mailbox are handled by new actor, existing `Address` and
+    /// `SyncAddress` of old actor deliver messages to new actor. Old actor
+    /// finishes message it is handling at the moment, then its `stopped`
+    /// method is called and futures and streams spawned by old actor
+    /// get dropped. After that `started` is called for new actor.
+    ///
+    /// Actor is returned back if old actor is stopped already.
+    fn take_over(self, old: &Address<Self>) -> Result<(), Self>
+        where Self: Actor<Context=Context<Self>>
+    {
+        old.take_over(self)
+    }
+
     /// Create static response.
     fn reply<M>(val: Self::Item) -> Response<Self, M> where Self: ResponseType<M> {
         Response::reply(val)
@@ -156,6 +213,13 @@ pub trait Actor: Sized + 'static {
         Response::async_reply(fut)
     }
 
+    /// Create response that replies after specified period of time.
+    fn reply_after<M>(val: Self::Item, delay: Duration) -> Response<Self, M>
+        where Self: ResponseType<M>, Self::Item: 'static, Self::Error: 'static
+    {
+        Response::reply_after(val, delay)
+    }
+
     /// Create unit response, for case when `ResponseType::Item = ()`
     fn empty<M>() -> Response<Self, M> where Self: ResponseType<M, Item=()> {
         Response::empty()
@@ -165,6 +229,18 @@ pub trait Actor: Sized + 'static {
     fn reply_error<M>(err: Self::Error) -> Response<Self, M> where Self: ResponseType<M> {
         Response::error(err)
     }
+
+    /// Create static response from `Result`, `Err` becomes error response.
+    fn reply_result<M>(res: Result<Self::Item, Self::Error>) -> Response<Self, M>
+        where Self: ResponseType<M>
+    {
+        Response::from(res)
+    }
+}
+
+/// Actor name for user facing output, falls back to type name
+pub(crate) fn actor_name<A: Actor>() -> &'static str {
+    A::name().unwrap_or_else(std::any::type_name::<A>)
 }
 
 /// Actor trait that allows to handle `tokio_io::codec::Framed` objects.
@@ -198,13 +274,12 @@ pub trait FramedActor: Actor {
                                   <<Self as FramedActor>::Codec as Decoder>::Error>,
               F: FnOnce(&mut FramedContext<Self>) -> Self + 'static
     {
-        let mut ctx = FramedContext::new(unsafe{std::mem::uninitialized()}, io, codec);
+        let mut ctx = FramedContext::empty(io, codec);
         let addr =  <Self as ActorAddress<Self, Addr>>::get(&mut ctx);
 
         Arbiter::handle().spawn_fn(move || {
             let act = f(&mut ctx);
-            let old = ctx.replace_actor(act);
-            std::mem::forget(old);
+            ctx.set_actor(act);
             ctx.run(Arbiter::handle());
             future::ok(())
         });
@@ -228,6 +303,35 @@ pub trait Supervised: Actor {
     fn restarting(&mut self, ctx: &mut <Self as Actor>::Context) {}
 }
 
+/// Actors which state could be transferred to new actor instance
+///
+/// Actor is replaced with `Snapshotable::reload()`, i.e. for hot reload
+/// during development. Running actor handles messages queued before reload,
+/// then its state is captured with `snapshot()` and restored into new actor
+/// with `restore()`, after that new actor takes over the context.
+/// Supervisor restarts failed actor with the same actor instance, so state of
+/// supervised actor is preserved without snapshot.
+pub trait Snapshotable: Actor {
+
+    /// Serialize actor's state
+    fn snapshot(&self) -> Vec<u8>;
+
+    /// Restore actor's state from data returned by `snapshot()`
+    fn restore(&mut self, data: &[u8]);
+
+    /// Replace actor behind `old` address with this actor, state of old actor
+    /// is restored into this actor.
+    ///
+    /// Old actor's `stopped` method is called and futures and streams spawned
+    /// by old actor get dropped, like with `Actor::take_over()`.
+    /// Actor is returned back if old actor is stopped already.
+    fn reload(self, old: &Address<Self>) -> Result<(), Self>
+        where Self: Actor<Context=Context<Self>>
+    {
+        old.reload(self)
+    }
+}
+
 /// Message handler
 ///
 /// `Handler` implementation is a general way how to handle
@@ -236,6 +340,10 @@ pub trait Supervised: Actor {
 /// `M` is a message which can be handled by the actor.
 /// `E` is an optional error type, if message handler is used for handling
 /// Future or Stream results, then `E` type has to be set to correspondent `Error` type.
+///
+/// `Response` could be created from `Result<Self::Item, Self::Error>`, from any
+/// `ActorFuture` and from boxed `ActorFuture`, so handler with several code paths
+/// can return `.into()` from each of them without boxing synchronous results.
 #[allow(unused_variables)]
 pub trait Handler<M, E=()> where Self: Actor + ResponseType<M>
 {
@@ -244,6 +352,14 @@ pub trait Handler<M, E=()> where Self: Actor + ResponseType<M>
 
     /// Method is called on error. By default it does nothing.
     fn error(&mut self, err: E, ctx: &mut Self::Context) {}
+
+    /// Method is called before `handle()` for every message received through
+    /// actor's address. Message that fails validation is not passed to `handle()`,
+    /// returned error is sent to the caller as response.
+    /// By default every message is accepted.
+    fn validate(&self, msg: &M) -> Result<(), <Self as ResponseType<M>>::Error> {
+        Ok(())
+    }
 }
 
 /// Message response type
@@ -256,6 +372,32 @@ pub trait ResponseType<M> where Self: Actor {
     type Error;
 }
 
+/// Returns type names of `ResponseType::Item` and `ResponseType::Error`
+/// of actor `A` for message `M`.
+///
+/// ```rust
+/// use actix::*;
+///
+/// struct Ping;
+/// struct MyActor;
+///
+/// impl Actor for MyActor {
+///     type Context = Context<Self>;
+/// }
+///
+/// impl ResponseType<Ping> for MyActor {
+///     type Item = usize;
+///     type Error = String;
+/// }
+///
+/// let (item, error) = response_type_names::<MyActor, Ping>();
+/// assert_eq!(item, "usize");
+/// assert_eq!(error, std::any::type_name::<String>());
+/// ```
+pub fn response_type_names<A, M>() -> (&'static str, &'static str) where A: ResponseType<M> {
+    (std::any::type_name::<A::Item>(), std::any::type_name::<A::Error>())
+}
+
 /// Stream handler
 ///
 /// `StreamHandler` is an extension of a `Handler` with stream specific methods.
@@ -268,6 +410,24 @@ pub trait StreamHandler<M, E=()>: Handler<M, E> + ResponseType<M>
 
     /// Method is called when stream finishes, even if stream finishes with error.
     fn finished(&mut self, ctx: &mut Self::Context) {}
+
+    /// Method is called for every stream item and for stream error.
+    ///
+    /// By default item is passed to `Handler::handle()` and error to `Handler::error()`.
+    /// Override this method to handle both cases with single match expression.
+    /// Next item is not polled until returned response resolves, stream
+    /// finishes after error response resolves.
+    fn handle_result(&mut self, item: Result<M, E>, ctx: &mut Self::Context)
+                     -> Option<Response<Self, M>>
+    {
+        match item {
+            Ok(msg) => Some(<Self as Handler<M, E>>::handle(self, msg, ctx)),
+            Err(err) => {
+                <Self as Handler<M, E>>::error(self, err, ctx);
+                None
+            }
+        }
+    }
 }
 
 /// Actor execution state
@@ -283,6 +443,19 @@ pub enum ActorState {
     Stopped,
 }
 
+/// Actor scheduling priority, see `Context::set_priority()`
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum ActorPriority {
+    /// Actor handles one message at a time, then lets other actors
+    /// of the arbiter run.
+    Low,
+    /// Actor follows arbiter's throughput setting.
+    Normal,
+    /// Actor handles all messages available in its mailbox before
+    /// other actors of the arbiter run, regardless of arbiter's throughput.
+    High,
+}
+
 /// Actor execution context
 ///
 /// Each actor runs within specific execution context. `Actor::Context` defines
@@ -328,6 +501,24 @@ impl Default for SpawnHandle {
     }
 }
 
+/// Stream handle. Could be used for closing stream added with `add_stream`.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct StreamHandle(SpawnHandle);
+
+/// Watch handle. Could be used for cancelling watch started with `Context::watch`.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct WatchHandle(SpawnHandle);
+
+impl WatchHandle {
+    pub(crate) fn new(handle: SpawnHandle) -> WatchHandle {
+        WatchHandle(handle)
+    }
+
+    pub(crate) fn into_inner(self) -> SpawnHandle {
+        self.0
+    }
+}
+
 /// Asynchronous execution context
 pub trait AsyncContext<A>: ActorContext<A> where A: Actor<Context=Self>
 {
@@ -344,6 +535,15 @@ pub trait AsyncContext<A>: ActorContext<A> where A: Actor<Context=Self>
     /// Cancel future. idx is a value returned by `spawn` method.
     fn cancel_future(&mut self, handle: SpawnHandle) -> bool;
 
+    /// Spawn long lived item (stream, interval, watch), `Context::exclusive()`
+    /// does not wait for such items.
+    #[doc(hidden)]
+    fn spawn_background<F>(&mut self, fut: F) -> SpawnHandle
+        where F: ActorFuture<Item=(), Error=(), Actor=A> + 'static
+    {
+        self.spawn(fut)
+    }
+
     /// This method allow to handle Future in similar way as normal actor message.
     ///
     /// ```rust
@@ -397,17 +597,29 @@ pub trait AsyncContext<A>: ActorContext<A> where A: Actor<Context=Self>
     /// Information to consider. Actor wont receive next item from a stream
     /// until `Response` future resolves to result. `Self::reply` and
     /// `Self::reply_error` resolves immediately.
-    fn add_stream<S>(&mut self, fut: S)
+    ///
+    /// Returns handle of the stream, could be used for closing stream.
+    fn add_stream<S>(&mut self, fut: S) -> StreamHandle
         where S: Stream + 'static,
               A: Handler<S::Item, S::Error> + StreamHandler<S::Item, S::Error>
     {
         if self.state() == ActorState::Stopped {
             error!("Context::add_stream called for stopped actor.");
+            StreamHandle(SpawnHandle::default())
         } else {
-            self.spawn(ActorStreamCell::new(fut));
+            StreamHandle(self.spawn_background(ActorStreamCell::new(fut)))
         }
     }
 
+    /// Drop stream added with `add_stream`, actor keeps running.
+    ///
+    /// Actor does not receive any more items from the stream and
+    /// `StreamHandler::finished` is not called. Returns `false`
+    /// if stream is finished already.
+    fn close_stream(&mut self, handle: StreamHandle) -> bool {
+        self.cancel_future(handle.0)
+    }
+
     /// Send message `msg` to self after specified period of time. Returns spawn handle
     /// which could be used for cancelation.
     fn notify<M, E>(&mut self, msg: M, after: Duration) -> SpawnHandle
@@ -428,4 +640,23 @@ pub trait AsyncContext<A>: ActorContext<A> where A: Actor<Context=Self>
     {
         self.spawn(TimerFunc::new(dur, f))
     }
+
+    /// Execute closure every `interval` until `dur` elapses.
+    ///
+    /// First call happens after `interval`, call that is due exactly
+    /// at the end of `dur` still happens.
+    fn for_duration<F>(&mut self, dur: Duration, interval: Duration, f: F) -> SpawnHandle
+        where F: FnMut(&mut A, &mut A::Context) + 'static
+    {
+        self.spawn_background(IntervalFunc::new(dur, interval, usize::max_value(), f))
+    }
+
+    /// Execute closure every `interval` until `dur` elapses,
+    /// but not more than `max_iterations` times.
+    fn for_duration_max<F>(&mut self, dur: Duration, interval: Duration,
+                           max_iterations: usize, f: F) -> SpawnHandle
+        where F: FnMut(&mut A, &mut A::Context) + 'static
+    {
+        self.spawn_background(IntervalFunc::new(dur, interval, max_iterations, f))
+    }
 }
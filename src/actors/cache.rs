@@ -0,0 +1,182 @@
+//! Caching actor with TTL eviction
+//!
+//! `Cache<K, V>` is an actor that owns key-value entries, every entry
+//! expires after its time to live. Entries are added with `Put` message,
+//! read with `Get` message and removed with `Invalidate` message.
+//! Expired entries are evicted by arbiter's timer, so cache does not keep
+//! memory of entries nobody reads. `Stats` message reports hit and miss
+//! counts.
+//!
+//! # Examples
+//!
+//! ```rust
+//! extern crate actix;
+//! extern crate futures;
+//!
+//! use std::time::Duration;
+//! use futures::Future;
+//! use actix::prelude::*;
+//! use actix::actors::cache::{Cache, Get, Put};
+//!
+//! fn main() {
+//!     let sys = System::new("test");
+//!
+//!     let cache: Address<_> = Cache::<String, u32>::new().start();
+//!     cache.send(Put("answer".to_owned(), 42, Duration::from_secs(60)));
+//!
+//!     Arbiter::handle().spawn(
+//!         cache.call_fut(Get("answer".to_owned()))
+//!             .then(|res| {
+//!                 assert_eq!(res.unwrap(), Ok(Some(42)));
+//!                 Arbiter::system().send(msgs::SystemExit(0));
+//!                 Ok(())
+//!             }));
+//!
+//!     sys.run();
+//! }
+//! ```
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+
+use prelude::*;
+
+struct Entry<V> {
+    value: V,
+    expires: Instant,
+}
+
+/// Hit and miss counts of the cache
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CacheStats {
+    /// Number of `Get` messages that found live entry
+    pub hits: u64,
+    /// Number of `Get` messages that did not find live entry
+    pub misses: u64,
+    /// Number of entries evicted after their time to live
+    pub evicted: u64,
+    /// Number of entries in the cache
+    pub entries: usize,
+}
+
+/// Actor that caches values for limited time
+pub struct Cache<K, V> {
+    entries: HashMap<K, Entry<V>>,
+    stats: CacheStats,
+}
+
+impl<K: Hash + Eq, V> Default for Cache<K, V> {
+    fn default() -> Self {
+        Cache{entries: HashMap::new(), stats: CacheStats::default()}
+    }
+}
+
+impl<K: Hash + Eq, V> Cache<K, V> {
+
+    /// Create empty cache
+    pub fn new() -> Cache<K, V> {
+        Cache::default()
+    }
+
+    /// Remove entry if it is expired
+    fn evict(&mut self, key: &K) {
+        let expired = match self.entries.get(key) {
+            Some(entry) => entry.expires <= Arbiter::now(),
+            None => false,
+        };
+        if expired {
+            self.entries.remove(key);
+            self.stats.evicted += 1;
+        }
+    }
+}
+
+impl<K: 'static, V: 'static> Actor for Cache<K, V> {
+    type Context = Context<Self>;
+}
+
+/// Get value of the live entry, `None` if there is no one
+pub struct Get<K>(pub K);
+
+impl<K: 'static, V: 'static> ResponseType<Get<K>> for Cache<K, V> {
+    type Item = Option<V>;
+    type Error = ();
+}
+
+impl<K, V> Handler<Get<K>> for Cache<K, V>
+    where K: Hash + Eq + 'static, V: Clone + 'static
+{
+    fn handle(&mut self, msg: Get<K>, _: &mut Context<Self>) -> Response<Self, Get<K>> {
+        self.evict(&msg.0);
+        match self.entries.get(&msg.0) {
+            Some(entry) => {
+                self.stats.hits += 1;
+                Self::reply(Some(entry.value.clone()))
+            }
+            None => {
+                self.stats.misses += 1;
+                Self::reply(None)
+            }
+        }
+    }
+}
+
+/// Put value to the cache for given time to live
+///
+/// Entry with the same key gets replaced.
+pub struct Put<K, V>(pub K, pub V, pub Duration);
+
+impl<K: 'static, V: 'static> ResponseType<Put<K, V>> for Cache<K, V> {
+    type Item = ();
+    type Error = ();
+}
+
+impl<K, V> Handler<Put<K, V>> for Cache<K, V>
+    where K: Hash + Eq + Clone + 'static, V: 'static
+{
+    fn handle(&mut self, msg: Put<K, V>, ctx: &mut Context<Self>) -> Response<Self, Put<K, V>> {
+        let Put(key, value, ttl) = msg;
+        self.entries.insert(key.clone(), Entry{value: value, expires: Arbiter::now() + ttl});
+
+        // replaced entry keeps timer of previous one, evict checks expiration time
+        ctx.run_later(ttl, move |act, _| act.evict(&key));
+        Self::empty()
+    }
+}
+
+/// Remove entry from the cache, responds with `true` if entry was present
+pub struct Invalidate<K>(pub K);
+
+impl<K: 'static, V: 'static> ResponseType<Invalidate<K>> for Cache<K, V> {
+    type Item = bool;
+    type Error = ();
+}
+
+impl<K, V> Handler<Invalidate<K>> for Cache<K, V>
+    where K: Hash + Eq + 'static, V: 'static
+{
+    fn handle(&mut self, msg: Invalidate<K>, _: &mut Context<Self>)
+              -> Response<Self, Invalidate<K>>
+    {
+        self.evict(&msg.0);
+        Self::reply(self.entries.remove(&msg.0).is_some())
+    }
+}
+
+/// Get statistics of the cache
+pub struct Stats;
+
+impl<K: 'static, V: 'static> ResponseType<Stats> for Cache<K, V> {
+    type Item = CacheStats;
+    type Error = ();
+}
+
+impl<K, V> Handler<Stats> for Cache<K, V>
+    where K: Hash + Eq + 'static, V: 'static
+{
+    fn handle(&mut self, _: Stats, _: &mut Context<Self>) -> Response<Self, Stats> {
+        let mut stats = self.stats.clone();
+        stats.entries = self.entries.len();
+        Self::reply(stats)
+    }
+}
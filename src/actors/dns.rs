@@ -78,12 +78,9 @@ impl Handler<GetAddressInfo> for DnsResolver {
     fn handle(&mut self, msg: GetAddressInfo, _: &mut SyncContext<Self>)
               -> Response<Self, GetAddressInfo>
     {
-        match get_addrinfo(msg.host, msg.port, msg.family, msg.flags, msg.socktype) {
-            Err(err) =>
-                Self::reply_error(err),
-            Ok(addrs) =>
-                Self::reply(addrs.collect()),
-        }
+        Self::reply_result(
+            get_addrinfo(msg.host, msg.port, msg.family, msg.flags, msg.socktype)
+                .map(|addrs| addrs.collect()))
     }
 }
 
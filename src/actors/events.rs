@@ -0,0 +1,188 @@
+//! System lifecycle events
+//!
+//! `SystemEvents` is a system service that broadcasts `LifecycleEvent`
+//! messages to subscribers: actor start and stop, supervisor restart,
+//! arbiter registration and system shutdown. To subscribe, send `Subscribe` message to
+//! `SystemEvents` service.
+//!
+//! Events are collected only while service is running, service starts
+//! with first `Arbiter::system_registry().get::<SystemEvents>()` call.
+//! Stop event is reported only for actors which start event was reported.
+//! Actors are identified by `Actor::name()`, or by type name if actor
+//! does not provide one.
+//!
+//! # Examples
+//!
+//! ```rust
+//! extern crate actix;
+//! extern crate futures;
+//!
+//! use futures::Future;
+//! use actix::prelude::*;
+//! use actix::actors::events::{LifecycleEvent, Subscribe, SystemEvents};
+//!
+//! struct Watcher;
+//!
+//! impl Actor for Watcher {
+//!     type Context = Context<Self>;
+//! }
+//!
+//! impl ResponseType<LifecycleEvent> for Watcher {
+//!     type Item = ();
+//!     type Error = ();
+//! }
+//!
+//! impl Handler<LifecycleEvent> for Watcher {
+//!     fn handle(&mut self, msg: LifecycleEvent, _: &mut Context<Self>)
+//!               -> Response<Self, LifecycleEvent>
+//!     {
+//!         println!("{:?}", msg);
+//!         if let LifecycleEvent::ActorStopped(_) = msg {
+//!             Arbiter::system().send(msgs::SystemExit(0));
+//!         }
+//!         Self::empty()
+//!     }
+//! }
+//!
+//! struct Worker;
+//!
+//! impl Actor for Worker {
+//!     type Context = Context<Self>;
+//!
+//!     fn started(&mut self, ctx: &mut Context<Self>) {
+//!         ctx.stop();
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let sys = System::new("test");
+//!
+//!     let watcher: SyncAddress<_> = Watcher.start();
+//!     Arbiter::handle().spawn(
+//!         Arbiter::system_registry().get::<SystemEvents>()
+//!             .call_fut(Subscribe(watcher.subscriber()))
+//!             .then(|_| {
+//!                 // start worker after subscription is confirmed
+//!                 let _: () = Worker.start();
+//!                 Ok(())
+//!             }));
+//!
+//!     sys.run();
+//! }
+//! ```
+use std;
+use futures::{future, Future};
+
+use prelude::*;
+
+/// Lifecycle event of the system
+#[derive(Clone, Debug, PartialEq)]
+pub enum LifecycleEvent {
+    /// Actor with the given name started
+    ActorStarted(&'static str),
+    /// Actor with the given name stopped
+    ActorStopped(&'static str),
+    /// Supervisor restarted actor with the given name
+    ActorRestarted(&'static str),
+    /// Arbiter registered within system
+    ArbiterRegistered {
+        /// Unique arbiter id
+        id: String,
+        /// Name of the named arbiter
+        name: Option<String>,
+    },
+    /// Arbiter unregistered from system
+    ArbiterUnregistered {
+        /// Unique arbiter id
+        id: String,
+        /// Name of the named arbiter
+        name: Option<String>,
+    },
+    /// System received `SystemExit` with the given code and is shutting down.
+    /// System stops after all subscribers handle this event, or after
+    /// shutdown timeout.
+    SystemShutdown(i32),
+}
+
+/// Report lifecycle event if `SystemEvents` service is running
+pub(crate) fn emit(event: LifecycleEvent) {
+    if let Some(addr) = Arbiter::lifecycle_events() {
+        addr.send(event);
+    }
+}
+
+/// Service that broadcasts lifecycle events to subscribers
+#[derive(Default)]
+pub struct SystemEvents {
+    subscribers: Vec<Box<Subscriber<LifecycleEvent> + Send>>,
+}
+
+impl Actor for SystemEvents {
+    type Context = Context<Self>;
+}
+
+impl Supervised for SystemEvents {}
+
+impl SystemService for SystemEvents {
+
+    fn service_started(&mut self, ctx: &mut Context<Self>) {
+        if let Ok(mut events) = Arbiter::system_registry().events.lock() {
+            *events = Some(ctx.address());
+        }
+    }
+}
+
+impl ResponseType<LifecycleEvent> for SystemEvents {
+    type Item = ();
+    type Error = ();
+}
+
+/// Broadcast event to subscribers
+impl Handler<LifecycleEvent> for SystemEvents {
+
+    fn handle(&mut self, msg: LifecycleEvent, _: &mut Context<Self>)
+              -> Response<Self, LifecycleEvent>
+    {
+        // shutdown event is tracked, response resolves after
+        // all subscribers handle it
+        let mut delivered = Vec::new();
+
+        // disconnected subscribers get dropped
+        let subscribers = std::mem::replace(&mut self.subscribers, Vec::new());
+        for subscr in subscribers {
+            let sent = if let LifecycleEvent::SystemShutdown(_) = msg {
+                subscr.send_tracked(msg.clone())
+                    .map(|rx| delivered.push(rx.then(|_| Ok::<_, ()>(()))))
+            } else {
+                subscr.send(msg.clone())
+            };
+            if sent.is_ok() {
+                self.subscribers.push(subscr);
+            }
+        }
+
+        if delivered.is_empty() {
+            Self::empty()
+        } else {
+            Self::async_reply(future::join_all(delivered).map(|_| ()).actfuture())
+        }
+    }
+}
+
+/// Subscribe to lifecycle events
+pub struct Subscribe(pub Box<Subscriber<LifecycleEvent> + Send>);
+
+impl ResponseType<Subscribe> for SystemEvents {
+    type Item = ();
+    type Error = ();
+}
+
+/// Add subscriber for lifecycle events
+impl Handler<Subscribe> for SystemEvents {
+
+    fn handle(&mut self, msg: Subscribe, _: &mut Context<Self>) -> Response<Self, Subscribe>
+    {
+        self.subscribers.push(msg.0);
+        Self::empty()
+    }
+}
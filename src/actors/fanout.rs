@@ -0,0 +1,223 @@
+//! Deterministic message fan-out
+//!
+//! `FanOut<M>` delivers every message to all registered recipients in
+//! registration order. Delivery depends on `FanOutMode`:
+//!
+//! * `Sequential` - message is delivered to next recipient only after
+//!   previous recipient handled it, next message waits until current one
+//!   is handled by all recipients
+//! * `Concurrent` - message is sent to all recipients at once
+//! * `AllOrNothing` - message is sent to all recipients at once, but only if
+//!   all of them are connected. Otherwise message is not delivered to any
+//!   recipient, it goes to dead letters recipient instead.
+//!
+//! Fan-out responds with number of recipients message was delivered to.
+//! Disconnected recipients get removed, `AllOrNothing` fan-out responds with
+//! error if message is rolled back.
+//!
+//! # Examples
+//!
+//! ```rust
+//! extern crate actix;
+//! extern crate futures;
+//!
+//! use futures::Future;
+//! use actix::prelude::*;
+//! use actix::actors::fanout::{FanOut, FanOutConfig, FanOutMode};
+//!
+//! #[derive(Clone)]
+//! struct Tick(u32);
+//!
+//! struct Listener(&'static str);
+//!
+//! impl Actor for Listener {
+//!     type Context = Context<Self>;
+//! }
+//!
+//! impl ResponseType<Tick> for Listener {
+//!     type Item = ();
+//!     type Error = ();
+//! }
+//!
+//! impl Handler<Tick> for Listener {
+//!     fn handle(&mut self, msg: Tick, _: &mut Context<Self>) -> Response<Self, Tick> {
+//!         println!("{} got tick {}", self.0, msg.0);
+//!         Self::empty()
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let sys = System::new("test");
+//!
+//!     let first: SyncAddress<_> = Listener("first").start();
+//!     let second: SyncAddress<_> = Listener("second").start();
+//!
+//!     let fanout: Address<_> = FanOut::new(FanOutConfig{mode: FanOutMode::Sequential})
+//!         .add_recipient(first.recipient())
+//!         .add_recipient(second.recipient())
+//!         .start();
+//!
+//!     Arbiter::handle().spawn(
+//!         fanout.call_fut(Tick(1))
+//!             .then(|res| {
+//!                 assert_eq!(res.unwrap(), Ok(2));
+//!                 Arbiter::system().send(msgs::SystemExit(0));
+//!                 Ok(())
+//!             }));
+//!
+//!     sys.run();
+//! }
+//! ```
+use std::any::type_name;
+use std::collections::VecDeque;
+use futures::{future, stream, Future, Stream};
+use futures::future::Either;
+use futures::unsync::oneshot::{channel, Sender};
+
+use prelude::*;
+
+/// How fan-out delivers message to recipients
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FanOutMode {
+    /// Deliver to one recipient at a time
+    Sequential,
+    /// Deliver to all recipients at once
+    Concurrent,
+    /// Deliver to all recipients at once, or to none of them
+    AllOrNothing,
+}
+
+/// Fan-out configuration
+#[derive(Clone, Copy, Debug)]
+pub struct FanOutConfig {
+    /// Delivery mode
+    pub mode: FanOutMode,
+}
+
+impl Default for FanOutConfig {
+    fn default() -> Self {
+        FanOutConfig{mode: FanOutMode::Sequential}
+    }
+}
+
+/// Actor that delivers each message to all registered recipients
+pub struct FanOut<M: 'static> {
+    config: FanOutConfig,
+    recipients: Vec<Recipient<M>>,
+    dead_letters: Option<Recipient<M>>,
+    queue: VecDeque<(M, Sender<usize>)>,
+    delivering: bool,
+}
+
+impl<M: Clone + Send + 'static> FanOut<M> {
+
+    /// Create fan-out without recipients
+    pub fn new(config: FanOutConfig) -> FanOut<M> {
+        FanOut {
+            config: config,
+            recipients: Vec::new(),
+            dead_letters: None,
+            queue: VecDeque::new(),
+            delivering: false,
+        }
+    }
+
+    /// Register recipient, messages are delivered in registration order
+    pub fn add_recipient(mut self, recipient: Recipient<M>) -> Self {
+        self.recipients.push(recipient);
+        self
+    }
+
+    /// Send messages that are rolled back in `AllOrNothing` mode to `recipient`
+    pub fn dead_letters(mut self, recipient: Recipient<M>) -> Self {
+        self.dead_letters = Some(recipient);
+        self
+    }
+
+    /// Number of registered recipients
+    pub fn recipients(&self) -> usize {
+        self.recipients.len()
+    }
+
+    /// Send message to all recipients, returns number of delivered messages
+    fn broadcast(&self, msg: &M) -> usize {
+        self.recipients.iter()
+            .filter(|recipient| recipient.send(msg.clone()).is_ok())
+            .count()
+    }
+
+    fn sequential(&mut self, msg: M, ctx: &mut Context<Self>) -> Response<Self, M> {
+        let (tx, rx) = channel();
+        self.queue.push_back((msg, tx));
+        self.deliver_next(ctx);
+
+        Self::async_reply(
+            rx.actfuture().map_err(|_, _: &mut Self, _: &mut Context<Self>| ()))
+    }
+
+    /// Deliver next queued message, unless previous one is still being delivered
+    fn deliver_next(&mut self, ctx: &mut Context<Self>) {
+        if self.delivering {
+            return
+        }
+        let (msg, tx) = match self.queue.pop_front() {
+            Some(item) => item,
+            None => return,
+        };
+        self.delivering = true;
+
+        // recipient gets message after previous one handled it
+        stream::iter_ok(self.recipients.clone())
+            .fold(0, move |delivered, recipient| match recipient.send_tracked(msg.clone()) {
+                Ok(handled) => Either::A(
+                    handled.then(move |res| Ok(delivered + if res.is_ok() {1} else {0}))),
+                Err(_) => Either::B(future::ok::<_, ()>(delivered)),
+            })
+            .actfuture()
+            .map(|delivered, act: &mut Self, ctx: &mut Context<Self>| {
+                let _ = tx.send(delivered);
+                act.recipients.retain(|recipient| recipient.connected());
+                act.delivering = false;
+                act.deliver_next(ctx);
+            })
+            .spawn(ctx);
+    }
+
+    fn all_or_nothing(&mut self, msg: M) -> Response<Self, M> {
+        if self.recipients.iter().all(|recipient| recipient.connected()) {
+            return Self::reply(self.broadcast(&msg))
+        }
+
+        debug!("FanOut rolls back {}, recipient is disconnected", type_name::<M>());
+        self.recipients.retain(|recipient| recipient.connected());
+        if let Some(ref dead_letters) = self.dead_letters {
+            let _ = dead_letters.send(msg);
+        }
+        Self::reply_error(())
+    }
+}
+
+impl<M: 'static> Actor for FanOut<M> {
+    type Context = Context<Self>;
+}
+
+impl<M: 'static> ResponseType<M> for FanOut<M> {
+    type Item = usize;
+    type Error = ();
+}
+
+/// Deliver message to registered recipients according to fan-out mode
+impl<M: Clone + Send + 'static> Handler<M> for FanOut<M> {
+
+    fn handle(&mut self, msg: M, ctx: &mut Context<Self>) -> Response<Self, M> {
+        match self.config.mode {
+            FanOutMode::Sequential => self.sequential(msg, ctx),
+            FanOutMode::Concurrent => {
+                let delivered = self.broadcast(&msg);
+                self.recipients.retain(|recipient| recipient.connected());
+                Self::reply(delivered)
+            }
+            FanOutMode::AllOrNothing => self.all_or_nothing(msg),
+        }
+    }
+}
@@ -0,0 +1,194 @@
+//! Smallest mailbox load balancing
+//!
+//! `LoadBalancer<M>` forwards every message to the worker with the shortest
+//! mailbox, mailbox size is reported by `Subscriber::mailbox_len()`. Workers
+//! are added and removed at runtime with `AddWorker` and `RemoveWorker`
+//! messages. If mailboxes of all workers are longer than high watermark,
+//! messages are queued by load balancer and forwarded once some worker
+//! catches up. Disconnected workers get removed.
+//!
+//! # Examples
+//!
+//! ```rust
+//! extern crate actix;
+//!
+//! use actix::prelude::*;
+//! use actix::actors::load_balancer::{AddWorker, LoadBalancer};
+//!
+//! struct Job(u32);
+//!
+//! struct Worker;
+//!
+//! impl Actor for Worker {
+//!     type Context = Context<Self>;
+//! }
+//!
+//! impl ResponseType<Job> for Worker {
+//!     type Item = ();
+//!     type Error = ();
+//! }
+//!
+//! impl Handler<Job> for Worker {
+//!     fn handle(&mut self, msg: Job, _: &mut Context<Self>) -> Response<Self, Job> {
+//!         println!("job {}", msg.0);
+//!         if msg.0 == 9 {
+//!             Arbiter::system().send(msgs::SystemExit(0));
+//!         }
+//!         Self::empty()
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let sys = System::new("test");
+//!
+//!     let balancer: Address<_> = LoadBalancer::new(16).start();
+//!     for _ in 0..2 {
+//!         let worker: SyncAddress<_> = Worker.start();
+//!         balancer.send(AddWorker(worker.recipient()));
+//!     }
+//!     for i in 0..10 {
+//!         balancer.send(Job(i));
+//!     }
+//!
+//!     sys.run();
+//! }
+//! ```
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use prelude::*;
+
+/// Actor that forwards messages to the least loaded worker
+pub struct LoadBalancer<M: Send + 'static> {
+    workers: Vec<Recipient<M>>,
+    high_watermark: usize,
+    queue: VecDeque<M>,
+    retry: Duration,
+    retrying: bool,
+}
+
+impl<M: Send + 'static> LoadBalancer<M> {
+
+    /// Create load balancer without workers. Messages are queued if mailboxes
+    /// of all workers are longer than `high_watermark`.
+    pub fn new(high_watermark: usize) -> LoadBalancer<M> {
+        LoadBalancer {
+            workers: Vec::new(),
+            high_watermark: high_watermark,
+            queue: VecDeque::new(),
+            retry: Duration::from_millis(10),
+            retrying: false,
+        }
+    }
+
+    /// Set how often queued messages are retried, default is 10 milliseconds
+    pub fn retry_interval(mut self, interval: Duration) -> Self {
+        self.retry = interval;
+        self
+    }
+
+    /// Send message to the worker with the shortest mailbox,
+    /// message is returned back if all workers are busy
+    fn forward(&mut self, mut msg: M) -> Result<(), M> {
+        loop {
+            let idx = match self.workers.iter()
+                .enumerate()
+                .filter(|&(_, w)| w.mailbox_len() <= self.high_watermark)
+                .min_by_key(|&(_, w)| w.mailbox_len())
+            {
+                Some((idx, _)) => idx,
+                None => return Err(msg),
+            };
+            match self.workers[idx].send(msg) {
+                Ok(()) => return Ok(()),
+                Err(m) => {
+                    // worker is disconnected, try next one
+                    self.workers.remove(idx);
+                    msg = m;
+                }
+            }
+        }
+    }
+
+    /// Forward queued messages while workers have capacity
+    fn drain(&mut self, ctx: &mut Context<Self>) {
+        while let Some(msg) = self.queue.pop_front() {
+            if let Err(msg) = self.forward(msg) {
+                self.queue.push_front(msg);
+                break
+            }
+        }
+
+        if !self.queue.is_empty() && !self.retrying {
+            self.retrying = true;
+            ctx.run_later(self.retry, |act, ctx| {
+                act.retrying = false;
+                act.drain(ctx);
+            });
+        }
+    }
+}
+
+impl<M: Send + 'static> Actor for LoadBalancer<M> {
+    type Context = Context<Self>;
+}
+
+impl<M: Send + 'static> ResponseType<M> for LoadBalancer<M> {
+    type Item = ();
+    type Error = ();
+}
+
+/// Forward message to the worker with the shortest mailbox
+impl<M: Send + 'static> Handler<M> for LoadBalancer<M> {
+
+    fn handle(&mut self, msg: M, ctx: &mut Context<Self>) -> Response<Self, M> {
+        // queued messages go first
+        if self.queue.is_empty() {
+            if let Err(msg) = self.forward(msg) {
+                self.queue.push_back(msg);
+            }
+        } else {
+            self.queue.push_back(msg);
+        }
+        self.drain(ctx);
+        Self::empty()
+    }
+}
+
+/// Add worker to load balancer
+pub struct AddWorker<M: 'static>(pub Recipient<M>);
+
+impl<M: Send + 'static> ResponseType<AddWorker<M>> for LoadBalancer<M> {
+    type Item = ();
+    type Error = ();
+}
+
+impl<M: Send + 'static> Handler<AddWorker<M>> for LoadBalancer<M> {
+
+    fn handle(&mut self, msg: AddWorker<M>, ctx: &mut Context<Self>)
+              -> Response<Self, AddWorker<M>>
+    {
+        self.workers.push(msg.0);
+        self.drain(ctx);
+        Self::empty()
+    }
+}
+
+/// Remove worker from load balancer, worker is matched by actor id
+pub struct RemoveWorker<M: 'static>(pub Recipient<M>);
+
+impl<M: Send + 'static> ResponseType<RemoveWorker<M>> for LoadBalancer<M> {
+    type Item = ();
+    type Error = ();
+}
+
+impl<M: Send + 'static> Handler<RemoveWorker<M>> for LoadBalancer<M> {
+
+    fn handle(&mut self, msg: RemoveWorker<M>, _: &mut Context<Self>)
+              -> Response<Self, RemoveWorker<M>>
+    {
+        let id = msg.0.actor_id();
+        self.workers.retain(|worker| id.is_none() || worker.actor_id() != id);
+        Self::empty()
+    }
+}
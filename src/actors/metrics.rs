@@ -0,0 +1,480 @@
+//! Actor metrics
+//!
+//! `MetricsActor` is a system service that collects message handling metrics.
+//! Actor opts in by implementing `Instrumented` trait and handling messages
+//! with `InstrumentedHandler` instead of `Handler`. For each instrumented actor
+//! `MetricsActor` records number of received messages, number of successfully
+//! processed messages, number of errors and handler latency histogram.
+//! Latency of asynchronous response is measured until response future resolves.
+//!
+//! Arbiters and actors could report runtime metrics, like mailbox depth or
+//! event loop tick lag, with `Report` message. Both kinds of metrics are
+//! returned in `MetricsSnapshot`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! extern crate actix;
+//! extern crate futures;
+//!
+//! use futures::Future;
+//! use actix::prelude::*;
+//! use actix::actors::metrics::{self, Instrumented, InstrumentedHandler};
+//!
+//! struct Ping;
+//!
+//! struct MyActor;
+//!
+//! impl Actor for MyActor {
+//!     type Context = Context<Self>;
+//! }
+//!
+//! impl Instrumented for MyActor {
+//!     fn metrics_name() -> &'static str { "my-actor" }
+//! }
+//!
+//! impl ResponseType<Ping> for MyActor {
+//!     type Item = ();
+//!     type Error = ();
+//! }
+//!
+//! impl InstrumentedHandler<Ping> for MyActor {
+//!     fn handle(&mut self, _: Ping, _: &mut Context<Self>) -> Response<Self, Ping> {
+//!         Self::empty()
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let sys = System::new("test");
+//!
+//!     let addr: Address<_> = MyActor.start();
+//!     Arbiter::handle().spawn(
+//!         addr.call_fut(Ping)
+//!             .and_then(|_| {
+//!                 Arbiter::system_registry().get::<metrics::MetricsActor>()
+//!                     .call_fut(metrics::PrometheusExport)
+//!             })
+//!             .then(|res| {
+//!                 if let Ok(Ok(text)) = res {
+//!                     println!("{}", text);
+//!                 }
+//!                 Arbiter::system().send(msgs::SystemExit(0));
+//!                 Ok(())
+//!             }));
+//!
+//!     sys.run();
+//! }
+//! ```
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+use std::collections::BTreeMap;
+use futures::{Async, Poll};
+
+use prelude::*;
+
+/// Upper bounds of latency histogram buckets, in microseconds
+const BUCKETS: [u64; 8] = [1_000, 5_000, 10_000, 50_000, 100_000, 500_000, 1_000_000, 5_000_000];
+
+/// Actor that reports message handling metrics to `MetricsActor`
+///
+/// Metrics are sent to system's `MetricsActor`, address of the service
+/// is looked up once per actor. Actor that does not run in a system
+/// handles messages without reporting metrics.
+pub trait Instrumented: Actor<Context=Context<Self>> {
+    /// Actor name used in metrics snapshot
+    fn metrics_name() -> &'static str;
+}
+
+/// Instrumented message handler
+///
+/// Each actor that implements `InstrumentedHandler<M>` implements `Handler<M>` as well.
+/// Message dispatch is wrapped and reported to `MetricsActor`.
+pub trait InstrumentedHandler<M>: Instrumented + ResponseType<M> {
+    /// Method is called for every message received by this Actor
+    fn handle(&mut self, msg: M, ctx: &mut Context<Self>) -> Response<Self, M>;
+}
+
+impl<A, M> Handler<M> for A where A: InstrumentedHandler<M>, M: 'static
+{
+    fn handle(&mut self, msg: M, ctx: &mut Context<Self>) -> Response<Self, M> {
+        // metrics are not reported outside of system
+        let addr = match ctx.metrics() {
+            Some(addr) => addr,
+            None => return <A as InstrumentedHandler<M>>::handle(self, msg, ctx),
+        };
+        let name = A::metrics_name();
+        addr.send(Received(name));
+
+        let start = Instant::now();
+        let mut response = <A as InstrumentedHandler<M>>::handle(self, msg, ctx);
+
+        if response.is_async() {
+            Response::async_reply(InstrumentedResponse{
+                name: name, start: start, addr: addr, response: response})
+        } else {
+            match response.result() {
+                Some(Ok(item)) => {
+                    addr.send(Processed(name, true, start.elapsed()));
+                    Response::reply(item)
+                }
+                Some(Err(err)) => {
+                    addr.send(Processed(name, false, start.elapsed()));
+                    Response::error(err)
+                }
+                None => response,
+            }
+        }
+    }
+}
+
+/// Asynchronous response wrapper, reports result after response resolves
+struct InstrumentedResponse<A, M> where A: Instrumented + ResponseType<M> {
+    name: &'static str,
+    start: Instant,
+    addr: SyncAddress<MetricsActor>,
+    response: Response<A, M>,
+}
+
+impl<A, M> ActorFuture for InstrumentedResponse<A, M> where A: Instrumented + ResponseType<M>
+{
+    type Item = A::Item;
+    type Error = A::Error;
+    type Actor = A;
+
+    fn poll(&mut self, act: &mut A, ctx: &mut Context<A>) -> Poll<A::Item, A::Error> {
+        let result = match self.response.poll(act, ctx) {
+            Ok(Async::NotReady) => return Ok(Async::NotReady),
+            result => result,
+        };
+        self.addr.send(Processed(self.name, result.is_ok(), self.start.elapsed()));
+        result
+    }
+}
+
+/// Handler latency histogram
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+pub struct Histogram {
+    /// Number of observations in each bucket, buckets are not cumulative.
+    /// Last element counts observations above the largest bound.
+    pub buckets: [u64; 9],
+    /// Total number of observations
+    pub count: u64,
+    /// Sum of all observations in microseconds
+    pub sum: u64,
+}
+
+impl Histogram {
+    /// Upper bounds of histogram buckets in microseconds
+    pub fn bounds() -> &'static [u64] {
+        &BUCKETS
+    }
+
+    fn observe(&mut self, dur: Duration) {
+        let micros = dur.as_secs() * 1_000_000 + u64::from(dur.subsec_micros());
+        let idx = BUCKETS.iter().position(|b| micros <= *b).unwrap_or(BUCKETS.len());
+        self.buckets[idx] += 1;
+        self.count += 1;
+        self.sum += micros;
+    }
+}
+
+/// Metrics of the single actor type
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+pub struct ActorMetrics {
+    /// Number of received messages
+    pub received: u64,
+    /// Number of successfully processed messages
+    pub success: u64,
+    /// Number of messages processed with error
+    pub errors: u64,
+    /// Handler latency
+    pub latency: Histogram,
+}
+
+/// Source of the runtime metric
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+pub enum Source {
+    /// Arbiter name
+    Arbiter(String),
+    /// Actor name
+    Actor(String),
+}
+
+impl Source {
+    fn label(&self) -> String {
+        match *self {
+            Source::Arbiter(ref name) => format!("arbiter=\"{}\"", name),
+            Source::Actor(ref name) => format!("actor=\"{}\"", name),
+        }
+    }
+}
+
+/// Runtime metric
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+pub enum Metric {
+    /// Number of messages in the mailbox, gauge
+    MailboxDepth,
+    /// Number of handled messages, counter
+    Handled,
+    /// Number of dropped messages, counter
+    Dropped,
+    /// Event loop tick lag in microseconds, gauge
+    TickLag,
+}
+
+/// Runtime metrics of the single arbiter or actor
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+pub struct RuntimeMetrics {
+    /// Last reported mailbox depth
+    pub mailbox_depth: u64,
+    /// Total number of handled messages
+    pub handled: u64,
+    /// Total number of dropped messages
+    pub dropped: u64,
+    /// Last reported event loop tick lag in microseconds
+    pub tick_lag: u64,
+}
+
+impl RuntimeMetrics {
+    fn report(&mut self, metric: Metric, value: u64) {
+        match metric {
+            Metric::MailboxDepth => self.mailbox_depth = value,
+            Metric::Handled => self.handled += value,
+            Metric::Dropped => self.dropped += value,
+            Metric::TickLag => self.tick_lag = value,
+        }
+    }
+}
+
+/// Snapshot of collected metrics
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+pub struct MetricsSnapshot {
+    /// Metrics by actor name
+    pub actors: BTreeMap<String, ActorMetrics>,
+    /// Runtime metrics reported with `Report` message
+    #[cfg_attr(feature="serde", serde(with="pairs"))]
+    pub runtime: BTreeMap<Source, RuntimeMetrics>,
+}
+
+/// Map with non-string keys serialized as list of pairs, so snapshot
+/// could be encoded in formats like JSON
+#[cfg(feature="serde")]
+mod pairs {
+    use std::collections::BTreeMap;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<K, V, S>(map: &BTreeMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+        where K: Serialize, V: Serialize, S: Serializer
+    {
+        serializer.collect_seq(map.iter())
+    }
+
+    pub fn deserialize<'de, K, V, D>(deserializer: D) -> Result<BTreeMap<K, V>, D::Error>
+        where K: Deserialize<'de> + Ord, V: Deserialize<'de>, D: Deserializer<'de>
+    {
+        Vec::<(K, V)>::deserialize(deserializer).map(|pairs| pairs.into_iter().collect())
+    }
+}
+
+impl MetricsSnapshot {
+    fn actor(&mut self, name: &str) -> &mut ActorMetrics {
+        if !self.actors.contains_key(name) {
+            self.actors.insert(name.to_owned(), ActorMetrics::default());
+        }
+        self.actors.get_mut(name).unwrap()
+    }
+
+    /// Render snapshot in Prometheus text exposition format
+    pub fn to_prometheus(&self) -> String {
+        let mut s = String::new();
+
+        let _ = writeln!(s, "# TYPE actix_messages_received_total counter");
+        for (name, m) in &self.actors {
+            let _ = writeln!(s, "actix_messages_received_total{{actor=\"{}\"}} {}",
+                             name, m.received);
+        }
+
+        let _ = writeln!(s, "# TYPE actix_messages_processed_total counter");
+        for (name, m) in &self.actors {
+            let _ = writeln!(
+                s, "actix_messages_processed_total{{actor=\"{}\",result=\"success\"}} {}",
+                name, m.success);
+            let _ = writeln!(
+                s, "actix_messages_processed_total{{actor=\"{}\",result=\"error\"}} {}",
+                name, m.errors);
+        }
+
+        let _ = writeln!(s, "# TYPE actix_handler_latency_seconds histogram");
+        for (name, m) in &self.actors {
+            let mut total = 0;
+            for (idx, bound) in BUCKETS.iter().enumerate() {
+                total += m.latency.buckets[idx];
+                let _ = writeln!(
+                    s, "actix_handler_latency_seconds_bucket{{actor=\"{}\",le=\"{}\"}} {}",
+                    name, *bound as f64 / 1_000_000.0, total);
+            }
+            let _ = writeln!(
+                s, "actix_handler_latency_seconds_bucket{{actor=\"{}\",le=\"+Inf\"}} {}",
+                name, m.latency.count);
+            let _ = writeln!(s, "actix_handler_latency_seconds_sum{{actor=\"{}\"}} {}",
+                             name, m.latency.sum as f64 / 1_000_000.0);
+            let _ = writeln!(s, "actix_handler_latency_seconds_count{{actor=\"{}\"}} {}",
+                             name, m.latency.count);
+        }
+
+        let _ = writeln!(s, "# TYPE actix_runtime_mailbox_depth gauge");
+        for (source, m) in &self.runtime {
+            let _ = writeln!(s, "actix_runtime_mailbox_depth{{{}}} {}",
+                             source.label(), m.mailbox_depth);
+        }
+
+        let _ = writeln!(s, "# TYPE actix_runtime_handled_total counter");
+        for (source, m) in &self.runtime {
+            let _ = writeln!(s, "actix_runtime_handled_total{{{}}} {}",
+                             source.label(), m.handled);
+        }
+
+        let _ = writeln!(s, "# TYPE actix_runtime_dropped_total counter");
+        for (source, m) in &self.runtime {
+            let _ = writeln!(s, "actix_runtime_dropped_total{{{}}} {}",
+                             source.label(), m.dropped);
+        }
+
+        let _ = writeln!(s, "# TYPE actix_runtime_tick_lag_seconds gauge");
+        for (source, m) in &self.runtime {
+            let _ = writeln!(s, "actix_runtime_tick_lag_seconds{{{}}} {}",
+                             source.label(), m.tick_lag as f64 / 1_000_000.0);
+        }
+        s
+    }
+}
+
+/// Metrics collector actor
+#[derive(Default)]
+pub struct MetricsActor {
+    metrics: MetricsSnapshot,
+}
+
+impl Actor for MetricsActor {
+    type Context = Context<Self>;
+}
+
+impl Supervised for MetricsActor {}
+
+impl SystemService for MetricsActor {}
+
+/// Get snapshot of collected metrics
+pub struct GetMetrics;
+
+impl ResponseType<GetMetrics> for MetricsActor {
+    type Item = MetricsSnapshot;
+    type Error = ();
+}
+
+impl Handler<GetMetrics> for MetricsActor {
+
+    fn handle(&mut self, _: GetMetrics, _: &mut Context<Self>) -> Response<Self, GetMetrics> {
+        Self::reply(self.metrics.clone())
+    }
+}
+
+/// Get snapshot of collected metrics, same as `GetMetrics`
+///
+/// Snapshot is serializable with `serde` feature enabled.
+pub struct Snapshot;
+
+impl ResponseType<Snapshot> for MetricsActor {
+    type Item = MetricsSnapshot;
+    type Error = ();
+}
+
+impl Handler<Snapshot> for MetricsActor {
+
+    fn handle(&mut self, _: Snapshot, _: &mut Context<Self>) -> Response<Self, Snapshot> {
+        Self::reply(self.metrics.clone())
+    }
+}
+
+/// Export collected metrics in Prometheus text format
+pub struct PrometheusExport;
+
+impl ResponseType<PrometheusExport> for MetricsActor {
+    type Item = String;
+    type Error = ();
+}
+
+impl Handler<PrometheusExport> for MetricsActor {
+
+    fn handle(&mut self, _: PrometheusExport, _: &mut Context<Self>)
+              -> Response<Self, PrometheusExport>
+    {
+        Self::reply(self.metrics.to_prometheus())
+    }
+}
+
+/// Report runtime metric of the arbiter or actor
+///
+/// Counters are incremented by reported value, gauges are set to reported value.
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+pub struct Report(pub Source, pub Metric, pub u64);
+
+impl ResponseType<Report> for MetricsActor {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<Report> for MetricsActor {
+
+    fn handle(&mut self, msg: Report, _: &mut Context<Self>) -> Response<Self, Report> {
+        self.metrics.runtime.entry(msg.0).or_default().report(msg.1, msg.2);
+        Self::empty()
+    }
+}
+
+/// Message received by instrumented actor
+pub(crate) struct Received(&'static str);
+
+#[doc(hidden)]
+impl ResponseType<Received> for MetricsActor {
+    type Item = ();
+    type Error = ();
+}
+
+#[doc(hidden)]
+impl Handler<Received> for MetricsActor {
+
+    fn handle(&mut self, msg: Received, _: &mut Context<Self>) -> Response<Self, Received> {
+        self.metrics.actor(msg.0).received += 1;
+        Self::empty()
+    }
+}
+
+/// Message processed by instrumented actor
+pub(crate) struct Processed(&'static str, bool, Duration);
+
+#[doc(hidden)]
+impl ResponseType<Processed> for MetricsActor {
+    type Item = ();
+    type Error = ();
+}
+
+#[doc(hidden)]
+impl Handler<Processed> for MetricsActor {
+
+    fn handle(&mut self, msg: Processed, _: &mut Context<Self>) -> Response<Self, Processed> {
+        let metrics = self.metrics.actor(msg.0);
+        if msg.1 {
+            metrics.success += 1;
+        } else {
+            metrics.errors += 1;
+        }
+        metrics.latency.observe(msg.2);
+        Self::empty()
+    }
+}
@@ -1,7 +1,22 @@
 //! Helper actors
 
+pub mod cache;
+pub mod events;
+pub mod fanout;
+pub mod load_balancer;
+pub mod metrics;
+pub mod presence;
+pub mod rate_limiter;
+pub mod replay;
+pub mod router;
+pub mod subscription;
+pub mod supervision;
+
 #[cfg(feature="signal")]
 pub mod signal;
 
+#[cfg(feature="signal")]
+pub mod reload;
+
 #[cfg(feature="dns")]
 pub mod dns;
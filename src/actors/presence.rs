@@ -0,0 +1,213 @@
+//! Presence tracking
+//!
+//! `PresenceTracker` is a system service that keeps track of active members,
+//! i.e. connected users of chat server. Member joins with `PresenceJoin`
+//! message and leaves with `PresenceLeave` message. Every member receives
+//! `PresenceEvent` when other member joins, leaves or disconnects.
+//!
+//! Member is disconnected when its actor stops without `PresenceLeave`.
+//! Tracker checks members every second and before it handles any message.
+//! Tracker holds member's address, so actor that stops only after all of its
+//! addresses are dropped has to leave explicitly.
+//!
+//! # Examples
+//!
+//! ```rust
+//! extern crate actix;
+//! extern crate futures;
+//!
+//! use futures::Future;
+//! use actix::prelude::*;
+//! use actix::actors::presence::{GetPresence, PresenceEvent, PresenceJoin, PresenceTracker};
+//!
+//! struct User;
+//!
+//! impl Actor for User {
+//!     type Context = Context<Self>;
+//! }
+//!
+//! impl ResponseType<PresenceEvent> for User {
+//!     type Item = ();
+//!     type Error = ();
+//! }
+//!
+//! impl Handler<PresenceEvent> for User {
+//!     fn handle(&mut self, msg: PresenceEvent, _: &mut Context<Self>)
+//!               -> Response<Self, PresenceEvent>
+//!     {
+//!         println!("{:?}", msg);
+//!         Self::empty()
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let sys = System::new("test");
+//!
+//!     let tracker = Arbiter::system_registry().get::<PresenceTracker>();
+//!     let user: SyncAddress<_> = User.start();
+//!     tracker.send(PresenceJoin{id: "alice".to_owned(), addr: user.recipient()});
+//!
+//!     Arbiter::handle().spawn(
+//!         tracker.call_fut(GetPresence("alice".to_owned()))
+//!             .then(|res| {
+//!                 let info = res.unwrap().unwrap().unwrap();
+//!                 assert_eq!(info.id, "alice");
+//!                 Arbiter::system().send(msgs::SystemExit(0));
+//!                 Ok(())
+//!             }));
+//!
+//!     sys.run();
+//! }
+//! ```
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use prelude::*;
+
+/// Presence change of the member
+#[derive(Clone, Debug, PartialEq)]
+pub enum PresenceEvent {
+    /// Member with the given id joined
+    Joined(String),
+    /// Member with the given id left
+    Left(String),
+    /// Actor of the member with the given id stopped without leaving
+    Disconnected(String),
+}
+
+/// Information about active member
+#[derive(Clone, Debug)]
+pub struct PresenceInfo {
+    /// Member id
+    pub id: String,
+    /// Type name of the member's actor
+    pub actor: &'static str,
+    /// Time member joined at
+    pub since: Instant,
+}
+
+struct Member {
+    addr: Recipient<PresenceEvent>,
+    since: Instant,
+}
+
+/// Service that tracks active members
+#[derive(Default)]
+pub struct PresenceTracker {
+    members: HashMap<String, Member>,
+}
+
+impl PresenceTracker {
+
+    /// Send event to all members except `id`
+    fn broadcast(&self, id: &str, event: &PresenceEvent) {
+        for (member_id, member) in &self.members {
+            if member_id != id {
+                let _ = member.addr.send(event.clone());
+            }
+        }
+    }
+
+    /// Remove members which actors are stopped
+    fn check(&mut self) {
+        let disconnected: Vec<_> = self.members.iter()
+            .filter(|&(_, member)| !member.addr.connected())
+            .map(|(id, _)| id.clone())
+            .collect();
+        for id in disconnected {
+            self.members.remove(&id);
+            info!("Presence member {:?} disconnected", id);
+            self.broadcast(&id, &PresenceEvent::Disconnected(id.clone()));
+        }
+    }
+
+    fn check_later(&self, ctx: &mut Context<Self>) {
+        ctx.run_later(Duration::from_secs(1), |act, ctx| {
+            act.check();
+            act.check_later(ctx);
+        });
+    }
+}
+
+impl Actor for PresenceTracker {
+    type Context = Context<Self>;
+}
+
+impl Supervised for PresenceTracker {}
+
+impl SystemService for PresenceTracker {
+
+    fn service_started(&mut self, ctx: &mut Context<Self>) {
+        self.check_later(ctx);
+    }
+}
+
+/// Join presence tracker
+///
+/// Member with the same id gets replaced.
+pub struct PresenceJoin {
+    /// Member id
+    pub id: String,
+    /// Member's address, it receives presence events of other members
+    pub addr: Recipient<PresenceEvent>,
+}
+
+impl ResponseType<PresenceJoin> for PresenceTracker {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<PresenceJoin> for PresenceTracker {
+
+    fn handle(&mut self, msg: PresenceJoin, _: &mut Context<Self>)
+              -> Response<Self, PresenceJoin>
+    {
+        self.check();
+        self.broadcast(&msg.id, &PresenceEvent::Joined(msg.id.clone()));
+        self.members.insert(msg.id, Member{addr: msg.addr, since: Arbiter::now()});
+        Self::empty()
+    }
+}
+
+/// Leave presence tracker
+pub struct PresenceLeave(pub String);
+
+impl ResponseType<PresenceLeave> for PresenceTracker {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<PresenceLeave> for PresenceTracker {
+
+    fn handle(&mut self, msg: PresenceLeave, _: &mut Context<Self>)
+              -> Response<Self, PresenceLeave>
+    {
+        self.check();
+        if self.members.remove(&msg.0).is_some() {
+            self.broadcast(&msg.0, &PresenceEvent::Left(msg.0.clone()));
+        }
+        Self::empty()
+    }
+}
+
+/// Get information about member, `None` if member is not active
+pub struct GetPresence(pub String);
+
+impl ResponseType<GetPresence> for PresenceTracker {
+    type Item = Option<PresenceInfo>;
+    type Error = ();
+}
+
+impl Handler<GetPresence> for PresenceTracker {
+
+    fn handle(&mut self, msg: GetPresence, _: &mut Context<Self>)
+              -> Response<Self, GetPresence>
+    {
+        self.check();
+        Self::reply(self.members.get(&msg.0).map(|member| PresenceInfo {
+            id: msg.0.clone(),
+            actor: member.addr.type_name(),
+            since: member.since,
+        }))
+    }
+}
@@ -0,0 +1,260 @@
+//! Adaptive rate limiting
+//!
+//! `AdaptiveRateLimiter<A>` forwards messages to actor `A` at limited rate
+//! and adjusts the rate to the target's response time. Limiter tracks
+//! exponential moving average of handler latencies, if average exceeds
+//! threshold, rate is decreased multiplicatively, otherwise it is increased
+//! additively back toward max rate (AIMD). Messages that exceed current rate
+//! are queued by limiter.
+//!
+//! Limiter replies with target's response, handler error is reported as
+//! `CallError::Handler`, stopped target as `CallError::MailboxClosed`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! extern crate actix;
+//! extern crate futures;
+//!
+//! use std::time::Duration;
+//! use futures::{future, Future};
+//! use actix::prelude::*;
+//! use actix::actors::rate_limiter::AdaptiveRateLimiter;
+//!
+//! struct Query(u32);
+//!
+//! struct Database;
+//!
+//! impl Actor for Database {
+//!     type Context = Context<Self>;
+//! }
+//!
+//! impl ResponseType<Query> for Database {
+//!     type Item = u32;
+//!     type Error = ();
+//! }
+//!
+//! impl Handler<Query> for Database {
+//!     fn handle(&mut self, msg: Query, _: &mut Context<Self>) -> Response<Self, Query> {
+//!         Self::reply(msg.0 * 2)
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let sys = System::new("test");
+//!
+//!     let db: SyncAddress<_> = Database.start();
+//!
+//!     // at most 100 queries per second, slow down if queries take
+//!     // longer than 50 milliseconds on average
+//!     let limiter: Address<_> =
+//!         AdaptiveRateLimiter::new(db, 100.0, Duration::from_millis(50)).start();
+//!
+//!     Arbiter::handle().spawn(
+//!         future::join_all((0..5).map(|i| limiter.call_fut(Query(i))).collect::<Vec<_>>())
+//!             .then(|res| {
+//!                 let res: Vec<_> = res.unwrap().into_iter().map(|r| r.unwrap()).collect();
+//!                 assert_eq!(res, vec![0, 2, 4, 6, 8]);
+//!                 Arbiter::system().send(msgs::SystemExit(0));
+//!                 Ok(())
+//!             }));
+//!
+//!     sys.run();
+//! }
+//! ```
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+use futures::unsync::oneshot::{channel, Sender};
+
+use prelude::*;
+use address::CallError;
+use envelope::ToEnvelope;
+
+/// Message waiting for its turn
+trait Pending<A: Actor> {
+    fn dispatch(self: Box<Self>,
+                limiter: &mut AdaptiveRateLimiter<A>,
+                ctx: &mut Context<AdaptiveRateLimiter<A>>);
+}
+
+type LimiterResult<A, M> =
+    Result<<A as ResponseType<M>>::Item, CallError<<A as ResponseType<M>>::Error>>;
+
+struct PendingMessage<A: Actor + ResponseType<M>, M> {
+    msg: M,
+    tx: Sender<LimiterResult<A, M>>,
+}
+
+impl<A, M> Pending<A> for PendingMessage<A, M>
+    where A: Handler<M>,
+          <A as ResponseType<M>>::Item: Send,
+          <A as ResponseType<M>>::Error: Send,
+          A: ToEnvelope<A, <A as Actor>::Context, M>,
+          M: Send + 'static,
+{
+    fn dispatch(self: Box<Self>,
+                limiter: &mut AdaptiveRateLimiter<A>,
+                ctx: &mut Context<AdaptiveRateLimiter<A>>)
+    {
+        let PendingMessage{msg, tx} = *self;
+        let start = Arbiter::now();
+
+        limiter.target.call_fut(msg).actfuture()
+            .then(move |res, act: &mut AdaptiveRateLimiter<A>, _: &mut Context<_>| {
+                let res = match res {
+                    Ok(res) => {
+                        act.update(Arbiter::now() - start);
+                        res.map_err(CallError::Handler)
+                    }
+                    Err(_) => Err(CallError::MailboxClosed),
+                };
+                let _ = tx.send(res);
+                fut::ok(())
+            })
+            .spawn(ctx);
+    }
+}
+
+/// Actor that forwards messages to actor `A` at adaptive rate
+pub struct AdaptiveRateLimiter<A: Actor> {
+    target: SyncAddress<A>,
+    rate: f64,
+    min_rate: f64,
+    max_rate: f64,
+    increase: f64,
+    decrease: f64,
+    smoothing: f64,
+    threshold: Duration,
+    latency: Option<f64>,
+    queue: VecDeque<Box<Pending<A>>>,
+    next: Instant,
+    waiting: bool,
+}
+
+impl<A: Actor> AdaptiveRateLimiter<A> {
+
+    /// Create limiter that forwards at most `max_rate` messages per second
+    /// to `target`. Rate is decreased while average response time of the
+    /// target exceeds `threshold`.
+    pub fn new(target: SyncAddress<A>, max_rate: f64, threshold: Duration)
+               -> AdaptiveRateLimiter<A>
+    {
+        AdaptiveRateLimiter {
+            target: target,
+            rate: max_rate,
+            min_rate: 1.0,
+            max_rate: max_rate,
+            increase: 1.0,
+            decrease: 0.5,
+            smoothing: 0.2,
+            threshold: threshold,
+            latency: None,
+            queue: VecDeque::new(),
+            next: Arbiter::now(),
+            waiting: false,
+        }
+    }
+
+    /// Set lowest rate limiter falls back to, default is 1 message per second
+    pub fn min_rate(mut self, rate: f64) -> Self {
+        self.min_rate = rate;
+        self
+    }
+
+    /// Set how many messages per second are added to the rate after
+    /// fast response, default is 1
+    pub fn increase(mut self, step: f64) -> Self {
+        self.increase = step;
+        self
+    }
+
+    /// Set factor rate is multiplied by after slow response, default is 0.5
+    pub fn decrease(mut self, factor: f64) -> Self {
+        self.decrease = factor;
+        self
+    }
+
+    /// Set weight of the latest response time in moving average,
+    /// default is 0.2
+    pub fn smoothing(mut self, weight: f64) -> Self {
+        self.smoothing = weight;
+        self
+    }
+
+    /// Update average response time and adjust rate
+    fn update(&mut self, latency: Duration) {
+        let sample = as_secs(latency);
+        let avg = match self.latency {
+            Some(avg) => avg + self.smoothing * (sample - avg),
+            None => sample,
+        };
+        self.latency = Some(avg);
+
+        if avg > as_secs(self.threshold) {
+            self.rate = (self.rate * self.decrease).max(self.min_rate);
+        } else {
+            self.rate = (self.rate + self.increase).min(self.max_rate);
+        }
+    }
+
+    /// Forward queued messages while rate allows
+    fn drain(&mut self, ctx: &mut Context<Self>) {
+        while !self.queue.is_empty() {
+            let now = Arbiter::now();
+            if self.next > now {
+                if !self.waiting {
+                    self.waiting = true;
+                    ctx.run_later(self.next - now, |act, ctx| {
+                        act.waiting = false;
+                        act.drain(ctx);
+                    });
+                }
+                return
+            }
+
+            self.next = now + interval(self.rate);
+            if let Some(pending) = self.queue.pop_front() {
+                pending.dispatch(self, ctx);
+            }
+        }
+    }
+}
+
+fn as_secs(dur: Duration) -> f64 {
+    dur.as_secs() as f64 + f64::from(dur.subsec_nanos()) / 1_000_000_000.0
+}
+
+fn interval(rate: f64) -> Duration {
+    let nanos = (1_000_000_000.0 / rate) as u64;
+    Duration::new(nanos / 1_000_000_000, (nanos % 1_000_000_000) as u32)
+}
+
+impl<A: Actor> Actor for AdaptiveRateLimiter<A> {
+    type Context = Context<Self>;
+}
+
+impl<A, M> ResponseType<M> for AdaptiveRateLimiter<A> where A: Actor + ResponseType<M> {
+    type Item = <A as ResponseType<M>>::Item;
+    type Error = CallError<<A as ResponseType<M>>::Error>;
+}
+
+/// Forward message to the target once rate allows
+impl<A, M> Handler<M> for AdaptiveRateLimiter<A>
+    where A: Handler<M>,
+          <A as ResponseType<M>>::Item: Send,
+          <A as ResponseType<M>>::Error: Send,
+          A: ToEnvelope<A, <A as Actor>::Context, M>,
+          M: Send + 'static,
+{
+    fn handle(&mut self, msg: M, ctx: &mut Context<Self>) -> Response<Self, M> {
+        let (tx, rx) = channel();
+        self.queue.push_back(Box::new(PendingMessage::<A, M>{msg: msg, tx: tx}));
+        self.drain(ctx);
+
+        Self::async_reply(
+            rx.actfuture().then(|res, _: &mut Self, _: &mut Context<Self>| match res {
+                Ok(res) => fut::result(res),
+                Err(_) => fut::err(CallError::MailboxClosed),
+            }))
+    }
+}
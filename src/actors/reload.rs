@@ -0,0 +1,331 @@
+//! Configuration reloading on `SIGHUP`
+//!
+//! `ReloadableConfig` actor reads config file with user provided parse
+//! function and broadcasts `ConfigReloaded` message to subscribers every time
+//! process receives `SIGHUP` signal. Actor subscribes itself to
+//! `ProcessSignals` service on start. Reload could also be requested
+//! with `Reload` message.
+//!
+//! `DebouncedReload` actor coalesces bursts of `SIGHUP` signals into single
+//! `Reload` message.
+//!
+//! If file can not be read or parsed, error is logged and subscribers keep
+//! previous configuration. New subscriber receives current configuration
+//! immediately.
+//!
+//! # Examples
+//!
+//! ```rust
+//! extern crate actix;
+//!
+//! use std::io::Write;
+//! use actix::prelude::*;
+//! use actix::actors::reload::{ConfigReloaded, ReloadableConfig, Subscribe};
+//! use actix::actors::signal::{Signal, SignalType};
+//!
+//! struct Server;
+//!
+//! impl Actor for Server {
+//!     type Context = Context<Self>;
+//! }
+//!
+//! impl ResponseType<ConfigReloaded<u16>> for Server {
+//!     type Item = ();
+//!     type Error = ();
+//! }
+//!
+//! impl Handler<ConfigReloaded<u16>> for Server {
+//!     fn handle(&mut self, msg: ConfigReloaded<u16>, _: &mut Context<Self>)
+//!               -> Response<Self, ConfigReloaded<u16>>
+//!     {
+//!         println!("port: {}", msg.0);
+//!         if *msg.0 == 8081 {
+//!             Arbiter::system().send(msgs::SystemExit(0));
+//!         }
+//!         Self::empty()
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let path = std::env::temp_dir().join("actix-reload-example.conf");
+//!     std::fs::File::create(&path).unwrap().write_all(b"8080").unwrap();
+//!
+//!     let sys = System::new("test");
+//!
+//!     let config: SyncAddress<_> =
+//!         ReloadableConfig::new(&path, |s| s.trim().parse::<u16>()).start();
+//!     let server: SyncAddress<_> = Server.start();
+//!     config.send(Subscribe(server.subscriber()));
+//!
+//!     // emulate SIGHUP after config file change
+//!     std::fs::File::create(&path).unwrap().write_all(b"8081").unwrap();
+//!     config.send(Signal::new(SignalType::Hup));
+//!
+//!     sys.run();
+//! }
+//! ```
+use std;
+use std::fmt;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use prelude::*;
+use actors::signal::{self, Signal, SignalType};
+
+type ParseFn<C> = Box<Fn(&str) -> Result<C, String>>;
+
+/// Configuration has been reloaded
+pub struct ConfigReloaded<C>(pub Arc<C>);
+
+impl<C> Clone for ConfigReloaded<C> {
+    fn clone(&self) -> Self {
+        ConfigReloaded(Arc::clone(&self.0))
+    }
+}
+
+/// Actor that reloads configuration file on `SIGHUP`
+pub struct ReloadableConfig<C: Send + Sync + 'static> {
+    path: PathBuf,
+    parse: ParseFn<C>,
+    config: Option<Arc<C>>,
+    subscribers: Vec<Box<Subscriber<ConfigReloaded<C>> + Send>>,
+    signals: bool,
+}
+
+impl<C: Send + Sync + 'static> ReloadableConfig<C> {
+
+    /// Create actor for config file `path`, file content is parsed with `parse`
+    pub fn new<P, F, E>(path: P, parse: F) -> ReloadableConfig<C>
+        where P: AsRef<Path>,
+              F: Fn(&str) -> Result<C, E> + 'static,
+              E: fmt::Display,
+    {
+        ReloadableConfig {
+            path: path.as_ref().to_owned(),
+            parse: Box::new(move |s| parse(s).map_err(|e| e.to_string())),
+            config: None,
+            subscribers: Vec::new(),
+            signals: true,
+        }
+    }
+
+    /// Reload configuration on `SIGHUP`, enabled by default. Disable it
+    /// if reloads are requested by `DebouncedReload`.
+    pub fn signals(mut self, enabled: bool) -> Self {
+        self.signals = enabled;
+        self
+    }
+
+    /// Read and parse config file, broadcast new configuration on success
+    fn reload(&mut self) -> Result<Arc<C>, String> {
+        let mut content = String::new();
+        File::open(&self.path)
+            .and_then(|mut f| f.read_to_string(&mut content))
+            .map_err(|e| format!("Can not read config {:?}: {}", self.path, e))?;
+        let config = Arc::new((self.parse)(&content)
+            .map_err(|e| format!("Can not parse config {:?}: {}", self.path, e))?);
+        self.config = Some(Arc::clone(&config));
+
+        // disconnected subscribers get dropped
+        let subscribers = std::mem::replace(&mut self.subscribers, Vec::new());
+        for subscr in subscribers {
+            if subscr.send(ConfigReloaded(Arc::clone(&config))).is_ok() {
+                self.subscribers.push(subscr);
+            }
+        }
+        Ok(config)
+    }
+}
+
+impl<C: Send + Sync + 'static> Actor for ReloadableConfig<C> {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Context<Self>) {
+        if let Err(err) = self.reload() {
+            error!("{}", err);
+        }
+
+        if self.signals {
+            let slf: SyncAddress<_> = ctx.address();
+            signal::subscribe(slf.subscriber())
+        }
+    }
+}
+
+impl<C: Send + Sync + 'static> ResponseType<Signal> for ReloadableConfig<C> {
+    type Item = ();
+    type Error = ();
+}
+
+/// Reload configuration on `SIGHUP`
+impl<C: Send + Sync + 'static> Handler<Signal> for ReloadableConfig<C> {
+
+    fn handle(&mut self, msg: Signal, _: &mut Context<Self>) -> Response<Self, Signal> {
+        if msg.0 == SignalType::Hup {
+            info!("SIGHUP received, reloading {:?}", self.path);
+            if let Err(err) = self.reload() {
+                error!("{}", err);
+            }
+        }
+        Self::empty()
+    }
+}
+
+/// Reload configuration
+///
+/// Actor responds with new configuration, or with error description if
+/// config file can not be read or parsed.
+pub struct Reload;
+
+impl<C: Send + Sync + 'static> ResponseType<Reload> for ReloadableConfig<C> {
+    type Item = Arc<C>;
+    type Error = String;
+}
+
+impl<C: Send + Sync + 'static> Handler<Reload> for ReloadableConfig<C> {
+
+    fn handle(&mut self, _: Reload, _: &mut Context<Self>) -> Response<Self, Reload> {
+        Self::reply_result(self.reload())
+    }
+}
+
+/// Subscribe to configuration changes
+pub struct Subscribe<C>(pub Box<Subscriber<ConfigReloaded<C>> + Send>);
+
+impl<C: Send + Sync + 'static> ResponseType<Subscribe<C>> for ReloadableConfig<C> {
+    type Item = ();
+    type Error = ();
+}
+
+/// Add subscriber, subscriber receives current configuration immediately
+impl<C: Send + Sync + 'static> Handler<Subscribe<C>> for ReloadableConfig<C> {
+
+    fn handle(&mut self, msg: Subscribe<C>, _: &mut Context<Self>) -> Response<Self, Subscribe<C>>
+    {
+        if let Some(ref config) = self.config {
+            if msg.0.send(ConfigReloaded(Arc::clone(config))).is_err() {
+                return Self::empty()
+            }
+        }
+        self.subscribers.push(msg.0);
+        Self::empty()
+    }
+}
+
+/// Actor that debounces `SIGHUP` triggered reloads
+///
+/// Each `SIGHUP` restarts debounce window, once window elapses without
+/// further signals, single `Reload` message is sent to the target. Actor
+/// subscribes itself to `ProcessSignals` service on start and stops
+/// if target is gone. Default window is 500 milliseconds.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate actix;
+///
+/// use std::time::Duration;
+/// use actix::prelude::*;
+/// use actix::actors::reload::{DebouncedReload, Reload};
+/// use actix::actors::signal::{Signal, SignalType};
+///
+/// struct App(usize);
+///
+/// impl Actor for App {
+///     type Context = Context<Self>;
+/// }
+///
+/// impl ResponseType<Reload> for App {
+///     type Item = ();
+///     type Error = ();
+/// }
+///
+/// impl Handler<Reload> for App {
+///     fn handle(&mut self, _: Reload, ctx: &mut Context<Self>) -> Response<Self, Reload> {
+///         self.0 += 1;
+///         println!("reload #{}", self.0);
+///         ctx.run_later(Duration::from_millis(100), |act, _| {
+///             // burst of signals caused single reload
+///             assert_eq!(act.0, 1);
+///             Arbiter::system().send(msgs::SystemExit(0));
+///         });
+///         Self::empty()
+///     }
+/// }
+///
+/// fn main() {
+///     let sys = System::new("test");
+///
+///     let app: SyncAddress<_> = App(0).start();
+///     let reload: SyncAddress<_> = DebouncedReload::new(app.subscriber())
+///         .window(Duration::from_millis(50))
+///         .start();
+///
+///     // emulate burst of SIGHUP signals
+///     for _ in 0..3 {
+///         reload.send(Signal::new(SignalType::Hup));
+///     }
+///
+///     sys.run();
+/// }
+/// ```
+pub struct DebouncedReload {
+    target: Box<Subscriber<Reload> + Send>,
+    window: Duration,
+    timer: Option<SpawnHandle>,
+}
+
+impl DebouncedReload {
+
+    /// Create actor that sends `Reload` messages to `target`
+    pub fn new(target: Box<Subscriber<Reload> + Send>) -> DebouncedReload {
+        DebouncedReload {
+            target: target,
+            window: Duration::from_millis(500),
+            timer: None,
+        }
+    }
+
+    /// Set debounce window
+    pub fn window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+}
+
+impl Actor for DebouncedReload {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Context<Self>) {
+        let slf: SyncAddress<_> = ctx.address();
+        signal::subscribe(slf.subscriber())
+    }
+}
+
+impl ResponseType<Signal> for DebouncedReload {
+    type Item = ();
+    type Error = ();
+}
+
+/// Restart debounce window on `SIGHUP`
+impl Handler<Signal> for DebouncedReload {
+
+    fn handle(&mut self, msg: Signal, ctx: &mut Context<Self>) -> Response<Self, Signal> {
+        if msg.0 == SignalType::Hup {
+            if let Some(timer) = self.timer.take() {
+                ctx.cancel_future(timer);
+            }
+            self.timer = Some(ctx.run_later(self.window, |act, ctx| {
+                act.timer = None;
+                info!("Reload requested after SIGHUP");
+                if act.target.send(Reload).is_err() {
+                    ctx.stop();
+                }
+            }));
+        }
+        Self::empty()
+    }
+}
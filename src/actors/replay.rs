@@ -0,0 +1,158 @@
+//! Replay buffer
+//!
+//! `ReplayBuffer<M>` is an actor that broadcasts published messages to
+//! subscribers and retains the most recent of them. New subscriber receives
+//! retained history first and live messages after that, in publish order.
+//! History is bounded by number of messages and optionally by message age.
+//!
+//! # Examples
+//!
+//! ```rust
+//! extern crate actix;
+//!
+//! use actix::prelude::*;
+//! use actix::actors::replay::{ReplayBuffer, Publish, Subscribe};
+//!
+//! #[derive(Clone)]
+//! struct ConfigReloaded(u32);
+//!
+//! struct Listener;
+//!
+//! impl Actor for Listener {
+//!     type Context = Context<Self>;
+//! }
+//!
+//! impl ResponseType<ConfigReloaded> for Listener {
+//!     type Item = ();
+//!     type Error = ();
+//! }
+//!
+//! impl Handler<ConfigReloaded> for Listener {
+//!     fn handle(&mut self, msg: ConfigReloaded, _: &mut Context<Self>)
+//!               -> Response<Self, ConfigReloaded>
+//!     {
+//!         println!("config version: {}", msg.0);
+//!         Arbiter::system().send(msgs::SystemExit(0));
+//!         Self::empty()
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let sys = System::new("test");
+//!
+//!     // keep last config reload only
+//!     let buffer: SyncAddress<_> = ReplayBuffer::new(1).start();
+//!     buffer.send(Publish(ConfigReloaded(1)));
+//!
+//!     // late subscriber still receives last reload
+//!     let listener: SyncAddress<_> = Listener.start();
+//!     buffer.send(Subscribe(listener.subscriber()));
+//!
+//!     sys.run();
+//! }
+//! ```
+use std;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use prelude::*;
+
+/// Actor that replays retained messages to late subscribers
+pub struct ReplayBuffer<M> {
+    capacity: usize,
+    max_age: Option<Duration>,
+    history: VecDeque<(Instant, M)>,
+    subscribers: Vec<Box<Subscriber<M> + Send>>,
+}
+
+impl<M> ReplayBuffer<M> where M: Clone + Send + 'static {
+
+    /// Create buffer that retains last `capacity` messages
+    pub fn new(capacity: usize) -> ReplayBuffer<M> {
+        ReplayBuffer {
+            capacity: capacity,
+            max_age: None,
+            history: VecDeque::new(),
+            subscribers: Vec::new(),
+        }
+    }
+
+    /// Create buffer that retains last `capacity` messages,
+    /// messages older than `max_age` are not replayed.
+    pub fn with_max_age(capacity: usize, max_age: Duration) -> ReplayBuffer<M> {
+        let mut buffer = ReplayBuffer::new(capacity);
+        buffer.max_age = Some(max_age);
+        buffer
+    }
+
+    /// Drop messages that exceed retention policy
+    fn expire(&mut self) {
+        while self.history.len() > self.capacity {
+            self.history.pop_front();
+        }
+        if let Some(max_age) = self.max_age {
+            let now = Arbiter::now();
+            while self.history.front().map(|&(t, _)| now - t > max_age).unwrap_or(false) {
+                self.history.pop_front();
+            }
+        }
+    }
+}
+
+impl<M> Actor for ReplayBuffer<M> where M: Clone + Send + 'static {
+    type Context = Context<Self>;
+}
+
+/// Publish message to all current subscribers and retain it for new ones
+pub struct Publish<M>(pub M);
+
+impl<M> ResponseType<Publish<M>> for ReplayBuffer<M> where M: Clone + Send + 'static {
+    type Item = ();
+    type Error = ();
+}
+
+impl<M> Handler<Publish<M>> for ReplayBuffer<M> where M: Clone + Send + 'static {
+
+    fn handle(&mut self, msg: Publish<M>, _: &mut Context<Self>) -> Response<Self, Publish<M>>
+    {
+        // disconnected subscribers get dropped
+        let subscribers = std::mem::replace(&mut self.subscribers, Vec::new());
+        for subscr in subscribers {
+            if subscr.send(msg.0.clone()).is_ok() {
+                self.subscribers.push(subscr);
+            }
+        }
+
+        self.history.push_back((Arbiter::now(), msg.0));
+        self.expire();
+        Self::empty()
+    }
+}
+
+/// Subscribe to published messages.
+///
+/// Retained messages are sent to subscriber before any live message.
+pub struct Subscribe<M>(pub Box<Subscriber<M> + Send>);
+
+impl<M> ResponseType<Subscribe<M>> for ReplayBuffer<M> where M: Clone + Send + 'static {
+    type Item = ();
+    type Error = ();
+}
+
+impl<M> Handler<Subscribe<M>> for ReplayBuffer<M> where M: Clone + Send + 'static {
+
+    fn handle(&mut self, msg: Subscribe<M>, _: &mut Context<Self>) -> Response<Self, Subscribe<M>>
+    {
+        self.expire();
+
+        // history is sent from the same handler, so live messages
+        // that are published later are queued after it
+        for &(_, ref item) in &self.history {
+            if msg.0.send(item.clone()).is_err() {
+                return Self::empty()
+            }
+        }
+        self.subscribers.push(msg.0);
+        Self::empty()
+    }
+}
@@ -0,0 +1,155 @@
+//! Content based message routing
+//!
+//! Rust dispatches `Handler<M>` by message type only. `Router<M>` is an actor
+//! that inspects message content and forwards message to the first route
+//! whose predicate matches. Routes are checked in the same order they were
+//! added, message that does not match any route goes to default route.
+//! Disconnected routes get removed.
+//!
+//! # Examples
+//!
+//! ```rust
+//! extern crate actix;
+//!
+//! use actix::prelude::*;
+//! use actix::actors::router::Router;
+//!
+//! struct Order {amount: u64}
+//!
+//! struct Desk(&'static str);
+//!
+//! impl Actor for Desk {
+//!     type Context = Context<Self>;
+//! }
+//!
+//! impl ResponseType<Order> for Desk {
+//!     type Item = ();
+//!     type Error = ();
+//! }
+//!
+//! impl Handler<Order> for Desk {
+//!     fn handle(&mut self, msg: Order, _: &mut Context<Self>) -> Response<Self, Order> {
+//!         println!("{} desk: {}", self.0, msg.amount);
+//!         Arbiter::system().send(msgs::SystemExit(0));
+//!         Self::empty()
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let sys = System::new("test");
+//!
+//!     let large: SyncAddress<_> = Desk("large").start();
+//!     let small: SyncAddress<_> = Desk("small").start();
+//!
+//!     let router: Address<_> = Router::new()
+//!         .add_route(|order: &Order| order.amount > 1000, large)
+//!         .default_route(small)
+//!         .start();
+//!     router.send(Order{amount: 10});
+//!
+//!     sys.run();
+//! }
+//! ```
+use std::any::type_name;
+
+use prelude::*;
+
+type Route<M> = (Box<Fn(&M) -> bool>, Box<Subscriber<M>>);
+
+/// Actor that forwards messages based on their content
+pub struct Router<M: 'static> {
+    routes: Vec<Route<M>>,
+    default: Option<Box<Subscriber<M>>>,
+}
+
+impl<M: 'static> Default for Router<M> {
+    fn default() -> Self {
+        Router::new()
+    }
+}
+
+impl<M: 'static> Router<M> {
+
+    /// Create router without routes
+    pub fn new() -> Router<M> {
+        Router {
+            routes: Vec::new(),
+            default: None,
+        }
+    }
+
+    /// Forward messages that match `pred` to `addr`
+    pub fn add_route<F, S>(mut self, pred: F, addr: S) -> Self
+        where F: Fn(&M) -> bool + 'static,
+              S: Subscriber<M> + 'static
+    {
+        self.routes.push((Box::new(pred), Box::new(addr)));
+        self
+    }
+
+    /// Forward messages that do not match any route to `addr`
+    pub fn default_route<S>(mut self, addr: S) -> Self where S: Subscriber<M> + 'static
+    {
+        self.default = Some(Box::new(addr));
+        self
+    }
+
+    /// Number of active routes, default route is not counted
+    pub fn routes(&self) -> usize {
+        self.routes.len()
+    }
+
+    fn route(&mut self, mut msg: M) -> Result<(), M> {
+        let mut idx = 0;
+        while idx < self.routes.len() {
+            if (self.routes[idx].0)(&msg) {
+                match self.routes[idx].1.send(msg) {
+                    Ok(()) => return Ok(()),
+                    Err(m) => {
+                        // route is disconnected, try next one
+                        let _ = self.routes.remove(idx);
+                        msg = m;
+                        continue
+                    }
+                }
+            }
+            idx += 1;
+        }
+
+        if let Some(default) = self.default.take() {
+            match default.send(msg) {
+                Ok(()) => {
+                    self.default = Some(default);
+                    Ok(())
+                }
+                Err(m) => Err(m),
+            }
+        } else {
+            Err(msg)
+        }
+    }
+}
+
+impl<M: 'static> Actor for Router<M> {
+    type Context = Context<Self>;
+}
+
+impl<M: 'static> ResponseType<M> for Router<M> {
+    type Item = ();
+    type Error = ();
+}
+
+/// Forward message to matching route, responds with error
+/// if message can not be delivered to any route.
+impl<M: 'static> Handler<M> for Router<M> {
+
+    fn handle(&mut self, msg: M, _: &mut Context<Self>) -> Response<Self, M> {
+        match self.route(msg) {
+            Ok(()) => Self::empty(),
+            Err(_) => {
+                debug!("Router drops message, no route for {}", type_name::<M>());
+                Self::reply_error(())
+            }
+        }
+    }
+}
@@ -4,6 +4,14 @@
 //! `ProcessSignals` actor sends `Signal` message to all subscriber. To subscriber,
 //! send `Subscribe` message to `ProcessSignals` actor.
 //!
+//! Broadcast never waits for a slow subscriber: signal is sent with
+//! `Subscriber::try_send()`, delivery to subscriber with full buffer is
+//! retried later, so other subscribers get signal without delay.
+//! Only `SystemExit` processing waits until all subscribers
+//! handle `SIGINT`, `SIGTERM` or `SIGQUIT` signal, but not longer than
+//! system shutdown timeout, see `System::set_shutdown_timeout()`. Second
+//! terminal signal stops waiting, so stuck subscriber can not block exit.
+//!
 //! # Examples
 //!
 //! ```rust
@@ -61,7 +69,7 @@
 //!    // send SIGTERM
 //!    std::thread::spawn(move || {
 //!       // emulate SIGNTERM
-//!       addr.send(signal::Signal(signal::SignalType::Term));
+//!       addr.send(signal::Signal::new(signal::SignalType::Term));
 //!    });
 //!
 //!    // Run system, this function blocks until system runs
@@ -71,12 +79,22 @@
 //! ```
 use std;
 use std::io;
+use std::rc::Rc;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use libc;
-use futures::{Future, Stream};
+use futures::{future, Future, Stream};
+use futures::future::Loop;
+use futures::sync::oneshot;
 use tokio_signal;
 use tokio_signal::unix;
 
 use prelude::*;
+use address::TrySendError;
+use clock::Delay;
+
+/// Delay between delivery attempts to subscriber with full buffer, in milliseconds
+const RETRY_DELAY: u64 = 10;
 
 /// Different types of process signals
 #[derive(PartialEq, Clone, Copy, Debug)]
@@ -91,19 +109,143 @@ pub enum SignalType {
     Quit,
     /// SIGCHILD
     Child,
+    /// Raw signal number installed with `AddSignals` message, i.e. `SIGWINCH`
+    Other(i32),
 }
 
 /// Process signal message
-pub struct Signal(pub SignalType);
+///
+/// Signal keeps the time when it was received by `ProcessSignals` actor,
+/// see `Signal::received()` and `Signal::delay()`.
+#[derive(Copy, Clone, Debug)]
+pub struct Signal(pub SignalType, Instant);
+
+impl Signal {
+    /// Create signal message received at current time
+    pub fn new(sig: SignalType) -> Signal {
+        Signal(sig, Instant::now())
+    }
+
+    /// Time when signal was received by `ProcessSignals` actor
+    pub fn received(&self) -> Instant {
+        self.1
+    }
+
+    /// Time elapsed since signal was received by `ProcessSignals` actor.
+    ///
+    /// Large delay usually means that event loop was blocked.
+    pub fn delay(&self) -> Duration {
+        self.1.elapsed()
+    }
+}
+
+/// Signals are equal if they have same type, receive time is not compared
+impl PartialEq for Signal {
+    fn eq(&self, other: &Signal) -> bool {
+        self.0 == other.0
+    }
+}
+
+/// Signal with the time it entered signal stream
+pub(crate) struct SignalEvent(SignalType, Instant);
+
+impl SignalEvent {
+    fn new(sig: SignalType) -> SignalEvent {
+        SignalEvent(sig, Instant::now())
+    }
+}
+
+/// Set of signals that `ProcessSignals` actor registers handlers for.
+///
+/// Default configuration contains `SIGHUP`, `SIGINT`, `SIGTERM`, `SIGQUIT`
+/// and `SIGCHLD`. Signals that are not listed keep default process behavior.
+#[derive(Clone, Debug)]
+pub struct ProcessSignalsConfig {
+    /// Signals to handle
+    pub signals: Vec<SignalType>,
+}
+
+impl Default for ProcessSignalsConfig {
+    fn default() -> Self {
+        ProcessSignalsConfig {
+            signals: vec![SignalType::Hup, SignalType::Int, SignalType::Term,
+                          SignalType::Quit, SignalType::Child],
+        }
+    }
+}
 
 /// An actor implementation of Unix signal handling
 pub struct ProcessSignals {
-    subscribers: Vec<Box<Subscriber<Signal>>>,
+    signals: Vec<SignalType>,
+    subscribers: Vec<Rc<Box<Subscriber<Signal>>>>,
+    /// Resolves deferral of pending terminal signal broadcast
+    force: Option<oneshot::Sender<()>>,
 }
 
 impl Default for ProcessSignals {
     fn default() -> Self {
-        ProcessSignals{subscribers: Vec::new()}
+        ProcessSignals::new(ProcessSignalsConfig::default())
+    }
+}
+
+impl ProcessSignals {
+    /// Create signals actor that handles only signals listed in `config`.
+    ///
+    /// ```rust
+    /// # extern crate actix;
+    /// use actix::prelude::*;
+    /// use actix::actors::signal::{ProcessSignals, ProcessSignalsConfig, SignalType};
+    ///
+    /// fn main() {
+    ///     let sys = System::new("test");
+    ///
+    ///     // handle SIGTERM only, SIGHUP keeps default behavior
+    ///     let config = ProcessSignalsConfig{signals: vec![SignalType::Term]};
+    ///     let _: SyncAddress<_> = ProcessSignals::new(config).start();
+    ///
+    ///     Arbiter::system().send(msgs::SystemExit(0));
+    ///     sys.run();
+    /// }
+    /// ```
+    pub fn new(config: ProcessSignalsConfig) -> ProcessSignals {
+        ProcessSignals {
+            signals: config.signals,
+            subscribers: Vec::new(),
+            force: None,
+        }
+    }
+
+    /// Start listening for signals listed in configuration
+    fn register(&self, ctx: &mut Context<Self>) {
+        for sig in self.signals.clone() {
+            // SIGINT
+            if sig == SignalType::Int {
+                ctx.with_handle(|handle| tokio_signal::ctrl_c(handle))
+                    .map_err(|_| ())
+                    .actfuture()
+                    .map(|sig, _: &mut ProcessSignals, ctx: &mut Context<Self>| {
+                        ctx.add_stream(sig.map(|_| SignalEvent::new(SignalType::Int)));
+                    })
+                    .spawn(ctx);
+                continue
+            }
+
+            let num = match sig {
+                SignalType::Hup => libc::SIGHUP,
+                SignalType::Term => libc::SIGTERM,
+                SignalType::Quit => libc::SIGQUIT,
+                SignalType::Child => libc::SIGCHLD,
+                SignalType::Other(num) => num,
+                SignalType::Int => unreachable!(),
+            };
+            ctx.with_handle(|handle| unix::Signal::new(num, handle))
+                .map_err(|_| ())
+                .actfuture()
+                .map(move |s, _: &mut ProcessSignals, ctx: &mut Context<Self>| {
+                    ctx.add_stream(s.map(move |_| SignalEvent::new(sig)));
+                })
+                .spawn(ctx);
+        }
     }
 }
 
@@ -116,69 +258,159 @@ impl Supervised for ProcessSignals {}
 impl SystemService for ProcessSignals {
 
     fn service_started(&mut self, ctx: &mut Context<Self>) {
-        let handle = Arbiter::handle();
-
-        // SIGINT
-        tokio_signal::ctrl_c(handle).map_err(|_| ())
-            .actfuture()
-            .map(|sig, _: &mut ProcessSignals, ctx: &mut Context<Self>|
-                 ctx.add_stream(sig.map(|_| SignalType::Int)))
-            .spawn(ctx);
-
-        // SIGHUP
-        unix::Signal::new(libc::SIGHUP, handle).map_err(|_| ())
-            .actfuture()
-            .map(|sig, _: &mut ProcessSignals, ctx: &mut Context<Self>|
-                 ctx.add_stream(sig.map(|_| SignalType::Hup)))
-            .spawn(ctx);
-
-        // SIGTERM
-        unix::Signal::new(libc::SIGTERM, handle).map_err(|_| ())
-            .actfuture()
-            .map(|sig, _: &mut Self, ctx: &mut Context<Self>|
-                 ctx.add_stream(sig.map(|_| SignalType::Term)))
-            .spawn(ctx);
-
-        // SIGQUIT
-        unix::Signal::new(libc::SIGQUIT, handle).map_err(|_| ())
-            .actfuture()
-            .map(|sig, _: &mut ProcessSignals, ctx: &mut Context<Self>|
-                 ctx.add_stream(sig.map(|_| SignalType::Quit)))
-            .spawn(ctx);
-
-        // SIGCHLD
-        unix::Signal::new(libc::SIGCHLD, handle).map_err(|_| ())
-            .actfuture()
-            .map(|sig, _: &mut ProcessSignals, ctx: &mut Context<Self>|
-                 ctx.add_stream(sig.map(|_| SignalType::Child)))
-            .spawn(ctx);
+        self.register(ctx);
     }
 }
 
 #[doc(hidden)]
-impl StreamHandler<SignalType, io::Error> for ProcessSignals {}
+impl StreamHandler<SignalEvent, io::Error> for ProcessSignals {}
 
-impl ResponseType<SignalType> for ProcessSignals {
+#[doc(hidden)]
+impl ResponseType<SignalEvent> for ProcessSignals {
     type Item = ();
     type Error = ();
 }
 
 #[doc(hidden)]
-impl Handler<SignalType, io::Error> for ProcessSignals {
+impl Handler<SignalEvent, io::Error> for ProcessSignals {
 
-    fn handle(&mut self, msg: SignalType, _: &mut Context<Self>) -> Response<Self, SignalType>
+    fn handle(&mut self, msg: SignalEvent, _: &mut Context<Self>) -> Response<Self, SignalEvent>
     {
+        self.broadcast(Signal(msg.0, msg.1));
+        Self::empty()
+    }
+
+    fn error(&mut self, err: io::Error, _: &mut Context<ProcessSignals>) {
+        error!("Error during signal handling: {}", err);
+    }
+}
+
+impl ResponseType<Signal> for ProcessSignals {
+    type Item = ();
+    type Error = ();
+}
+
+/// Broadcast signal to subscribers as if process received it,
+/// i.e. to emulate signals in tests.
+impl Handler<Signal> for ProcessSignals {
+
+    fn handle(&mut self, msg: Signal, _: &mut Context<Self>) -> Response<Self, Signal>
+    {
+        self.broadcast(msg);
+        Self::empty()
+    }
+}
+
+impl ProcessSignals {
+    fn broadcast(&mut self, signal: Signal) {
+        debug!("Broadcast {:?} signal, delivery delay: {:?}", signal.0, signal.delay());
+
+        // defer `SystemExit` until all subscribers handle terminal signal,
+        // second terminal signal does not wait for subscribers
+        let done = match signal.0 {
+            SignalType::Int | SignalType::Term | SignalType::Quit => {
+                let forced = match self.force.take() {
+                    Some(force) => force.send(()).is_ok(),
+                    None => false,
+                };
+                if forced {
+                    warn!("Second {:?} signal, do not wait for signal subscribers", signal.0);
+                    None
+                } else {
+                    let (tx, rx) = oneshot::channel();
+                    Arbiter::system().send(msgs::DeferSystemExit(rx));
+                    Some(tx)
+                }
+            }
+            _ => None,
+        };
+
+        let mut delivered: Vec<Box<Future<Item=(), Error=()>>> = Vec::new();
         let subscribers = std::mem::replace(&mut self.subscribers, Vec::new());
         for subscr in subscribers {
-            if subscr.send(Signal(msg)).is_ok() {
+            match subscr.try_send_tracked(signal) {
+                Ok(rx) => delivered.push(Box::new(rx.then(|_| Ok(())))),
+                // do not block other subscribers, retry later
+                Err(TrySendError::Full(_)) => delivered.push(
+                    Box::new(retry(Rc::clone(&subscr), signal))),
+                Err(TrySendError::Closed(_)) => continue,
+            }
+            // one-shot subscriber disconnects after delivery
+            if subscr.connected() {
                 self.subscribers.push(subscr);
             }
         }
-        Self::empty()
+
+        let delivered = future::join_all(delivered).map(|_| ());
+        if let Some(tx) = done {
+            let (force_tx, force_rx) = oneshot::channel();
+            self.force = Some(force_tx);
+            Arbiter::handle().spawn(
+                delivered.select2(force_rx).then(move |_| {
+                    let _ = tx.send(());
+                    Ok(())
+                }));
+        } else {
+            Arbiter::handle().spawn(delivered);
+        }
     }
+}
 
-    fn error(&mut self, err: io::Error, _: &mut Context<ProcessSignals>) {
-        error!("Error during signal handling: {}", err);
+/// Retry delivery to subscriber with full buffer until it accepts signal,
+/// resolves after subscriber handles signal.
+fn retry(subscr: Rc<Box<Subscriber<Signal>>>, signal: Signal)
+         -> impl Future<Item=(), Error=()>
+{
+    future::loop_fn((), move |_| {
+        let subscr = Rc::clone(&subscr);
+        Delay::new(Duration::from_millis(RETRY_DELAY)).and_then(move |_| {
+            match subscr.try_send_tracked(signal) {
+                Ok(rx) => future::Either::A(rx.then(|_| Ok(Loop::Break(())))),
+                Err(TrySendError::Full(_)) => future::Either::B(future::ok(Loop::Continue(()))),
+                Err(TrySendError::Closed(_)) => future::Either::B(future::ok(Loop::Break(()))),
+            }
+        })
+    })
+}
+
+/// Install handlers for additional raw signal numbers.
+///
+/// Each signal is delivered to subscribers as `Signal` with `SignalType::Other(n)`.
+/// Use this message for signals that are not covered by `SignalType`,
+/// i.e. `SIGWINCH` or application specific real-time signals.
+///
+/// Response is an error if any of the signals can not be installed,
+/// none of the signals is installed in that case.
+pub struct AddSignals(pub Vec<i32>);
+
+impl ResponseType<AddSignals> for ProcessSignals {
+    type Item = ();
+    type Error = io::Error;
+}
+
+/// Install handlers for additional signals
+impl Handler<AddSignals> for ProcessSignals {
+
+    fn handle(&mut self, msg: AddSignals,
+              ctx: &mut Context<ProcessSignals>) -> Response<Self, AddSignals>
+    {
+        let signals: Vec<_> = msg.0.into_iter()
+            .map(|sig| ctx.with_handle(|handle| unix::Signal::new(sig, handle))
+                 .map(move |s| (sig, s)))
+            .collect();
+
+        Self::async_reply(
+            future::join_all(signals)
+                .map_err(|err| {
+                    error!("Can not install signal handler: {}", err);
+                    err
+                })
+                .actfuture()
+                .map(|signals, _: &mut ProcessSignals, ctx: &mut Context<Self>| {
+                    for (sig, s) in signals {
+                        ctx.add_stream(s.map(move |_| SignalEvent::new(SignalType::Other(sig))));
+                    }
+                }))
     }
 }
 
@@ -196,11 +428,86 @@ impl Handler<Subscribe> for ProcessSignals {
     fn handle(&mut self, msg: Subscribe,
               _: &mut Context<ProcessSignals>) -> Response<Self, Subscribe>
     {
-        self.subscribers.push(msg.0);
+        self.subscribers.push(Rc::new(msg.0));
         Self::empty()
     }
 }
 
+/// Subscribe to `ProcessSignals` service of current system.
+///
+/// Subscriber is dropped if current thread does not belong to running system.
+pub(crate) fn subscribe(subscriber: Box<Subscriber<Signal> + Send>) {
+    match Arbiter::try_system_registry() {
+        Some(registry) => registry.get::<ProcessSignals>().send(Subscribe(subscriber)),
+        None => warn!("System is not running, signals are not handled"),
+    }
+}
+
+/// Returns future that resolves next time signal of type `kind` is received.
+///
+/// Future subscribes to `ProcessSignals` service of current system and
+/// unsubscribes after signal fires. Future resolves with error if
+/// system stops before signal is received.
+///
+/// ```rust
+/// # extern crate actix;
+/// # extern crate futures;
+/// use futures::Future;
+/// use actix::prelude::*;
+/// use actix::actors::signal::{self, SignalType};
+///
+/// fn main() {
+///     let sys = System::new("test");
+///
+///     Arbiter::handle().spawn(
+///         signal::wait_for_signal(SignalType::Hup)
+///             .map(|_| println!("SIGHUP received")));
+///
+///     Arbiter::system().send(msgs::SystemExit(0));
+///     sys.run();
+/// }
+/// ```
+pub fn wait_for_signal(kind: SignalType) -> impl Future<Item=(), Error=()> {
+    let (tx, rx) = oneshot::channel();
+    subscribe(Box::new(SignalWaiter{kind: kind, tx: Mutex::new(Some(tx))}));
+    rx.map_err(|_| ())
+}
+
+/// One-shot subscriber that waits for signal of specific type
+struct SignalWaiter {
+    kind: SignalType,
+    tx: Mutex<Option<oneshot::Sender<()>>>,
+}
+
+impl Subscriber<Signal> for SignalWaiter {
+
+    fn send(&self, msg: Signal) -> Result<(), Signal> {
+        let mut tx = match self.tx.lock() {
+            Ok(tx) => tx,
+            Err(_) => return Err(msg),
+        };
+        match tx.take() {
+            Some(sender) => if msg.0 == self.kind {
+                let _ = sender.send(());
+                Ok(())
+            } else if sender.is_canceled() {
+                Err(msg)
+            } else {
+                *tx = Some(sender);
+                Ok(())
+            },
+            None => Err(msg),
+        }
+    }
+
+    fn connected(&self) -> bool {
+        match self.tx.lock() {
+            Ok(tx) => tx.as_ref().map(|tx| !tx.is_canceled()).unwrap_or(false),
+            Err(_) => false,
+        }
+    }
+}
+
 /// Default signals handler. This actor sends `SystemExit` message to `System` actor
 /// for each of `SIGINT`, `SIGTERM`, `SIGQUIT` signals.
 pub struct DefaultSignalsHandler;
@@ -215,9 +522,12 @@ impl Actor for DefaultSignalsHandler {
     type Context = Context<Self>;
 
     fn started(&mut self, ctx: &mut Context<Self>) {
-        let addr = Arbiter::system_registry().get::<ProcessSignals>();
         let slf: SyncAddress<_> = ctx.address();
-        addr.send(Subscribe(slf.subscriber()))
+        subscribe(slf.subscriber())
+    }
+
+    fn name() -> Option<&'static str> {
+        Some("DefaultSignalsHandler")
     }
 }
 
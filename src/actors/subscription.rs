@@ -0,0 +1,222 @@
+//! Bulk subscriptions
+//!
+//! `SyncAddress::subscribe_all()` subscribes actor to several broadcasting
+//! services at once, i.e. to `ProcessSignals` and `SystemEvents`. Each
+//! service is described by `SubscribeTarget`. Returned `Subscription`
+//! unsubscribes actor from all of the services at once.
+//!
+//! Services drop subscriber on next broadcast after `Subscription::unsubscribe()`
+//! call, messages that are already in actor's mailbox still get handled.
+//! Dropping `Subscription` without `unsubscribe()` call keeps actor subscribed.
+//!
+//! # Examples
+//!
+//! ```rust
+//! extern crate actix;
+//!
+//! use std::time::Duration;
+//! use actix::prelude::*;
+//! use actix::actors::events::LifecycleEvent;
+//! use actix::actors::subscription::{SubscribeTarget, Subscription};
+//!
+//! struct Watcher(Option<Subscription>);
+//!
+//! impl Actor for Watcher {
+//!     type Context = Context<Self>;
+//!
+//!     fn started(&mut self, ctx: &mut Context<Self>) {
+//!         let addr: SyncAddress<_> = ctx.address();
+//!         self.0 = Some(addr.subscribe_all(vec![SubscribeTarget::events()]));
+//!
+//!         // give `SystemEvents` service time to start
+//!         ctx.run_later(Duration::from_millis(100), |_, _| {
+//!             let _: () = Worker.start();
+//!         });
+//!     }
+//! }
+//!
+//! impl ResponseType<LifecycleEvent> for Watcher {
+//!     type Item = ();
+//!     type Error = ();
+//! }
+//!
+//! impl Handler<LifecycleEvent> for Watcher {
+//!     fn handle(&mut self, msg: LifecycleEvent, _: &mut Context<Self>)
+//!               -> Response<Self, LifecycleEvent>
+//!     {
+//!         println!("{:?}", msg);
+//!         if let Some(subscription) = self.0.take() {
+//!             subscription.unsubscribe();
+//!             Arbiter::system().send(msgs::SystemExit(0));
+//!         }
+//!         Self::empty()
+//!     }
+//! }
+//!
+//! struct Worker;
+//!
+//! impl Actor for Worker {
+//!     type Context = Context<Self>;
+//! }
+//!
+//! fn main() {
+//!     let sys = System::new("test");
+//!
+//!     let _: () = Watcher(None).start();
+//!
+//!     sys.run();
+//! }
+//! ```
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use futures::sync::oneshot::Receiver;
+
+use prelude::*;
+use address::TrySendError;
+use envelope::ToEnvelope;
+use actors::events::{self, LifecycleEvent, SystemEvents};
+
+#[cfg(feature="signal")]
+use actors::signal::{self, ProcessSignals, Signal};
+
+type SubscribeFn<A> = Box<Fn(&SyncAddress<A>, &Arc<AtomicBool>)>;
+
+/// Broadcasting service to subscribe actor `A` to
+pub struct SubscribeTarget<A: Actor> {
+    subscribe: SubscribeFn<A>,
+}
+
+impl<A: Actor> SubscribeTarget<A> {
+
+    /// Subscribe to messages of type `M` from actor `S`, `subscribe` builds
+    /// subscription message of the service.
+    pub fn new<S, M, R, F>(service: SyncAddress<S>, subscribe: F) -> SubscribeTarget<A>
+        where A: Handler<M>,
+              <A as ResponseType<M>>::Item: Send,
+              <A as ResponseType<M>>::Error: Send,
+              A: ToEnvelope<A, <A as Actor>::Context, M>,
+              M: Send + 'static,
+              S: Actor + Handler<R>,
+              <S as ResponseType<R>>::Item: Send,
+              <S as ResponseType<R>>::Error: Send,
+              S: ToEnvelope<S, <S as Actor>::Context, R>,
+              R: Send + 'static,
+              F: Fn(Box<Subscriber<M> + Send>) -> R + 'static,
+    {
+        SubscribeTarget {
+            subscribe: Box::new(move |addr, active| {
+                service.send(subscribe(Box::new(
+                    CancelableSubscriber{sub: addr.subscriber(), active: Arc::clone(active)})))
+            })
+        }
+    }
+
+    /// Subscribe to lifecycle events of `SystemEvents` service
+    pub fn events() -> SubscribeTarget<A>
+        where A: Handler<LifecycleEvent>,
+              <A as ResponseType<LifecycleEvent>>::Item: Send,
+              <A as ResponseType<LifecycleEvent>>::Error: Send,
+              A: ToEnvelope<A, <A as Actor>::Context, LifecycleEvent>,
+    {
+        SubscribeTarget::new(
+            Arbiter::system_registry().get::<SystemEvents>(), events::Subscribe)
+    }
+
+    #[cfg(feature="signal")]
+    /// Subscribe to process signals of `ProcessSignals` service
+    pub fn signals() -> SubscribeTarget<A>
+        where A: Handler<Signal>,
+              <A as ResponseType<Signal>>::Item: Send,
+              <A as ResponseType<Signal>>::Error: Send,
+              A: ToEnvelope<A, <A as Actor>::Context, Signal>,
+    {
+        SubscribeTarget::new(
+            Arbiter::system_registry().get::<ProcessSignals>(), signal::Subscribe)
+    }
+}
+
+/// Combined subscription created by `SyncAddress::subscribe_all()`
+pub struct Subscription {
+    active: Arc<AtomicBool>,
+    count: usize,
+}
+
+impl Subscription {
+
+    pub(crate) fn new<A: Actor>(addr: &SyncAddress<A>, targets: Vec<SubscribeTarget<A>>)
+                                -> Subscription
+    {
+        let active = Arc::new(AtomicBool::new(true));
+        for target in &targets {
+            (target.subscribe)(addr, &active);
+        }
+        Subscription{active: active, count: targets.len()}
+    }
+
+    /// Number of services actor is subscribed to
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Check if subscription does not include any service
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Unsubscribe from all services
+    pub fn unsubscribe(self) {
+        self.active.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Subscriber that disconnects once subscription is canceled
+struct CancelableSubscriber<M: 'static> {
+    sub: Box<Subscriber<M> + Send>,
+    active: Arc<AtomicBool>,
+}
+
+impl<M: 'static> Subscriber<M> for CancelableSubscriber<M> {
+    fn send(&self, msg: M) -> Result<(), M> {
+        if self.active.load(Ordering::SeqCst) {
+            self.sub.send(msg)
+        } else {
+            Err(msg)
+        }
+    }
+
+    fn connected(&self) -> bool {
+        self.active.load(Ordering::SeqCst) && self.sub.connected()
+    }
+
+    fn send_tracked(&self, msg: M) -> Result<Receiver<()>, M> {
+        if self.active.load(Ordering::SeqCst) {
+            self.sub.send_tracked(msg)
+        } else {
+            Err(msg)
+        }
+    }
+
+    fn try_send(&self, msg: M) -> Result<(), TrySendError<M>> {
+        if self.active.load(Ordering::SeqCst) {
+            self.sub.try_send(msg)
+        } else {
+            Err(TrySendError::Closed(msg))
+        }
+    }
+
+    fn try_send_tracked(&self, msg: M) -> Result<Receiver<()>, TrySendError<M>> {
+        if self.active.load(Ordering::SeqCst) {
+            self.sub.try_send_tracked(msg)
+        } else {
+            Err(TrySendError::Closed(msg))
+        }
+    }
+
+    fn type_name(&self) -> &'static str {
+        self.sub.type_name()
+    }
+
+    fn mailbox_len(&self) -> usize {
+        self.sub.mailbox_len()
+    }
+}
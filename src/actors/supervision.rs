@@ -0,0 +1,308 @@
+//! Supervision trees
+//!
+//! Supervisor actor starts group of child actors and restarts them when they
+//! stop. `OneForOneSupervisor` restarts only stopped child,
+//! `AllForOneSupervisor` stops all other children and restarts whole group.
+//! Children are defined by `ActorGroup` implementation and have to run in the
+//! supervisor's arbiter.
+//!
+//! Number of restarts is limited by `RestartPolicy`. If children stop more
+//! often than policy allows, supervisor stops all children and stops itself.
+//! Supervisor that is stopped, i.e. by its own supervisor, stops its children
+//! as well and stops after all of them stop.
+//!
+//! # Examples
+//!
+//! ```rust
+//! extern crate actix;
+//!
+//! use actix::prelude::*;
+//! use actix::actors::supervision::{ActorGroup, Child, OneForOneSupervisor};
+//!
+//! struct Worker;
+//!
+//! impl Actor for Worker {
+//!     type Context = Context<Self>;
+//!
+//!     fn started(&mut self, ctx: &mut Context<Self>) {
+//!         println!("worker started");
+//!         Arbiter::system().send(msgs::SystemExit(0));
+//!     }
+//! }
+//!
+//! struct Workers {
+//!     workers: Vec<Option<Address<Worker>>>,
+//! }
+//!
+//! impl ActorGroup for Workers {
+//!     fn size(&self) -> usize {
+//!         self.workers.len()
+//!     }
+//!
+//!     fn start(&mut self, idx: usize) -> Child {
+//!         let addr: Address<_> = Worker.start();
+//!         self.workers[idx] = Some(addr.clone());
+//!         Child::new(&addr)
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let sys = System::new("test");
+//!
+//!     let _: () = OneForOneSupervisor::new(Workers{workers: vec![None, None]}).start();
+//!
+//!     sys.run();
+//! }
+//! ```
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+use futures::unsync::oneshot::Receiver;
+
+use prelude::*;
+use actor::actor_name;
+
+/// Limits how often supervisor restarts children
+#[derive(Clone, Debug)]
+pub struct RestartPolicy {
+    /// Maximum number of restarts within `within` period
+    pub max_restarts: usize,
+    /// Period of time
+    pub within: Duration,
+}
+
+/// Default policy allows 3 restarts within 5 seconds
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        RestartPolicy {
+            max_restarts: 3,
+            within: Duration::from_secs(5),
+        }
+    }
+}
+
+/// Group of child actors managed by supervisor
+pub trait ActorGroup: 'static {
+    /// Number of children in the group
+    fn size(&self) -> usize;
+
+    /// Start child with index `idx`. Method is called in supervisor's
+    /// arbiter for initial start and for every restart.
+    fn start(&mut self, idx: usize) -> Child;
+}
+
+/// Running child actor
+pub struct Child {
+    stopped: Receiver<()>,
+    stop: Box<Fn()>,
+}
+
+impl Child {
+    /// Supervise actor behind `addr`
+    pub fn new<A>(addr: &Address<A>) -> Child where A: Actor, A::Context: AsyncContext<A>
+    {
+        let addr = addr.clone();
+        Child {
+            stopped: addr.watch(),
+            stop: Box::new(move || addr.stop()),
+        }
+    }
+}
+
+/// Children state shared by both supervisors
+struct Children<G: ActorGroup> {
+    group: G,
+    policy: RestartPolicy,
+    restarts: VecDeque<Instant>,
+    running: Vec<Option<Box<Fn()>>>,
+    stopping: bool,
+}
+
+impl<G: ActorGroup> Children<G> {
+    fn new(group: G, policy: RestartPolicy) -> Children<G> {
+        let size = group.size();
+        Children {
+            group: group,
+            policy: policy,
+            restarts: VecDeque::new(),
+            running: (0..size).map(|_| None).collect(),
+            stopping: false,
+        }
+    }
+
+    /// Record restart, returns false if restart exceeds policy
+    fn restart_allowed(&mut self) -> bool {
+        let now = Arbiter::now();
+        while self.restarts.front().map(|t| now - *t > self.policy.within).unwrap_or(false) {
+            self.restarts.pop_front();
+        }
+        if self.restarts.len() >= self.policy.max_restarts {
+            false
+        } else {
+            self.restarts.push_back(now);
+            true
+        }
+    }
+
+    /// Supervisor is going to stop, stop children without restarting them
+    fn shutdown(&mut self) {
+        self.stopping = true;
+        self.stop_all();
+    }
+
+    /// Stop all running children
+    fn stop_all(&mut self) {
+        for stop in &self.running {
+            if let Some(ref stop) = *stop {
+                stop()
+            }
+        }
+    }
+
+    fn is_running(&self) -> bool {
+        self.running.iter().any(|child| child.is_some())
+    }
+}
+
+/// Supervisor actor that restarts children
+trait Supervision: Actor<Context=Context<Self>> {
+    type Group: ActorGroup;
+
+    fn children(&mut self) -> &mut Children<Self::Group>;
+
+    fn child_stopped(&mut self, idx: usize, ctx: &mut Context<Self>);
+
+    fn start_child(&mut self, idx: usize, ctx: &mut Context<Self>) {
+        let child = self.children().group.start(idx);
+        self.children().running[idx] = Some(child.stop);
+
+        child.stopped
+            .actfuture()
+            .then(move |_, act: &mut Self, ctx: &mut Context<Self>| {
+                act.children().running[idx] = None;
+                if act.children().stopping {
+                    // supervisor stops after all children stop
+                    if !act.children().is_running() {
+                        ctx.stop();
+                    }
+                } else {
+                    act.child_stopped(idx, ctx);
+                }
+                fut::ok(())
+            })
+            .spawn(ctx);
+    }
+
+    /// Restart limit is exceeded, stop children and supervisor
+    fn escalate(&mut self, ctx: &mut Context<Self>) {
+        error!("Supervisor {} exceeded restart limit, stopping", actor_name::<Self>());
+        self.children().shutdown();
+        if !self.children().is_running() {
+            ctx.stop();
+        }
+    }
+}
+
+/// Supervisor that restarts only stopped child
+pub struct OneForOneSupervisor<G: ActorGroup> {
+    children: Children<G>,
+}
+
+impl<G: ActorGroup> OneForOneSupervisor<G> {
+    /// Create supervisor with default restart policy
+    pub fn new(group: G) -> Self {
+        OneForOneSupervisor::with_policy(group, RestartPolicy::default())
+    }
+
+    /// Create supervisor with restart policy
+    pub fn with_policy(group: G, policy: RestartPolicy) -> Self {
+        OneForOneSupervisor{children: Children::new(group, policy)}
+    }
+}
+
+impl<G: ActorGroup> Actor for OneForOneSupervisor<G> {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Context<Self>) {
+        for idx in 0..self.children.running.len() {
+            self.start_child(idx, ctx);
+        }
+    }
+    fn stopping(&mut self, _: &mut Context<Self>) {
+        // supervisor keeps running until all children stop
+        self.children.shutdown();
+    }
+}
+
+impl<G: ActorGroup> Supervision for OneForOneSupervisor<G> {
+    type Group = G;
+
+    fn children(&mut self) -> &mut Children<G> {
+        &mut self.children
+    }
+
+    fn child_stopped(&mut self, idx: usize, ctx: &mut Context<Self>) {
+        if self.children.restart_allowed() {
+            self.start_child(idx, ctx);
+        } else {
+            self.escalate(ctx);
+        }
+    }
+}
+
+/// Supervisor that restarts all children when any of them stops
+pub struct AllForOneSupervisor<G: ActorGroup> {
+    children: Children<G>,
+    restarting: bool,
+}
+
+impl<G: ActorGroup> AllForOneSupervisor<G> {
+    /// Create supervisor with default restart policy
+    pub fn new(group: G) -> Self {
+        AllForOneSupervisor::with_policy(group, RestartPolicy::default())
+    }
+
+    /// Create supervisor with restart policy
+    pub fn with_policy(group: G, policy: RestartPolicy) -> Self {
+        AllForOneSupervisor{children: Children::new(group, policy), restarting: false}
+    }
+}
+
+impl<G: ActorGroup> Actor for AllForOneSupervisor<G> {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Context<Self>) {
+        for idx in 0..self.children.running.len() {
+            self.start_child(idx, ctx);
+        }
+    }
+    fn stopping(&mut self, _: &mut Context<Self>) {
+        // supervisor keeps running until all children stop
+        self.children.shutdown();
+    }
+}
+
+impl<G: ActorGroup> Supervision for AllForOneSupervisor<G> {
+    type Group = G;
+
+    fn children(&mut self) -> &mut Children<G> {
+        &mut self.children
+    }
+
+    fn child_stopped(&mut self, _: usize, ctx: &mut Context<Self>) {
+        if !self.restarting {
+            if !self.children.restart_allowed() {
+                return self.escalate(ctx)
+            }
+            self.restarting = true;
+            self.children.stop_all();
+        }
+
+        // restart group after all children stop
+        if !self.children.is_running() {
+            self.restarting = false;
+            for idx in 0..self.children.running.len() {
+                self.start_child(idx, ctx);
+            }
+        }
+    }
+}
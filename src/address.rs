@@ -1,11 +1,25 @@
+use std::any::{type_name, Any};
 use std::cell::Cell;
+use std::{error, fmt};
+use std::marker::PhantomData;
+use std::panic::{RefUnwindSafe, UnwindSafe};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::{Duration, Instant};
+use futures::{executor, Async, Future};
+use uuid::Uuid;
 use futures::unsync::oneshot::{channel, Receiver};
-use futures::sync::oneshot::{channel as sync_channel, Receiver as SyncReceiver};
+use futures::sync::oneshot::{channel as sync_channel, Canceled, Receiver as SyncReceiver};
 
-use actor::{Actor, Handler, ResponseType, AsyncContext};
-use context::{ContextProtocol, AsyncContextApi};
-use envelope::{Envelope, ToEnvelope};
+use actors::subscription::{SubscribeTarget, Subscription};
+use actor::{actor_name, Actor, Handler, ResponseType, AsyncContext, Snapshotable};
+use arbiter::Arbiter;
+use context::{Context, ContextProtocol, AsyncContextApi, ReloadActor};
+use dynamic::{DynamicEnvelope, DynamicMessage};
+use envelope::{Envelope, ToEnvelope, TrackedEnvelope};
 use message::Request;
+use msgs::Execute;
+#[cfg(feature="test-utils")]
+use msgs::Inspect;
 use queue::{sync, unsync};
 
 
@@ -33,6 +47,28 @@ impl<A> ActorAddress<A, SyncAddress<A>> for A
     }
 }
 
+/// Address of the actor `A` that could be watched with `Context::watch()`
+pub trait Watchable<A: Actor> {
+    #[doc(hidden)]
+    /// Returned future resolves after actor's context stops
+    fn terminated(&self) -> Box<Future<Item=(), Error=()>>;
+}
+
+impl<A> Watchable<A> for Address<A> where A: Actor, A::Context: AsyncContext<A> {
+    fn terminated(&self) -> Box<Future<Item=(), Error=()>> {
+        Box::new(self.watch().then(|_| Ok(())))
+    }
+}
+
+impl<A> Watchable<A> for SyncAddress<A> where A: Actor<Context=Context<A>> {
+    fn terminated(&self) -> Box<Future<Item=(), Error=()>> {
+        let (tx, rx) = sync_channel();
+        // stopped actor drops sender immediately
+        self.send_envelope(Context::sync_watch(tx));
+        Box::new(rx.then(|_| Ok(())))
+    }
+}
+
 impl<A> ActorAddress<A, (Address<A>, SyncAddress<A>)> for A
     where A: Actor,
           A::Context: AsyncContext<A> + AsyncContextApi<A>
@@ -55,25 +91,299 @@ pub trait Subscriber<M: 'static> {
     /// Send buffered message
     fn send(&self, msg: M) -> Result<(), M>;
 
+    /// Indicates if subscriber is still connected to the actor.
+    /// Subscriber that can not tell connection state returns `true`.
+    fn connected(&self) -> bool {
+        true
+    }
+
+    #[doc(hidden)]
+    /// Send message, returned receiver resolves after message get handled.
+    /// Receiver get canceled if message is dropped without handling.
+    fn send_tracked(&self, msg: M) -> Result<SyncReceiver<()>, M> {
+        let (tx, rx) = sync_channel();
+        self.send(msg)?;
+        let _ = tx.send(());
+        Ok(rx)
+    }
+
+    /// Send message if subscriber can accept it right now.
+    ///
+    /// Subscriber with bounded buffer returns `TrySendError::Full` instead
+    /// of waiting for free space, caller could retry later. Default
+    /// implementation calls `send()`, so it never reports full buffer.
+    fn try_send(&self, msg: M) -> Result<(), TrySendError<M>> {
+        self.send(msg).map_err(TrySendError::Closed)
+    }
+
+    #[doc(hidden)]
+    /// Same as `send_tracked()`, but fails if subscriber's buffer is full.
+    fn try_send_tracked(&self, msg: M) -> Result<SyncReceiver<()>, TrySendError<M>> {
+        let (tx, rx) = sync_channel();
+        self.try_send(msg)?;
+        let _ = tx.send(());
+        Ok(rx)
+    }
+
+    /// Returns type name of the actor behind this subscriber.
+    ///
+    /// Default implementation returns type name of the subscriber itself,
+    /// subscribers that deliver messages to an actor should override it.
+    fn type_name(&self) -> &'static str {
+        type_name::<Self>()
+    }
+
+    /// Number of messages queued in the mailbox of the actor behind this
+    /// subscriber. Subscriber that can not tell mailbox size returns `0`.
+    fn mailbox_len(&self) -> usize {
+        0
+    }
+
+    #[doc(hidden)]
+    /// Id of the actor behind this subscriber.
+    fn actor_id(&self) -> Option<Uuid> {
+        None
+    }
+
+    #[doc(hidden)]
+    /// Access concrete subscriber, used by `downcast_ref()`.
+    fn as_any(&self) -> Option<&Any> {
+        None
+    }
+
+    /// Adapt subscriber to a different message type.
+    ///
+    /// Returned subscriber accepts messages of type `N` and converts
+    /// them with `f` before passing to this subscriber. Original message
+    /// is returned back to the caller if this subscriber fails to send.
+    fn map<N, F>(self, f: F) -> Box<Subscriber<N>>
+        where Self: Sized + 'static,
+              N: 'static,
+              F: Fn(&N) -> M + 'static
+    {
+        Box::new(MapSubscriber{sub: self, f: f, msg: PhantomData})
+    }
+
+    /// Same as `map` but returned subscriber could be sent to different thread.
+    fn sync_map<N, F>(self, f: F) -> Box<Subscriber<N> + Send>
+        where Self: Sized + Send + 'static,
+              N: 'static,
+              F: Fn(&N) -> M + Send + 'static
+    {
+        Box::new(MapSubscriber{sub: self, f: f, msg: PhantomData})
+    }
+}
+
+impl<M: 'static> Subscriber<M> for Box<Subscriber<M>> {
+    fn send(&self, msg: M) -> Result<(), M> {
+        (**self).send(msg)
+    }
+
+    fn connected(&self) -> bool {
+        (**self).connected()
+    }
+
+    fn send_tracked(&self, msg: M) -> Result<SyncReceiver<()>, M> {
+        (**self).send_tracked(msg)
+    }
+
+    fn try_send(&self, msg: M) -> Result<(), TrySendError<M>> {
+        (**self).try_send(msg)
+    }
+
+    fn try_send_tracked(&self, msg: M) -> Result<SyncReceiver<()>, TrySendError<M>> {
+        (**self).try_send_tracked(msg)
+    }
+
+    fn type_name(&self) -> &'static str {
+        (**self).type_name()
+    }
+
+    fn mailbox_len(&self) -> usize {
+        (**self).mailbox_len()
+    }
+
+    fn actor_id(&self) -> Option<Uuid> {
+        (**self).actor_id()
+    }
+
+    fn as_any(&self) -> Option<&Any> {
+        (**self).as_any()
+    }
+}
+
+impl<M: 'static> Subscriber<M> for Box<Subscriber<M> + Send> {
+    fn send(&self, msg: M) -> Result<(), M> {
+        (**self).send(msg)
+    }
+
+    fn connected(&self) -> bool {
+        (**self).connected()
+    }
+
+    fn send_tracked(&self, msg: M) -> Result<SyncReceiver<()>, M> {
+        (**self).send_tracked(msg)
+    }
+
+    fn try_send(&self, msg: M) -> Result<(), TrySendError<M>> {
+        (**self).try_send(msg)
+    }
+
+    fn try_send_tracked(&self, msg: M) -> Result<SyncReceiver<()>, TrySendError<M>> {
+        (**self).try_send_tracked(msg)
+    }
+
+    fn type_name(&self) -> &'static str {
+        (**self).type_name()
+    }
+
+    fn mailbox_len(&self) -> usize {
+        (**self).mailbox_len()
+    }
+
+    fn actor_id(&self) -> Option<Uuid> {
+        (**self).actor_id()
+    }
+
+    fn as_any(&self) -> Option<&Any> {
+        (**self).as_any()
+    }
+}
+
+impl<M: 'static> Subscriber<M> {
+    /// Returns reference to the concrete subscriber if it is of type `T`.
+    pub fn downcast_ref<T: Subscriber<M> + 'static>(&self) -> Option<&T> {
+        self.as_any().and_then(|sub| sub.downcast_ref())
+    }
+}
+
+impl<M: 'static> Subscriber<M> + Send {
+    /// Returns reference to the concrete subscriber if it is of type `T`.
+    pub fn downcast_ref<T: Subscriber<M> + 'static>(&self) -> Option<&T> {
+        self.as_any().and_then(|sub| sub.downcast_ref())
+    }
+}
+
+impl<M: 'static> fmt::Debug for Subscriber<M> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Subscriber<{}>(connected: {})", type_name::<M>(), self.connected())
+    }
+}
+
+impl<M: 'static> fmt::Debug for Subscriber<M> + Send {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Subscriber<{}>(connected: {})", type_name::<M>(), self.connected())
+    }
+}
+
+/// Subscriber adapter, converts message before sending to inner subscriber
+struct MapSubscriber<S, M, N, F> {
+    sub: S,
+    f: F,
+    msg: PhantomData<fn(&N) -> M>,
+}
+
+impl<S, M, N, F> Subscriber<N> for MapSubscriber<S, M, N, F>
+    where S: Subscriber<M>,
+          F: Fn(&N) -> M,
+          M: 'static,
+          N: 'static,
+{
+    fn send(&self, msg: N) -> Result<(), N> {
+        // converted message can not be returned back to the caller,
+        // so original message is kept until send succeeds
+        match self.sub.send((self.f)(&msg)) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(msg),
+        }
+    }
+
+    fn connected(&self) -> bool {
+        self.sub.connected()
+    }
+
+    fn type_name(&self) -> &'static str {
+        self.sub.type_name()
+    }
+
+    fn mailbox_len(&self) -> usize {
+        self.sub.mailbox_len()
+    }
+
+    fn actor_id(&self) -> Option<Uuid> {
+        self.sub.actor_id()
+    }
+
+    fn send_tracked(&self, msg: N) -> Result<SyncReceiver<()>, N> {
+        match self.sub.send_tracked((self.f)(&msg)) {
+            Ok(rx) => Ok(rx),
+            Err(_) => Err(msg),
+        }
+    }
+
+    fn try_send(&self, msg: N) -> Result<(), TrySendError<N>> {
+        match self.sub.try_send((self.f)(&msg)) {
+            Ok(()) => Ok(()),
+            Err(err) => Err(err.map(msg)),
+        }
+    }
+
+    fn try_send_tracked(&self, msg: N) -> Result<SyncReceiver<()>, TrySendError<N>> {
+        match self.sub.try_send_tracked((self.f)(&msg)) {
+            Ok(rx) => Ok(rx),
+            Err(err) => Err(err.map(msg)),
+        }
+    }
 }
 
 /// Address of the actor
 ///
 /// Actor has to run in the same thread as owner of the address.
 pub struct Address<A> where A: Actor, A::Context: AsyncContext<A> {
-    tx: unsync::UnboundedSender<ContextProtocol<A>>
+    tx: unsync::UnboundedSender<ContextProtocol<A>>,
+    id: Uuid,
 }
 
+// address only sends messages, panic can not leave actor in broken state
+impl<A> UnwindSafe for Address<A> where A: Actor, A::Context: AsyncContext<A> {}
+impl<A> RefUnwindSafe for Address<A> where A: Actor, A::Context: AsyncContext<A> {}
+
 impl<A> Clone for Address<A> where A: Actor, A::Context: AsyncContext<A> {
     fn clone(&self) -> Self {
-        Address{tx: self.tx.clone() }
+        Address{tx: self.tx.clone(), id: self.id}
+    }
+}
+
+impl<A> fmt::Debug for Address<A> where A: Actor, A::Context: AsyncContext<A> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Address<{}>(id: {})", actor_name::<A>(), self.id.hyphenated())
+    }
+}
+
+/// Short form for logs, i.e. `Address<ProcessSignals>(3f4a)`
+impl<A> fmt::Display for Address<A> where A: Actor, A::Context: AsyncContext<A> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Address<{}>({})", short_name::<A>(), &self.id.simple().to_string()[..4])
+    }
+}
+
+impl<'a, A> From<&'a Address<A>> for Address<A> where A: Actor, A::Context: AsyncContext<A> {
+    fn from(addr: &'a Address<A>) -> Self {
+        addr.clone()
     }
 }
 
 impl<A> Address<A> where A: Actor, A::Context: AsyncContext<A> {
 
-    pub(crate) fn new(sender: unsync::UnboundedSender<ContextProtocol<A>>) -> Address<A> {
-        Address{tx: sender}
+    pub(crate) fn new(sender: unsync::UnboundedSender<ContextProtocol<A>>, id: Uuid)
+                      -> Address<A>
+    {
+        Address{tx: sender, id: id}
+    }
+
+    /// Id of the actor, all addresses of the actor share the same id.
+    pub fn id(&self) -> Uuid {
+        self.id
     }
 
     /// Indicates if address is still connected to the actor.
@@ -81,6 +391,12 @@ impl<A> Address<A> where A: Actor, A::Context: AsyncContext<A> {
         self.tx.connected()
     }
 
+    /// Number of messages sent with local addresses that actor
+    /// has not handled yet.
+    pub fn mailbox_len(&self) -> usize {
+        self.tx.len()
+    }
+
     /// Send message `M` to actor `A`. Communication channel to the actor is
     /// unbuonded.
     pub fn send<M: 'static>(&self, msg: M) where A: Handler<M>
@@ -124,6 +440,53 @@ impl<A> Address<A> where A: Actor, A::Context: AsyncContext<A> {
         rx
     }
 
+    /// Returned receiver resolves, as canceled, after actor's context stops.
+    pub(crate) fn watch(&self) -> Receiver<()> {
+        let (tx, rx) = channel();
+        // stopped actor drops sender immediately
+        let _ = self.tx.unbounded_send(ContextProtocol::Watch(tx));
+        rx
+    }
+
+    /// Gracefully stop actor
+    pub(crate) fn stop(&self) {
+        let _ = self.tx.unbounded_send(ContextProtocol::Stop);
+    }
+
+    /// Replace actor, `act` is returned back if actor is stopped
+    pub(crate) fn take_over(&self, act: A) -> Result<(), A> {
+        self.tx.unbounded_send_front(ContextProtocol::TakeOver(act))
+            .map_err(|err| match err.into_inner() {
+                ContextProtocol::TakeOver(act) => act,
+                _ => unreachable!(),
+            })
+    }
+
+    /// Replace actor with `act` that restores state of running actor,
+    /// `act` is returned back if actor is stopped
+    pub(crate) fn reload(&self, act: A) -> Result<(), A>
+        where A: Snapshotable + Actor<Context=Context<A>>
+    {
+        if !self.connected() {
+            return Err(act)
+        }
+        let _ = self.tx.unbounded_send(
+            ContextProtocol::Envelope(Envelope::new(ReloadActor::new(act))));
+        Ok(())
+    }
+
+    #[cfg(feature="test-utils")]
+    /// Call `f` with the actor between messages, returned receiver resolves
+    /// to the result of `f`.
+    pub fn inspect<F, R>(&self, f: F) -> SyncReceiver<R>
+        where F: FnOnce(&A) -> R + Send + 'static, R: Send + 'static
+    {
+        let (tx, rx) = sync_channel();
+        let _ = self.tx.unbounded_send(
+            ContextProtocol::Envelope(Envelope::new(Inspect::new(f, tx))));
+        rx
+    }
+
     /// Get `Subscriber` for specific message type
     pub fn subscriber<M: 'static>(&self) -> Box<Subscriber<M>>
         where A: Handler<M>
@@ -145,32 +508,277 @@ impl<A, M> Subscriber<M> for Address<A>
             Err(msg)
         }
     }
+
+    fn connected(&self) -> bool {
+        self.connected()
+    }
+
+    fn type_name(&self) -> &'static str {
+        type_name::<A>()
+    }
+
+    fn mailbox_len(&self) -> usize {
+        self.mailbox_len()
+    }
+
+    fn actor_id(&self) -> Option<Uuid> {
+        Some(self.id)
+    }
+
+    fn as_any(&self) -> Option<&Any> {
+        Some(self)
+    }
+
+    fn send_tracked(&self, msg: M) -> Result<SyncReceiver<()>, M> {
+        if self.connected() {
+            let (tx, rx) = sync_channel();
+            let _ = self.tx.unbounded_send(ContextProtocol::Envelope(
+                Envelope::new(TrackedEnvelope::new(Envelope::local(msg, None), tx))));
+            Ok(rx)
+        } else {
+            Err(msg)
+        }
+    }
+
+    fn try_send_tracked(&self, msg: M) -> Result<SyncReceiver<()>, TrySendError<M>> {
+        // mailbox is unbounded
+        Subscriber::send_tracked(self, msg).map_err(TrySendError::Closed)
+    }
+}
+
+/// Actor name without module path
+fn short_name<A: Actor>() -> &'static str {
+    let name = actor_name::<A>();
+    let end = name.find('<').unwrap_or(name.len());
+    match name[..end].rfind("::") {
+        Some(pos) => &name[pos+2..],
+        None => name,
+    }
+}
+
+/// Errors that can occur during `SyncAddress::call_timeout()` call
+#[derive(Debug, PartialEq)]
+pub enum CallError<E> {
+    /// Actor did not respond in time
+    Timeout,
+    /// Actor is stopped or dropped message without response
+    MailboxClosed,
+    /// Handler responded with error
+    Handler(E),
+}
+
+impl<E: error::Error + 'static> error::Error for CallError<E> {
+    fn description(&self) -> &str {
+        match *self {
+            CallError::Timeout => "call timed out",
+            CallError::MailboxClosed => "mailbox closed",
+            CallError::Handler(_) => "handler error",
+        }
+    }
+
+    fn source(&self) -> Option<&(error::Error + 'static)> {
+        match *self {
+            CallError::Handler(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for CallError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CallError::Timeout => write!(f, "call timed out"),
+            CallError::MailboxClosed => write!(f, "mailbox closed"),
+            CallError::Handler(ref err) => write!(f, "handler error: {}", err),
+        }
+    }
+}
+
+/// Error returned by `Subscriber::try_send()`, contains message that was not sent
+#[derive(Debug, PartialEq)]
+pub enum TrySendError<M> {
+    /// Subscriber can not accept message right now
+    Full(M),
+    /// Subscriber is disconnected
+    Closed(M),
+}
+
+impl<M> TrySendError<M> {
+    /// Returns message that was not sent
+    pub fn into_inner(self) -> M {
+        match self {
+            TrySendError::Full(msg) | TrySendError::Closed(msg) => msg,
+        }
+    }
+
+    /// Check if subscriber's buffer is full
+    pub fn is_full(&self) -> bool {
+        match *self {
+            TrySendError::Full(_) => true,
+            TrySendError::Closed(_) => false,
+        }
+    }
+
+    /// Same error with message `msg`
+    fn map<N>(self, msg: N) -> TrySendError<N> {
+        match self {
+            TrySendError::Full(_) => TrySendError::Full(msg),
+            TrySendError::Closed(_) => TrySendError::Closed(msg),
+        }
+    }
+}
+
+impl<M> fmt::Display for TrySendError<M> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TrySendError::Full(_) => write!(f, "subscriber is full"),
+            TrySendError::Closed(_) => write!(f, "subscriber is closed"),
+        }
+    }
+}
+
+/// Wakes up thread blocked in `SyncAddress::call_timeout()`
+struct ThreadNotify(Mutex<mpsc::Sender<()>>);
+
+impl executor::Notify for ThreadNotify {
+    fn notify(&self, _: usize) {
+        if let Ok(tx) = self.0.lock() {
+            let _ = tx.send(());
+        }
+    }
+}
+
+/// Block current thread until response is received or `deadline` passes
+fn wait_deadline<F, T, E>(rx: F, deadline: Instant) -> Result<T, CallError<E>>
+    where F: Future<Item=Result<T, E>, Error=Canceled>
+{
+    let (tx, notified) = mpsc::channel();
+    let notify = Arc::new(ThreadNotify(Mutex::new(tx)));
+    let mut rx = executor::spawn(rx);
+
+    loop {
+        match rx.poll_future_notify(&notify, 0) {
+            Ok(Async::Ready(Ok(item))) => return Ok(item),
+            Ok(Async::Ready(Err(err))) => return Err(CallError::Handler(err)),
+            Ok(Async::NotReady) => (),
+            // message is dropped once deadline passes
+            Err(_) if Instant::now() >= deadline => return Err(CallError::Timeout),
+            Err(_) => return Err(CallError::MailboxClosed),
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return Err(CallError::Timeout)
+        }
+        if let Err(mpsc::RecvTimeoutError::Timeout) = notified.recv_timeout(deadline - now) {
+            return Err(CallError::Timeout)
+        }
+    }
 }
 
 /// `Send` address of the actor. Actor can run in differend thread
 pub struct SyncAddress<A> where A: Actor {
     tx: sync::UnboundedSender<Envelope<A>>,
     closed: Cell<bool>,
+    id: Uuid,
 }
 
 unsafe impl<A> Send for SyncAddress<A> where A: Actor {}
 unsafe impl<A> Sync for SyncAddress<A> where A: Actor {}
+impl<A> UnwindSafe for SyncAddress<A> where A: Actor {}
+impl<A> RefUnwindSafe for SyncAddress<A> where A: Actor {}
 
 impl<A> Clone for SyncAddress<A> where A: Actor {
     fn clone(&self) -> Self {
-        SyncAddress{tx: self.tx.clone(), closed: self.closed.clone()}
+        SyncAddress{tx: self.tx.clone(), closed: self.closed.clone(), id: self.id}
+    }
+}
+
+impl<A> fmt::Debug for SyncAddress<A> where A: Actor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SyncAddress<{}>(id: {})", actor_name::<A>(), self.id.hyphenated())
+    }
+}
+
+/// Short form for logs, i.e. `SyncAddress<ProcessSignals>(3f4a)`
+impl<A> fmt::Display for SyncAddress<A> where A: Actor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SyncAddress<{}>({})", short_name::<A>(), &self.id.simple().to_string()[..4])
+    }
+}
+
+impl<'a, A> From<&'a SyncAddress<A>> for SyncAddress<A> where A: Actor {
+    fn from(addr: &'a SyncAddress<A>) -> Self {
+        addr.clone()
     }
 }
 
 impl<A> SyncAddress<A> where A: Actor {
 
-    pub(crate) fn new(sender: sync::UnboundedSender<Envelope<A>>) -> SyncAddress<A> {
-        SyncAddress{tx: sender, closed: Cell::new(false)}
+    pub(crate) fn new(sender: sync::UnboundedSender<Envelope<A>>, id: Uuid) -> SyncAddress<A> {
+        SyncAddress{tx: sender, closed: Cell::new(false), id: id}
+    }
+
+    /// Id of the actor, all addresses of the actor share the same id.
+    pub fn id(&self) -> Uuid {
+        self.id
     }
 
     /// Indicates if address is still connected to the actor.
     pub fn connected(&self) -> bool {
-        !self.closed.get()
+        !self.closed.get() && !self.tx.is_closed()
+    }
+
+    /// Number of messages sent with `SyncAddress` that actor
+    /// has not handled yet.
+    pub fn mailbox_len(&self) -> usize {
+        self.tx.len()
+    }
+
+    pub(crate) fn send_envelope(&self, env: Envelope<A>) {
+        if self.tx.unbounded_send(env).is_err() {
+            self.closed.set(true)
+        }
+    }
+
+    /// Move running actor to arbiter `arb`.
+    ///
+    /// Actor stops processing messages, its state is moved to arbiter's thread,
+    /// so actor has to be `Send`, and actor starts again with new context,
+    /// `Actor::started` is called again. Sync mailbox is moved together with
+    /// the actor, existing `SyncAddress` holders keep working and messages that
+    /// are not processed yet get delivered in new arbiter. Local `Address`
+    /// gets disconnected, spawned futures and streams are dropped.
+    /// Supervised actors can not be migrated.
+    ///
+    /// Returned future resolves after actor is started in new arbiter,
+    /// it fails if actor can not be migrated or either arbiter is not running.
+    /// If arbiter `arb` is stopped or stopping, actor is started again
+    /// in current arbiter and returned future fails.
+    pub fn migrate_to(&self, arb: &SyncAddress<Arbiter>) -> impl Future<Item=(), Error=()>
+        where A: Actor<Context=Context<A>> + Send
+    {
+        let (tx, rx) = sync_channel();
+        let arb = arb.clone();
+        self.send_envelope(Context::migration(move |act: A, msgs| {
+            // actor comes back if target arbiter drops the job
+            let state = Arc::new(Mutex::new(Some((act, msgs))));
+            let moved = Arc::clone(&state);
+            let job = arb.call_fut(Execute::new(move || -> Result<(), ()> {
+                if let Some((act, msgs)) = moved.lock().ok().and_then(|mut state| state.take()) {
+                    Context::restore(act, msgs);
+                    let _ = tx.send(());
+                }
+                Ok(())
+            }));
+            Arbiter::handle().spawn(job.then(move |_| {
+                if let Some((act, msgs)) = state.lock().ok().and_then(|mut state| state.take()) {
+                    warn!("Can not migrate actor {}, arbiter is not running", actor_name::<A>());
+                    Context::restore(act, msgs);
+                }
+                Ok(())
+            }));
+        }));
+        rx.map_err(|_| ())
     }
 
     /// Send message `M` to actor `A`. Message cold be sent to actor running in
@@ -188,6 +796,37 @@ impl<A> SyncAddress<A> where A: Actor {
         }
     }
 
+    /// Send message `M` to actor `A`, same as `send` but logs warning
+    /// if actor is stopped and message is lost.
+    pub fn send_or_log<M: 'static + Send>(&self, msg: M)
+        where A: Handler<M> + ResponseType<M>,
+              A::Item: Send,
+              A::Error: Send,
+              A: ToEnvelope<A, <A as Actor>::Context, M>,
+    {
+        if self.tx.unbounded_send(
+            <A as ToEnvelope<A, <A as Actor>::Context, M>>::pack(msg, None)).is_err()
+        {
+            self.closed.set(true);
+            warn!("Message {} is lost, actor {} is stopped", type_name::<M>(), actor_name::<A>());
+        }
+    }
+
+    /// Send message `M` to actor `A` with deadline.
+    ///
+    /// If actor does not start handling message before `deadline`,
+    /// message get dropped and counted in `Context::dropped_messages()`.
+    pub fn send_deadline<M: 'static + Send>(&self, msg: M, deadline: Instant)
+        where A: Handler<M> + ResponseType<M>,
+              A::Item: Send,
+              A::Error: Send,
+              A: ToEnvelope<A, <A as Actor>::Context, M>,
+    {
+        self.send_envelope(
+            <A as ToEnvelope<A, <A as Actor>::Context, M>>::pack(msg, None)
+                .with_deadline(deadline))
+    }
+
     /// Send message to actor `A` and asyncronously wait for response.
     pub fn call<B: Actor, M: 'static + Send>(&self, _: &B, msg: M) -> Request<A, B, M>
         where A: Handler<M>,
@@ -223,6 +862,57 @@ impl<A> SyncAddress<A> where A: Actor {
         rx
     }
 
+    /// Send message to actor `A` and block current thread until actor
+    /// responds or `timeout` expires.
+    ///
+    /// This method is intended for sync code outside of event loop, i.e. tests
+    /// or cli handlers. It must not be called from actor's own arbiter thread,
+    /// actor can not handle message while its thread is blocked.
+    ///
+    /// `timeout` becomes deadline of the message, handler sees time left with
+    /// `Context::remaining_budget()` and requests it makes inherit the deadline.
+    pub fn call_timeout<M>(&self, msg: M, timeout: Duration)
+                           -> Result<A::Item, CallError<A::Error>>
+        where A: Handler<M>,
+              M: 'static,
+              A::Item: Send,
+              A::Error: Send,
+              A: ToEnvelope<A, <A as Actor>::Context, M>,
+    {
+        let deadline = Arbiter::now() + timeout;
+
+        // handler and its nested requests get the same deadline,
+        // blocked thread waits in real time
+        let (tx, rx) = sync_channel();
+        self.send_envelope(
+            <A as ToEnvelope<A, <A as Actor>::Context, M>>::pack(msg, Some(tx))
+                .with_deadline(deadline));
+        wait_deadline(rx, Instant::now() + timeout)
+    }
+
+    #[cfg(feature="test-utils")]
+    /// Call `f` with the actor between messages, returned receiver resolves
+    /// to the result of `f`.
+    pub fn inspect<F, R>(&self, f: F) -> SyncReceiver<R>
+        where F: FnOnce(&A) -> R + Send + 'static, R: Send + 'static
+    {
+        let (tx, rx) = sync_channel();
+        self.send_envelope(Envelope::new(Inspect::new(f, tx)));
+        rx
+    }
+
+    #[cfg(feature="test-utils")]
+    /// Call `f` with the actor and block current thread until result of `f`
+    /// is available or `timeout` expires, see `SyncAddress::call_timeout()`.
+    pub fn inspect_timeout<F, R>(&self, f: F, timeout: Duration) -> Result<R, CallError<()>>
+        where F: FnOnce(&A) -> R + Send + 'static, R: Send + 'static
+    {
+        let deadline = Arbiter::now() + timeout;
+        let (tx, rx) = sync_channel();
+        self.send_envelope(Envelope::new(Inspect::new(f, tx)).with_deadline(deadline));
+        wait_deadline(rx.map(Ok), Instant::now() + timeout)
+    }
+
     /// Get `Subscriber` for specific message type
     pub fn subscriber<M: 'static + Send>(&self) -> Box<Subscriber<M> + Send>
         where A: Handler<M>,
@@ -232,6 +922,44 @@ impl<A> SyncAddress<A> where A: Actor {
     {
         Box::new(self.clone())
     }
+
+    /// Get cloneable `Recipient` for specific message type
+    pub fn recipient<M: 'static + Send>(&self) -> Recipient<M>
+        where A: Handler<M>,
+              A::Item: Send,
+              A::Error: Send,
+              A: ToEnvelope<A, <A as Actor>::Context, M>,
+    {
+        Recipient::new(self.clone())
+    }
+
+    /// Subscribe actor to all `targets` at once
+    ///
+    /// Returned `Subscription` unsubscribes actor from all of the targets.
+    pub fn subscribe_all(&self, targets: Vec<SubscribeTarget<A>>) -> Subscription {
+        Subscription::new(self, targets)
+    }
+}
+
+impl<A> SyncAddress<A> where A: Actor<Context=Context<A>> {
+
+    /// Send message that is handled by handler registered with
+    /// `Context::handle()`. Message is dropped if actor does not handle it.
+    pub fn send_dynamic<M: DynamicMessage>(&self, msg: M) {
+        self.send_envelope(Envelope::new(DynamicEnvelope::<A, M>::new(msg, None)))
+    }
+
+    /// Send dynamic message and asyncronously wait for response.
+    ///
+    /// Response is canceled if actor does not handle message of type `M`.
+    pub fn call_dynamic<M: DynamicMessage>(&self, msg: M)
+                                           -> SyncReceiver<Result<M::Item, M::Error>>
+    {
+        let (tx, rx) = sync_channel();
+        self.send_envelope(
+            Envelope::with_request(DynamicEnvelope::<A, M>::new(msg, Some(tx)), true));
+        rx
+    }
 }
 
 impl<A, M> Subscriber<M> for SyncAddress<A>
@@ -249,4 +977,114 @@ impl<A, M> Subscriber<M> for SyncAddress<A>
             Err(msg)
         }
     }
+
+    fn connected(&self) -> bool {
+        self.connected()
+    }
+
+    fn type_name(&self) -> &'static str {
+        type_name::<A>()
+    }
+
+    fn mailbox_len(&self) -> usize {
+        self.mailbox_len()
+    }
+
+    fn actor_id(&self) -> Option<Uuid> {
+        Some(self.id)
+    }
+
+    fn as_any(&self) -> Option<&Any> {
+        Some(self)
+    }
+
+    fn send_tracked(&self, msg: M) -> Result<SyncReceiver<()>, M> {
+        if self.connected() {
+            let (tx, rx) = sync_channel();
+            self.send_envelope(Envelope::new(TrackedEnvelope::new(
+                <A as ToEnvelope<A, <A as Actor>::Context, M>>::pack(msg, None), tx)));
+            Ok(rx)
+        } else {
+            Err(msg)
+        }
+    }
+
+    fn try_send_tracked(&self, msg: M) -> Result<SyncReceiver<()>, TrySendError<M>> {
+        // mailbox is unbounded
+        Subscriber::send_tracked(self, msg).map_err(TrySendError::Closed)
+    }
+}
+
+/// Cloneable subscriber
+///
+/// `Recipient` could be stored in several places at once, i.e. registered
+/// with multiple subscriber lists. Cloning recipient does not clone the actor,
+/// all clones send messages to the same mailbox.
+pub struct Recipient<M: 'static>(Box<CloneSubscriber<M>>);
+
+impl<M: 'static> Recipient<M> {
+    /// Create recipient from cloneable subscriber, i.e. `SyncAddress<A>`
+    pub fn new<S>(sub: S) -> Recipient<M> where S: Subscriber<M> + Clone + Send + 'static {
+        Recipient(Box::new(sub))
+    }
+}
+
+impl<M: 'static> Clone for Recipient<M> {
+    fn clone(&self) -> Self {
+        Recipient(self.0.clone_box())
+    }
+}
+
+impl<M: 'static> Subscriber<M> for Recipient<M> {
+    fn send(&self, msg: M) -> Result<(), M> {
+        self.0.send(msg)
+    }
+
+    fn connected(&self) -> bool {
+        self.0.connected()
+    }
+
+    fn send_tracked(&self, msg: M) -> Result<SyncReceiver<()>, M> {
+        self.0.send_tracked(msg)
+    }
+
+    fn try_send(&self, msg: M) -> Result<(), TrySendError<M>> {
+        self.0.try_send(msg)
+    }
+
+    fn try_send_tracked(&self, msg: M) -> Result<SyncReceiver<()>, TrySendError<M>> {
+        self.0.try_send_tracked(msg)
+    }
+
+    fn type_name(&self) -> &'static str {
+        self.0.type_name()
+    }
+
+    fn mailbox_len(&self) -> usize {
+        self.0.mailbox_len()
+    }
+
+    fn actor_id(&self) -> Option<Uuid> {
+        self.0.actor_id()
+    }
+
+    fn as_any(&self) -> Option<&Any> {
+        self.0.as_any()
+    }
+}
+
+impl<M: 'static> fmt::Debug for Recipient<M> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Recipient<{}>(connected: {})", type_name::<M>(), self.connected())
+    }
+}
+
+trait CloneSubscriber<M: 'static>: Subscriber<M> + Send {
+    fn clone_box(&self) -> Box<CloneSubscriber<M>>;
+}
+
+impl<M: 'static, S> CloneSubscriber<M> for S where S: Subscriber<M> + Clone + Send + 'static {
+    fn clone_box(&self) -> Box<CloneSubscriber<M>> {
+        Box::new(self.clone())
+    }
 }
@@ -1,17 +1,38 @@
 use std;
-use std::thread;
-use std::cell::RefCell;
+use std::{error, fmt, io, panic, thread};
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::rc::{Rc, Weak};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::RecvTimeoutError;
+use std::time::{Duration, Instant};
 use uuid::Uuid;
-use tokio_core::reactor::{Core, Handle};
+use tokio_core::reactor::{Core, Handle, Remote};
+use futures::{Async, Future, Poll, Stream};
+use futures::task::{self, Task};
 use futures::sync::oneshot::{channel, Sender};
+use futures::unsync::oneshot;
 
-use actor::{Actor, Handler, ResponseType, ActorContext};
-use address::{Address, SyncAddress};
-use context::Context;
-use msgs::{Execute, StartActor, StopArbiter};
+use actor::{Actor, AsyncContext, Handler, ResponseType, ActorContext, SpawnHandle};
+use actors::events::SystemEvents;
+use address::{Address, CallError, SyncAddress};
+use clock::{Delay, TestClock, Ticker};
+use context::{Context, AsyncContextApi, ContextFutureSpawner};
+use fut::{self, ActorFuture};
+use msgs::{Execute, ExecuteAsync, ForwardTo, GetRemote, StartActor, StopArbiter, SystemArbiterUpdated,
+           SystemExit};
 use message::Response;
-use registry::{Registry, SystemRegistry};
-use system::{System, RegisterArbiter, UnregisterArbiter};
+use registry::{ActorsCounter, Registry, SystemRegistry};
+use system::{System, ArbiterDied, RegisterArbiter, UnregisterArbiter};
+
+/// Default number of seconds arbiter waits for in-flight jobs on `StopArbiter`
+/// if drain timeout is not set
+const JOBS_TIMEOUT: u64 = 30;
+
+/// Number of event loop iterations `Arbiter::turn()` runs, enough for
+/// a message to travel through several actors
+const IDLE_TURNS: usize = 16;
 
 thread_local!(
     static HND: RefCell<Option<Handle>> = RefCell::new(None);
@@ -23,8 +44,102 @@ thread_local!(
     static SYSARB: RefCell<Option<SyncAddress<Arbiter>>> = RefCell::new(None);
     static SYSNAME: RefCell<Option<String>> = RefCell::new(None);
     static SYSREG: RefCell<Option<SystemRegistry>> = RefCell::new(None);
+    static DOWN: Cell<bool> = Cell::new(false);
+    static FROZEN: RefCell<Option<Vec<Task>>> = RefCell::new(None);
+    static DRAIN: Cell<Option<Duration>> = Cell::new(None);
+    static PENDING: Cell<usize> = Cell::new(0);
+    static STREAMS: Cell<usize> = Cell::new(0);
+    static DRAINED: RefCell<Option<Task>> = RefCell::new(None);
+    static THROUGHPUT: Cell<Option<usize>> = Cell::new(None);
+    static MAILBOX: Cell<usize> = Cell::new(0);
+    static HIGH: Arc<AtomicUsize> = Arc::new(AtomicUsize::new(0));
+    static CLOCK: RefCell<Option<TestClock>> = RefCell::new(None);
+    static CORE: RefCell<Option<Weak<RefCell<Core>>>> = RefCell::new(None);
+    static INTERVALS: RefCell<(SpawnHandle, Vec<(SpawnHandle, oneshot::Sender<()>)>)> =
+        RefCell::new((SpawnHandle::default(), Vec::new()));
+    static COUNTERS: Counters = Counters::default();
 );
 
+/// Counters behind `Arbiter::metrics()`
+#[derive(Default)]
+struct Counters {
+    processed: Cell<u64>,
+    dropped: Cell<u64>,
+    started: Cell<u64>,
+    stopped: Cell<u64>,
+    since: Cell<Option<Instant>>,
+}
+
+impl Counters {
+    fn reset(&self) {
+        self.processed.set(0);
+        self.dropped.set(0);
+        self.started.set(0);
+        self.stopped.set(0);
+        self.since.set(Some(Instant::now()));
+    }
+}
+
+/// Snapshot of arbiter's runtime counters, see `Arbiter::metrics()`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ArbiterMetrics {
+    /// Number of messages handled by actors of the arbiter
+    pub messages_processed: u64,
+    /// Number of messages dropped because their deadline has passed
+    pub messages_dropped: u64,
+    /// Number of actors started in the arbiter
+    pub actors_started: u64,
+    /// Number of actors stopped in the arbiter
+    pub actors_stopped: u64,
+    /// Time since arbiter started
+    pub uptime: Duration,
+}
+
+/// Arbiter's thread-locals guard
+///
+/// Guard clears all arbiter's thread-locals on drop and marks them as
+/// torn down. Guard has to be dropped before event loop, so references returned
+/// by `Arbiter::handle()` and `Arbiter::registry()` can not outlive event loop.
+pub(crate) struct ArbiterGuard;
+
+impl ArbiterGuard {
+    pub(crate) fn new() -> ArbiterGuard {
+        DOWN.with(|cell| cell.set(false));
+        ArbiterGuard
+    }
+}
+
+impl Drop for ArbiterGuard {
+    fn drop(&mut self) {
+        DOWN.with(|cell| cell.set(true));
+        ADDR.with(|cell| cell.borrow_mut().take());
+        STOP.with(|cell| cell.borrow_mut().take());
+        REG.with(|cell| cell.borrow_mut().take());
+        NAME.with(|cell| cell.borrow_mut().take());
+        SYS.with(|cell| cell.borrow_mut().take());
+        SYSARB.with(|cell| cell.borrow_mut().take());
+        SYSNAME.with(|cell| cell.borrow_mut().take());
+        SYSREG.with(|cell| cell.borrow_mut().take());
+        FROZEN.with(|cell| cell.borrow_mut().take());
+        CLOCK.with(|cell| cell.borrow_mut().take());
+        CORE.with(|cell| cell.borrow_mut().take());
+        DRAIN.with(|cell| cell.set(None));
+        DRAINED.with(|cell| cell.borrow_mut().take());
+        INTERVALS.with(|cell| cell.borrow_mut().1.clear());
+        THROUGHPUT.with(|cell| cell.set(None));
+        MAILBOX.with(|cell| cell.set(0));
+        HND.with(|cell| cell.borrow_mut().take());
+    }
+}
+
+fn not_running(name: &str) -> ! {
+    if DOWN.with(|cell| cell.get()) {
+        panic!("{} is torn down", name)
+    } else {
+        panic!("{} is not running", name)
+    }
+}
+
 /// Event loop controller
 ///
 /// Arbiter controls event loop in it's thread. Each arbiter runs in separate
@@ -33,6 +148,12 @@ thread_local!(
 pub struct Arbiter {
     id: Uuid,
     sys: bool,
+    name: Option<String>,
+    register: bool,
+    stopping: bool,
+    jobs: usize,
+    jobs_done: Option<Task>,
+    jobs_timeout: Duration,
 }
 
 
@@ -40,9 +161,14 @@ impl Actor for Arbiter {
     type Context = Context<Self>;
 
     fn started(&mut self, ctx: &mut Context<Self>) {
+        // arbiter has to handle control messages while frozen
+        ctx.address_cell().set_control();
+
         // register arbiter within system
-        Arbiter::system().send(
-            RegisterArbiter(self.id.simple().to_string(), ctx.address()));
+        if self.register {
+            Arbiter::system().send_or_log(
+                RegisterArbiter(self.id.simple().to_string(), self.name.take(), ctx.address()));
+        }
     }
 }
 
@@ -50,70 +176,79 @@ impl Arbiter {
 
     /// Spawn new thread and run event loop in spawned thread.
     /// Returns address of newly created arbiter.
+    ///
+    /// This is shorthand for `ArbiterBuilder`, use builder for more configuration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if arbiter's thread can not be started.
     pub fn new(name: Option<String>) -> SyncAddress<Arbiter> {
-        let (tx, rx) = std::sync::mpsc::channel();
-
-        let id = Uuid::new_v4();
-        let sys = Arbiter::system();
-        let sys_name = Arbiter::system_name();
-        let sys_arbiter = Arbiter::system_arbiter();
-        let sys_registry = Arbiter::system_registry().clone();
-        let name = if let Some(n) = name {
-            format!("arbiter:{:?}:{:?}", id.hyphenated().to_string(), n)
-        } else {
-            format!("arbiter:{:?}", id.hyphenated().to_string())
-        };
-
-        let _ = thread::Builder::new().name(name.clone()).spawn(move|| {
-            let mut core = Core::new().unwrap();
-
-            let (stop_tx, stop_rx) = channel();
-            HND.with(|cell| *cell.borrow_mut() = Some(core.handle()));
-            STOP.with(|cell| *cell.borrow_mut() = Some(stop_tx));
-            NAME.with(|cell| *cell.borrow_mut() = Some(name));
-
-            // system
-            SYS.with(|cell| *cell.borrow_mut() = Some(sys));
-            SYSARB.with(|cell| *cell.borrow_mut() = Some(sys_arbiter));
-            SYSNAME.with(|cell| *cell.borrow_mut() = Some(sys_name));
-            SYSREG.with(|cell| *cell.borrow_mut() = Some(sys_registry));
-
-            // start arbiter
-            let (addr, saddr) = Actor::start(
-                Arbiter {sys: false, id: id});
-            ADDR.with(|cell| *cell.borrow_mut() = Some(addr));
+        let mut builder = ArbiterBuilder::new();
+        if let Some(name) = name {
+            builder = builder.name(name);
+        }
+        match builder.spawn() {
+            Ok(addr) => addr,
+            Err(err) => panic!("Can not start Arbiter: {}", err),
+        }
+    }
 
-            if tx.send(saddr).is_err() {
-                error!("Can not start Arbiter, remote side is dead");
-            } else {
-                // run loop
-                let _ = match core.run(stop_rx) {
-                    Ok(code) => code,
-                    Err(_) => 1,
-                };
-            }
+    /// Returns builder for new arbiter.
+    pub fn builder() -> ArbiterBuilder {
+        ArbiterBuilder::new()
+    }
 
-            // unregister arbiter
-            Arbiter::system().send(
-                UnregisterArbiter(id.simple().to_string()));
-        });
+    /// Start arbiter on top of existing event loop.
+    ///
+    /// Method has to be called from the thread that runs event loop
+    /// of the `handle`, thread does not need to belong to any system,
+    /// arbiter joins `system` and registers itself within it.
+    /// Arbiter does not own event loop, so `StopArbiter` message does not stop
+    /// event loop, it is responsibility of the event loop owner.
+    ///
+    /// This is shorthand for `ArbiterBuilder::start_with_handle()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if current thread runs arbiter already.
+    pub fn new_with_handle(handle: Handle, system: SystemInfo) -> SyncAddress<Arbiter> {
+        ArbiterBuilder::new().start_with_handle(handle, system)
+    }
 
-        rx.recv().unwrap()
+    /// Returns system of the current thread, it could be sent to
+    /// other thread and used with `Arbiter::new_with_handle()`.
+    pub fn system_info() -> SystemInfo {
+        SystemInfo {
+            sys: Arbiter::system(),
+            name: Arbiter::system_name(),
+            arbiter: Arbiter::system_arbiter(),
+            registry: Arbiter::system_registry().clone(),
+        }
     }
 
-    pub(crate) fn new_system(name: String) -> Core {
-        let core = Core::new().unwrap();
+    /// Start system arbiter on top of `core`, new system registry is
+    /// created if `sys_registry` is not provided.
+    pub(crate) fn new_system(name: String, core: Core, sys_registry: Option<SystemRegistry>)
+                             -> Rc<RefCell<Core>>
+    {
+        DOWN.with(|cell| cell.set(false));
+        COUNTERS.with(|counters| counters.reset());
         HND.with(|cell| *cell.borrow_mut() = Some(core.handle()));
         REG.with(|cell| *cell.borrow_mut() = Some(Registry::new()));
         NAME.with(|cell| *cell.borrow_mut() = Some(name));
-        SYSREG.with(|cell| *cell.borrow_mut() = Some(SystemRegistry::new()));
+        SYSREG.with(|cell| *cell.borrow_mut() = Some(
+            sys_registry.unwrap_or_else(SystemRegistry::new)));
 
         // start arbiter
         let (addr, sys_addr) = Actor::start(
-            Arbiter {sys: true, id: Uuid::new_v4()});
+            Arbiter {sys: true, id: Uuid::new_v4(), name: None, register: true,
+                     stopping: false, jobs: 0, jobs_done: None,
+                     jobs_timeout: Duration::from_secs(JOBS_TIMEOUT)});
         ADDR.with(|cell| *cell.borrow_mut() = Some(addr));
         SYSARB.with(|cell| *cell.borrow_mut() = Some(sys_addr));
 
+        let core = Rc::new(RefCell::new(core));
+        CORE.with(|cell| *cell.borrow_mut() = Some(Rc::downgrade(&core)));
         core
     }
 
@@ -126,15 +261,20 @@ impl Arbiter {
     pub fn name() -> String {
         NAME.with(|cell| match *cell.borrow() {
             Some(ref name) => name.clone(),
-            None => panic!("Arbiter is not running"),
+            None => not_running("Arbiter"),
         })
     }
 
+    /// Check if current thread runs arbiter
+    pub(crate) fn in_arbiter() -> bool {
+        HND.with(|cell| cell.borrow().is_some())
+    }
+
     /// Returns current arbiter's address
     pub fn arbiter() -> Address<Arbiter> {
         ADDR.with(|cell| match *cell.borrow() {
             Some(ref addr) => addr.clone(),
-            None => panic!("Arbiter is not running"),
+            None => not_running("Arbiter"),
         })
     }
 
@@ -142,7 +282,7 @@ impl Arbiter {
     pub fn system() -> SyncAddress<System> {
         SYS.with(|cell| match *cell.borrow() {
             Some(ref addr) => addr.clone(),
-            None => panic!("System is not running"),
+            None => not_running("System"),
         })
     }
 
@@ -150,23 +290,61 @@ impl Arbiter {
     pub fn system_arbiter() -> SyncAddress<Arbiter> {
         SYSARB.with(|cell| match *cell.borrow() {
             Some(ref addr) => addr.clone(),
-            None => panic!("System is not running"),
+            None => not_running("System"),
         })
     }
 
+    /// Stop system and all its arbiters with exit code `code`.
+    ///
+    /// Sends `SystemExit(code)` to the system of the current thread. If current
+    /// thread does not belong to running system, process exits immediately
+    /// with `std::process::exit(code)`.
+    pub fn shutdown_all(code: i32) {
+        let sys = SYS.try_with(|cell| cell.borrow().clone()).ok().and_then(|sys| sys);
+        match sys {
+            Some(sys) => sys.send(SystemExit(code)),
+            None => std::process::exit(code),
+        }
+    }
+
     /// This function returns system name,
     pub fn system_name() -> String {
         SYSNAME.with(|cell| match *cell.borrow() {
             Some(ref name) => name.clone(),
-            None => panic!("System is not running"),
+            None => not_running("System"),
         })
     }
 
-    /// This function returns system registry,
+    /// This function returns system registry, panics if system is not running.
     pub fn system_registry() -> &'static SystemRegistry {
         SYSREG.with(|cell| match *cell.borrow() {
             Some(ref reg) => unsafe{std::mem::transmute(reg)},
-            None => panic!("System is not running"),
+            None => not_running("System"),
+        })
+    }
+
+    /// Returns handle of system registry, `None` if current thread does not
+    /// belong to running system.
+    ///
+    /// Unlike `system_registry()` this function does not panic, so it could be
+    /// used by code that works with or without system.
+    pub fn try_system_registry() -> Option<SystemRegistry> {
+        SYSREG.with(|cell| cell.borrow().clone())
+    }
+
+    /// Live actors counter of the current system
+    pub(crate) fn actors_counter() -> Option<Arc<ActorsCounter>> {
+        SYSREG.with(|cell| cell.borrow().as_ref().map(|reg| Arc::clone(&reg.actors)))
+    }
+
+    /// Address of `SystemEvents` service if it is running
+    pub(crate) fn lifecycle_events() -> Option<SyncAddress<SystemEvents>> {
+        SYSREG.with(|cell| match *cell.borrow() {
+            Some(ref reg) => match reg.events.lock() {
+                Ok(events) => events.clone(),
+                Err(_) => None,
+            },
+            None => None,
         })
     }
 
@@ -174,17 +352,690 @@ impl Arbiter {
     pub fn handle() -> &'static Handle {
         HND.with(|cell| match *cell.borrow() {
             Some(ref h) => unsafe{std::mem::transmute(h)},
-            None => panic!("Arbiter is not running"),
+            None => not_running("Arbiter"),
         })
     }
 
+    /// Returns `Remote` handle of current event loop.
+    ///
+    /// Unlike `Handle`, `Remote` is `Send`, so it could be passed to other
+    /// threads and used to spawn futures onto this arbiter's event loop.
+    pub fn remote() -> Remote {
+        Arbiter::handle().remote().clone()
+    }
+
     /// This function returns arbiter's registry,
     pub fn registry() -> &'static Registry {
         REG.with(|cell| match *cell.borrow() {
             Some(ref reg) => unsafe{std::mem::transmute(reg)},
-            None => panic!("System is not running"),
+            None => not_running("System"),
+        })
+    }
+
+    /// Max number of messages actor handles before yielding to other actors
+    pub(crate) fn throughput() -> Option<usize> {
+        THROUGHPUT.with(|cell| cell.get())
+    }
+
+    /// Initial capacity of actor's local mailbox
+    pub(crate) fn mailbox_capacity() -> usize {
+        MAILBOX.with(|cell| cell.get())
+    }
+
+    /// Number of `High` priority actors of the current arbiter that
+    /// have messages to handle, counter could be updated from any thread
+    pub(crate) fn high_priority_counter() -> Arc<AtomicUsize> {
+        HIGH.with(Arc::clone)
+    }
+
+    /// Check if any `High` priority actor of the current arbiter
+    /// has messages to handle
+    pub(crate) fn high_priority_ready() -> bool {
+        HIGH.with(|cell| cell.load(Ordering::SeqCst) > 0)
+    }
+
+    /// Check if current arbiter is frozen.
+    ///
+    /// If it is, current task get notified when arbiter thaws.
+    pub(crate) fn frozen() -> bool {
+        FROZEN.with(|cell| match *cell.borrow_mut() {
+            Some(ref mut tasks) => {
+                if !tasks.iter().any(|task| task.will_notify_current()) {
+                    tasks.push(task::current());
+                }
+                true
+            }
+            None => false,
+        })
+    }
+
+    /// Install test clock in current arbiter.
+    ///
+    /// Timers created in current arbiter after this call use `clock`
+    /// instead of system time, see `TestClock`.
+    pub fn set_clock(clock: TestClock) {
+        CLOCK.with(|cell| *cell.borrow_mut() = Some(clock));
+    }
+
+    /// Current time of the arbiter, time of the test clock if it is installed
+    pub fn now() -> Instant {
+        CLOCK.with(|cell| match *cell.borrow() {
+            Some(ref clock) => clock.now(),
+            None => Instant::now(),
+        })
+    }
+
+    /// Snapshot of runtime counters of the current arbiter.
+    ///
+    /// Counters are kept in arbiter's thread-locals, so this method reports
+    /// arbiter of the calling thread. Send `msgs::SystemMetrics` to `System`
+    /// actor for totals of all arbiters.
+    pub fn metrics() -> ArbiterMetrics {
+        COUNTERS.with(|counters| ArbiterMetrics {
+            messages_processed: counters.processed.get(),
+            messages_dropped: counters.dropped.get(),
+            actors_started: counters.started.get(),
+            actors_stopped: counters.stopped.get(),
+            uptime: counters.since.get().map(|since| since.elapsed()).unwrap_or_default(),
+        })
+    }
+
+    /// Count message taken from actor's mailbox
+    pub(crate) fn message_handled(dropped: bool) {
+        let _ = COUNTERS.try_with(|counters| if dropped {
+            counters.dropped.set(counters.dropped.get() + 1)
+        } else {
+            counters.processed.set(counters.processed.get() + 1)
+        });
+    }
+
+    /// Count actor start or stop
+    pub(crate) fn actor_lifecycle(started: bool) {
+        let _ = COUNTERS.try_with(|counters| if started {
+            counters.started.set(counters.started.get() + 1)
+        } else {
+            counters.stopped.set(counters.stopped.get() + 1)
+        });
+    }
+
+    pub(crate) fn clock() -> Option<TestClock> {
+        CLOCK.with(|cell| cell.borrow().clone())
+    }
+
+    /// Run ready tasks of system's event loop if the loop is not running,
+    /// returns `false` if loop is running already or it is not available.
+    pub(crate) fn turn() -> bool {
+        let core = CORE.with(|cell| cell.borrow().as_ref().and_then(Weak::upgrade));
+        let core = match core {
+            Some(core) => core,
+            None => return false,
+        };
+        let mut core = match core.try_borrow_mut() {
+            Ok(core) => core,
+            Err(_) => return false,
+        };
+        for _ in 0..IDLE_TURNS {
+            core.turn(Some(Duration::from_millis(0)));
+        }
+        true
+    }
+
+    /// Call `f` every `dur` in current arbiter's event loop.
+    ///
+    /// Periodic task runs until it get cancelled with `Arbiter::cancel_interval()`
+    /// or arbiter stops.
+    pub fn spawn_interval<F>(dur: Duration, f: F) -> SpawnHandle
+        where F: Fn() + 'static
+    {
+        let (tx, rx) = oneshot::channel();
+        let handle = INTERVALS.with(|cell| {
+            let mut intervals = cell.borrow_mut();
+            intervals.0 = intervals.0.next();
+            let handle = intervals.0;
+            intervals.1.push((handle, tx));
+            handle
+        });
+
+        Arbiter::handle().spawn(
+            Ticker::new(dur)
+                .for_each(move |_| {
+                    f();
+                    Ok(())
+                })
+                .select2(rx)
+                .then(move |_| {
+                    let _ = INTERVALS.try_with(
+                        |cell| cell.borrow_mut().1.retain(|item| item.0 != handle));
+                    Ok(())
+                }));
+        handle
+    }
+
+    /// Cancel periodic task started with `Arbiter::spawn_interval()`.
+    ///
+    /// Returns `false` if task is not running in current arbiter.
+    pub fn cancel_interval(handle: SpawnHandle) -> bool {
+        let item = INTERVALS.with(|cell| {
+            let mut intervals = cell.borrow_mut();
+            intervals.1.iter().position(|item| item.0 == handle)
+                .map(|idx| intervals.1.remove(idx))
+        });
+        if let Some((_, tx)) = item {
+            let _ = tx.send(());
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Set drain timeout for current arbiter.
+    ///
+    /// On `StopArbiter` message arbiter waits until all futures spawned by
+    /// actors in arbiter's thread complete, but not longer than `timeout`,
+    /// then event loop stops. Streams registered with `AsyncContext::add_stream()`
+    /// are not waited for. By default arbiter waits only for in-flight
+    /// `ExecuteAsync` jobs, but not longer than jobs timeout,
+    /// see `ArbiterBuilder::jobs_timeout()`.
+    pub fn set_drain_timeout(timeout: Duration) {
+        DRAIN.with(|cell| cell.set(Some(timeout)));
+    }
+
+    /// Actor's future is spawned in current thread
+    pub(crate) fn future_spawned() {
+        PENDING.with(|cell| cell.set(cell.get() + 1));
+    }
+
+    /// Actor's futures are completed or dropped
+    pub(crate) fn futures_completed(count: usize) {
+        PENDING.with(|cell| cell.set(cell.get().saturating_sub(count)));
+        Arbiter::check_drained();
+    }
+
+    /// Actor's stream is registered, streams are spawned as futures
+    /// but drain does not wait for them
+    pub(crate) fn stream_added() {
+        let _ = STREAMS.try_with(|cell| cell.set(cell.get() + 1));
+    }
+
+    /// Actor's stream is finished or dropped
+    pub(crate) fn stream_removed() {
+        let _ = STREAMS.try_with(|cell| cell.set(cell.get().saturating_sub(1)));
+        Arbiter::check_drained();
+    }
+
+    fn drained() -> bool {
+        let pending = PENDING.try_with(|cell| cell.get()).unwrap_or(0);
+        pending <= STREAMS.try_with(|cell| cell.get()).unwrap_or(0)
+    }
+
+    fn check_drained() {
+        if Arbiter::drained() {
+            if let Ok(Some(task)) = DRAINED.try_with(|cell| cell.borrow_mut().take()) {
+                task.notify();
+            }
+        }
+    }
+
+    /// Run blocking function in the system wide thread pool.
+    ///
+    /// Use this method for blocking io or cpu bound work, so arbiter's event loop
+    /// does not get blocked. Returned future resolves to the result of the function,
+    /// so handler can respond with `async_reply`. Error of the function is returned
+    /// as `CallError::Handler`, if function panics or system stops before function
+    /// is executed returned future resolves with `CallError::MailboxClosed`.
+    pub fn spawn_blocking<F, I, E>(f: F) -> impl Future<Item=I, Error=CallError<E>>
+        where F: FnOnce() -> Result<I, E> + Send + 'static,
+              I: Send + 'static,
+              E: Send + 'static,
+    {
+        Arbiter::system_registry().blocking_pool()
+            .call_fut(Execute::new(f))
+            .then(|res| match res {
+                Ok(Ok(res)) => Ok(res),
+                Ok(Err(err)) => Err(CallError::Handler(err)),
+                Err(_) => Err(CallError::MailboxClosed),
+            })
+    }
+
+    /// Run future in the arbiter's thread and wait for its result.
+    ///
+    /// Future is sent to arbiter with `ExecuteAsync` message. Error of the future
+    /// is returned as `CallError::Handler`, if arbiter stops before future
+    /// resolves returned future resolves with `CallError::MailboxClosed`.
+    pub fn run_in<F, I, E>(arb: &SyncAddress<Arbiter>, f: F)
+                           -> impl Future<Item=I, Error=CallError<E>>
+        where F: Future<Item=I, Error=E> + Send + 'static,
+              I: Send + 'static,
+              E: Send + 'static,
+    {
+        arb.call_fut(ExecuteAsync::new(f)).then(|res| match res {
+            Ok(Ok(res)) => Ok(res),
+            Ok(Err(err)) => Err(CallError::Handler(err)),
+            Err(_) => Err(CallError::MailboxClosed),
         })
     }
+
+    /// Reply with result of the job's future, event loop does not stop
+    /// until in-flight jobs complete.
+    fn job<M, I, E, F>(&mut self, fut: F) -> Response<Self, M>
+        where Self: ResponseType<M, Item=I, Error=E>,
+              F: Future<Item=I, Error=E> + 'static,
+              I: 'static, E: 'static,
+    {
+        self.jobs += 1;
+        Self::async_reply(
+            fut::wrap_future(fut)
+                .then(|res, act: &mut Arbiter, _: &mut Context<Arbiter>| {
+                    act.jobs -= 1;
+                    if act.jobs == 0 {
+                        if let Some(task) = act.jobs_done.take() {
+                            task.notify();
+                        }
+                    }
+                    fut::result(res)
+                }))
+    }
+}
+
+type PanicHandler = Box<Fn(Box<Any + Send>) + Send>;
+
+/// Arbiter configuration
+///
+/// Builder configures arbiter's thread and event loop before arbiter starts.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate actix;
+/// use actix::prelude::*;
+///
+/// fn main() {
+///     let sys = System::new("test");
+///
+///     let arb = Arbiter::builder()
+///         .name("worker")
+///         .stack_size(4 * 1024 * 1024)
+///         .throughput(32)
+///         .init(|| println!("worker started"))
+///         .spawn()
+///         .unwrap();
+///     arb.send(msgs::StopArbiter(0));
+///
+///     Arbiter::system().send(msgs::SystemExit(0));
+///     sys.run();
+/// }
+/// ```
+pub struct ArbiterBuilder {
+    name: Option<String>,
+    thread_name: Option<String>,
+    stack_size: Option<usize>,
+    panic_handler: Option<PanicHandler>,
+    throughput: Option<usize>,
+    mailbox_capacity: usize,
+    start_timeout: Option<Duration>,
+    jobs_timeout: Duration,
+    init: Option<Box<FnInit>>,
+    register: bool,
+}
+
+impl Default for ArbiterBuilder {
+    fn default() -> Self {
+        ArbiterBuilder::new()
+    }
+}
+
+impl ArbiterBuilder {
+
+    /// Create builder with default configuration
+    pub fn new() -> ArbiterBuilder {
+        ArbiterBuilder {
+            name: None,
+            thread_name: None,
+            stack_size: None,
+            panic_handler: None,
+            throughput: None,
+            mailbox_capacity: 0,
+            start_timeout: None,
+            jobs_timeout: Duration::from_secs(JOBS_TIMEOUT),
+            init: None,
+            register: true,
+        }
+    }
+
+    /// Set arbiter's name, named arbiter is available with `System::arbiter_for_name()`
+    pub fn name<T: ToString>(mut self, name: T) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Set name of arbiter's thread.
+    ///
+    /// By default thread name is generated from arbiter's id and name.
+    pub fn thread_name<T: ToString>(mut self, name: T) -> Self {
+        self.thread_name = Some(name.to_string());
+        self
+    }
+
+    /// Set stack size of arbiter's thread
+    pub fn stack_size(mut self, size: usize) -> Self {
+        self.stack_size = Some(size);
+        self
+    }
+
+    /// Set handler for panics in arbiter's thread.
+    ///
+    /// Handler is called with panic payload after event loop is dropped,
+    /// arbiter gets unregistered from the system. Without handler panic
+    /// terminates arbiter's thread.
+    pub fn panic_handler<F>(mut self, f: F) -> Self
+        where F: Fn(Box<Any + Send>) + Send + 'static
+    {
+        self.panic_handler = Some(Box::new(f));
+        self
+    }
+
+    /// Set max number of messages actor handles before other actors and
+    /// futures in arbiter's thread get chance to run.
+    ///
+    /// By default actor handles all messages available in its mailbox.
+    pub fn throughput(mut self, messages: usize) -> Self {
+        self.throughput = Some(std::cmp::max(messages, 1));
+        self
+    }
+
+    /// Set number of messages actor's local mailbox preallocates storage for.
+    pub fn mailbox_capacity(mut self, capacity: usize) -> Self {
+        self.mailbox_capacity = capacity;
+        self
+    }
+
+    /// Set max time `spawn()` waits for arbiter's thread to start.
+    ///
+    /// If arbiter does not start in time, `spawn()` returns
+    /// `ArbiterStartError::Timeout` and arbiter's thread exits without
+    /// running event loop. By default `spawn()` waits without limit.
+    pub fn start_timeout(mut self, timeout: Duration) -> Self {
+        self.start_timeout = Some(timeout);
+        self
+    }
+
+    /// Set max time arbiter waits for in-flight `ExecuteAsync` jobs
+    /// on `StopArbiter` message, 30 seconds by default.
+    ///
+    /// Timeout is not used if drain timeout is set with
+    /// `Arbiter::set_drain_timeout()`.
+    pub fn jobs_timeout(mut self, timeout: Duration) -> Self {
+        self.jobs_timeout = timeout;
+        self
+    }
+
+    /// Set initialization function.
+    ///
+    /// Function runs in arbiter's thread before event loop starts,
+    /// it could start actors with `Actor::start()`.
+    pub fn init<F>(mut self, f: F) -> Self where F: FnOnce() + Send + 'static {
+        self.init = Some(Box::new(f));
+        self
+    }
+
+    /// Register arbiter within system, `true` by default.
+    ///
+    /// Unregistered arbiter does not receive `StopArbiter` message on system exit
+    /// and is not available with `System::arbiter_for_name()`.
+    pub fn register(mut self, register: bool) -> Self {
+        self.register = register;
+        self
+    }
+
+    /// Spawn new thread and run arbiter's event loop in spawned thread.
+    /// Returns address of newly created arbiter.
+    ///
+    /// Arbiter's thread is not monitored with `JoinHandle`, event loop runs
+    /// inside `catch_unwind()` instead, so panic is detected in the same
+    /// thread. Registered arbiter sends `UnregisterArbiter` to the system
+    /// when event loop stops, or `ArbiterDied` if thread is going to die
+    /// because of panic without panic handler. Process abort can not be
+    /// observed either way.
+    pub fn spawn(self) -> Result<SyncAddress<Arbiter>, ArbiterStartError> {
+        let ArbiterBuilder { name, thread_name, stack_size, panic_handler,
+                             throughput, mailbox_capacity, start_timeout,
+                             jobs_timeout, init, register } = self;
+        let (tx, rx) = std::sync::mpsc::channel();
+        let abandoned = Arc::new(Mutex::new(false));
+        let handshake = Arc::clone(&abandoned);
+
+        let id = Uuid::new_v4();
+        let system = Arbiter::system_info();
+        let arb_name = name.clone();
+        let name = if let Some(n) = name {
+            format!("arbiter:{:?}:{:?}", id.hyphenated().to_string(), n)
+        } else {
+            format!("arbiter:{:?}", id.hyphenated().to_string())
+        };
+
+        let mut builder = thread::Builder::new().name(thread_name.unwrap_or_else(|| name.clone()));
+        if let Some(size) = stack_size {
+            builder = builder.stack_size(size);
+        }
+
+        builder.spawn(move|| {
+            let unregister = system.sys.clone();
+            let res = panic::catch_unwind(panic::AssertUnwindSafe(move || {
+                let mut core = Core::new().unwrap();
+                let _guard = ArbiterGuard::new();
+
+                let (stop_tx, stop_rx) = channel();
+                COUNTERS.with(|counters| counters.reset());
+                HND.with(|cell| *cell.borrow_mut() = Some(core.handle()));
+                STOP.with(|cell| *cell.borrow_mut() = Some(stop_tx));
+                NAME.with(|cell| *cell.borrow_mut() = Some(name));
+                THROUGHPUT.with(|cell| cell.set(throughput));
+                MAILBOX.with(|cell| cell.set(mailbox_capacity));
+
+                system.install();
+
+                // start arbiter
+                let (addr, saddr) = Actor::start(
+                    Arbiter {sys: false, id: id, name: arb_name, register: register,
+                             stopping: false, jobs: 0, jobs_done: None,
+                             jobs_timeout: jobs_timeout});
+                ADDR.with(|cell| *cell.borrow_mut() = Some(addr));
+
+                if let Some(init) = init {
+                    init.call_box();
+                }
+
+                // keep sync mailbox open while arbiter runs. Arbiter sends
+                // `RegisterArbiter` from its `started()`, which runs on first
+                // iteration of the loop. If caller drops returned address
+                // before that, last sync sender is gone, arbiter's context
+                // sees closed mailbox and stops before it registers, so
+                // `System::arbiter_for_name()` never finds it.
+                let _saddr = saddr.clone();
+
+                // caller could give up waiting for handshake
+                let started = match handshake.lock() {
+                    Ok(abandoned) => !*abandoned && tx.send(saddr).is_ok(),
+                    Err(_) => false,
+                };
+
+                if !started {
+                    error!("Can not start Arbiter, remote side is dead");
+                } else {
+                    // run loop
+                    let _ = match core.run(stop_rx) {
+                        Ok(code) => code,
+                        Err(_) => 1,
+                    };
+                }
+            }));
+
+            // thread dies if there is no panic handler
+            let died = res.is_err() && panic_handler.is_none();
+
+            // unregister arbiter, system could be stopped already
+            if !register {
+                if died {
+                    error!("Arbiter {} thread panicked", id.simple());
+                }
+            } else if died {
+                unregister.send_or_log(ArbiterDied(id.simple().to_string()));
+            } else {
+                unregister.send(UnregisterArbiter(id.simple().to_string()));
+            }
+
+            if let Err(err) = res {
+                match panic_handler {
+                    Some(handler) => handler(err),
+                    None => panic::resume_unwind(err),
+                }
+            }
+        }).map_err(ArbiterStartError::Io)?;
+
+        let timeout = match start_timeout {
+            Some(timeout) => timeout,
+            None => return rx.recv().map_err(|_| ArbiterStartError::Terminated),
+        };
+        match rx.recv_timeout(timeout) {
+            Ok(addr) => Ok(addr),
+            Err(RecvTimeoutError::Disconnected) => Err(ArbiterStartError::Terminated),
+            Err(RecvTimeoutError::Timeout) => {
+                // arbiter could start while lock is acquired
+                let mut abandoned = match abandoned.lock() {
+                    Ok(abandoned) => abandoned,
+                    Err(_) => return Err(ArbiterStartError::Terminated),
+                };
+                match rx.try_recv() {
+                    Ok(addr) => Ok(addr),
+                    Err(_) => {
+                        *abandoned = true;
+                        Err(ArbiterStartError::Timeout)
+                    }
+                }
+            }
+        }
+    }
+
+    /// Start arbiter on top of existing event loop in current thread.
+    ///
+    /// Arbiter joins `system`, see `Arbiter::new_with_handle()`. Thread
+    /// settings, panic handler and start timeout are not used, arbiter
+    /// does not own current thread.
+    ///
+    /// # Panics
+    ///
+    /// Panics if current thread runs arbiter already.
+    pub fn start_with_handle(self, handle: Handle, system: SystemInfo) -> SyncAddress<Arbiter> {
+        if ADDR.with(|cell| cell.borrow().is_some()) {
+            panic!("Arbiter is running in current thread already")
+        }
+        let ArbiterBuilder { name, throughput, mailbox_capacity, jobs_timeout,
+                             init, register, .. } = self;
+
+        let id = Uuid::new_v4();
+        let arb_name = name.clone();
+        let name = if let Some(n) = name {
+            format!("arbiter:{:?}:{:?}", id.hyphenated().to_string(), n)
+        } else {
+            format!("arbiter:{:?}", id.hyphenated().to_string())
+        };
+
+        DOWN.with(|cell| cell.set(false));
+        COUNTERS.with(|counters| counters.reset());
+        HND.with(|cell| *cell.borrow_mut() = Some(handle));
+        REG.with(|cell| *cell.borrow_mut() = Some(Registry::new()));
+        NAME.with(|cell| *cell.borrow_mut() = Some(name));
+        THROUGHPUT.with(|cell| cell.set(throughput));
+        MAILBOX.with(|cell| cell.set(mailbox_capacity));
+        system.install();
+
+        // start arbiter, it registers itself within system
+        let (addr, saddr) = Actor::start(
+            Arbiter {sys: false, id: id, name: arb_name, register: register,
+                     stopping: false, jobs: 0, jobs_done: None,
+                     jobs_timeout: jobs_timeout});
+        ADDR.with(|cell| *cell.borrow_mut() = Some(addr));
+
+        if let Some(init) = init {
+            init.call_box();
+        }
+        saddr
+    }
+}
+
+/// System information that is required to join arbiter to the system
+/// from other thread, see `Arbiter::system_info()`.
+#[derive(Clone)]
+pub struct SystemInfo {
+    sys: SyncAddress<System>,
+    name: String,
+    arbiter: SyncAddress<Arbiter>,
+    registry: SystemRegistry,
+}
+
+impl SystemInfo {
+    /// Name of the system
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn install(self) {
+        let SystemInfo { sys, name, arbiter, registry } = self;
+        SYS.with(|cell| *cell.borrow_mut() = Some(sys));
+        SYSARB.with(|cell| *cell.borrow_mut() = Some(arbiter));
+        SYSNAME.with(|cell| *cell.borrow_mut() = Some(name));
+        SYSREG.with(|cell| *cell.borrow_mut() = Some(registry));
+    }
+}
+
+trait FnInit: Send + 'static {
+    fn call_box(self: Box<Self>);
+}
+
+impl<F: FnOnce() + Send + 'static> FnInit for F {
+    #[cfg_attr(feature="cargo-clippy", allow(boxed_local))]
+    fn call_box(self: Box<Self>) {
+        (*self)()
+    }
+}
+
+/// Errors that can occur while starting arbiter with `ArbiterBuilder::spawn()`
+#[derive(Debug)]
+pub enum ArbiterStartError {
+    /// Arbiter's thread can not be spawned
+    Io(io::Error),
+    /// Arbiter's thread terminated before arbiter started,
+    /// i.e. initialization function panicked
+    Terminated,
+    /// Arbiter did not start within `ArbiterBuilder::start_timeout()`
+    Timeout,
+}
+
+impl error::Error for ArbiterStartError {
+    fn description(&self) -> &str {
+        match *self {
+            ArbiterStartError::Io(_) => "can not spawn arbiter's thread",
+            ArbiterStartError::Terminated => "arbiter's thread terminated",
+            ArbiterStartError::Timeout => "arbiter start timed out",
+        }
+    }
+
+    fn source(&self) -> Option<&(error::Error + 'static)> {
+        match *self {
+            ArbiterStartError::Io(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ArbiterStartError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ArbiterStartError::Io(ref err) => write!(f, "can not spawn arbiter's thread: {}", err),
+            ArbiterStartError::Terminated => write!(f, "arbiter's thread terminated"),
+            ArbiterStartError::Timeout => write!(f, "arbiter start timed out"),
+        }
+    }
 }
 
 #[doc(hidden)]
@@ -195,22 +1046,119 @@ impl ResponseType<StopArbiter> for Arbiter {
 
 impl Handler<StopArbiter> for Arbiter {
 
-    fn handle(&mut self, msg: StopArbiter, _: &mut Context<Self>) -> Response<Self, StopArbiter>
+    fn handle(&mut self, msg: StopArbiter, ctx: &mut Context<Self>) -> Response<Self, StopArbiter>
     {
         if self.sys {
             warn!("System arbiter received `StopArbiter` message.
                   To shutdown system `SystemExit` message should be send to `Address<System>`");
+            return Self::empty()
+        }
+
+        // reject new jobs
+        self.stopping = true;
+
+        if let Some(timeout) = DRAIN.with(|cell| cell.get()) {
+            // give actor's futures chance to complete
+            let code = msg.0;
+            Arbiter::handle().spawn(
+                Drain.select2(Delay::new(timeout))
+                    .then(move |_| {
+                        stop_arbiter(code);
+                        Ok(())
+                    }));
+        } else if self.jobs > 0 {
+            // wait for in-flight jobs, stuck job must not keep arbiter running forever
+            let code = msg.0;
+            JobsDone.then(move |_, _: &mut Arbiter, _: &mut Context<Arbiter>| {
+                stop_arbiter(code);
+                fut::ok(())
+            }).spawn(ctx);
+            Arbiter::handle().spawn(
+                Delay::new(self.jobs_timeout)
+                    .then(move |_| {
+                        stop_arbiter(code);
+                        Ok(())
+                    }));
         } else {
-            STOP.with(|cell| {
-                if let Some(stop) = cell.borrow_mut().take() {
-                    let _ = stop.send(msg.0);
-                }
-            });
+            stop_arbiter(msg.0);
         }
         Self::empty()
     }
 }
 
+fn stop_arbiter(code: i32) {
+    STOP.with(|cell| {
+        if let Some(stop) = cell.borrow_mut().take() {
+            let _ = stop.send(code);
+        }
+    });
+}
+
+/// Future resolves when all actor's futures in current thread complete
+struct Drain;
+
+impl Future for Drain {
+    type Item = ();
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<(), ()> {
+        if Arbiter::drained() {
+            Ok(Async::Ready(()))
+        } else {
+            DRAINED.with(|cell| *cell.borrow_mut() = Some(task::current()));
+            Ok(Async::NotReady)
+        }
+    }
+}
+
+/// Future resolves when all in-flight `ExecuteAsync` jobs complete
+struct JobsDone;
+
+impl ActorFuture for JobsDone {
+    type Item = ();
+    type Error = ();
+    type Actor = Arbiter;
+
+    fn poll(&mut self, act: &mut Arbiter, _: &mut Context<Arbiter>) -> Poll<(), ()> {
+        if act.jobs == 0 {
+            Ok(Async::Ready(()))
+        } else {
+            act.jobs_done = Some(task::current());
+            Ok(Async::NotReady)
+        }
+    }
+}
+
+#[doc(hidden)]
+impl ResponseType<SystemArbiterUpdated> for Arbiter {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<SystemArbiterUpdated> for Arbiter {
+
+    fn handle(&mut self, msg: SystemArbiterUpdated, _: &mut Context<Self>)
+              -> Response<Self, SystemArbiterUpdated>
+    {
+        SYSARB.with(|cell| *cell.borrow_mut() = Some(msg.0));
+        Self::empty()
+    }
+}
+
+#[doc(hidden)]
+impl ResponseType<GetRemote> for Arbiter {
+    type Item = Remote;
+    type Error = ();
+}
+
+impl Handler<GetRemote> for Arbiter {
+
+    fn handle(&mut self, _: GetRemote, _: &mut Context<Self>) -> Response<Self, GetRemote>
+    {
+        Self::reply(Arbiter::remote())
+    }
+}
+
 impl<A> ResponseType<StartActor<A>> for Arbiter where A: Actor<Context=Context<A>> {
     type Item = SyncAddress<A>;
     type Error = ();
@@ -220,7 +1168,11 @@ impl<A> Handler<StartActor<A>> for Arbiter where A: Actor<Context=Context<A>> {
 
     fn handle(&mut self, msg: StartActor<A>, _: &mut Context<Self>) -> Response<Self, StartActor<A>>
     {
-        Self::reply(msg.call())
+        if self.stopping || Arbiter::system_registry().actors.exhausted() {
+            Self::reply_error(())
+        } else {
+            Self::reply(msg.call())
+        }
     }
 }
 
@@ -236,9 +1188,117 @@ impl<I: Send, E: Send> Handler<Execute<I, E>> for Arbiter {
     fn handle(&mut self, msg: Execute<I, E>, _: &mut Context<Self>)
               -> Response<Self, Execute<I, E>>
     {
-        match msg.exec() {
-            Ok(i) => Self::reply(i),
-            Err(e) => Self::reply_error(e),
+        if self.stopping {
+            return Response::canceled()
+        }
+
+        // reply immediately if result is ready
+        let mut fut = msg.into_future();
+        match fut.poll() {
+            Ok(Async::Ready(item)) => Self::reply(item),
+            Ok(Async::NotReady) => self.job(fut),
+            Err(err) => Self::reply_error(err),
+        }
+    }
+}
+
+/// ExecuteAsync message response
+impl<I: Send, E: Send> ResponseType<ExecuteAsync<I, E>> for Arbiter {
+    type Item = I;
+    type Error = E;
+}
+
+/// Execute future in arbiter's thread
+impl<I: Send, E: Send> Handler<ExecuteAsync<I, E>> for Arbiter {
+
+    fn handle(&mut self, msg: ExecuteAsync<I, E>, _: &mut Context<Self>)
+              -> Response<Self, ExecuteAsync<I, E>>
+    {
+        if self.stopping {
+            return Response::canceled()
         }
+        self.job(msg.into_future())
+    }
+}
+
+impl<A, M> ResponseType<ForwardTo<A, M>> for Arbiter
+    where A: Actor + Handler<M>, A::Context: AsyncContext<A>, M: 'static
+{
+    type Item = ();
+    type Error = ();
+}
+
+/// Forward message to the target actor
+impl<A, M> Handler<ForwardTo<A, M>> for Arbiter
+    where A: Actor + Handler<M>, A::Context: AsyncContext<A>, M: 'static
+{
+    fn handle(&mut self, msg: ForwardTo<A, M>, _: &mut Context<Self>)
+              -> Response<Self, ForwardTo<A, M>>
+    {
+        msg.addr.send(msg.msg);
+        Self::empty()
+    }
+}
+
+/// Get runtime counters of the arbiter
+pub(crate) struct GetArbiterMetrics;
+
+#[doc(hidden)]
+impl ResponseType<GetArbiterMetrics> for Arbiter {
+    type Item = ArbiterMetrics;
+    type Error = ();
+}
+
+#[doc(hidden)]
+impl Handler<GetArbiterMetrics> for Arbiter {
+
+    fn handle(&mut self, _: GetArbiterMetrics, _: &mut Context<Self>)
+              -> Response<Self, GetArbiterMetrics>
+    {
+        Self::reply(Arbiter::metrics())
+    }
+}
+
+/// Stop draining actor mailboxes in arbiter's thread
+pub(crate) struct FreezeArbiter;
+
+#[doc(hidden)]
+impl ResponseType<FreezeArbiter> for Arbiter {
+    type Item = ();
+    type Error = ();
+}
+
+#[doc(hidden)]
+impl Handler<FreezeArbiter> for Arbiter {
+
+    fn handle(&mut self, _: FreezeArbiter, _: &mut Context<Self>) -> Response<Self, FreezeArbiter>
+    {
+        FROZEN.with(|cell| if cell.borrow().is_none() {
+            *cell.borrow_mut() = Some(Vec::new())
+        });
+        Self::empty()
+    }
+}
+
+/// Resume actor mailboxes processing in arbiter's thread
+pub(crate) struct ThawArbiter;
+
+#[doc(hidden)]
+impl ResponseType<ThawArbiter> for Arbiter {
+    type Item = ();
+    type Error = ();
+}
+
+#[doc(hidden)]
+impl Handler<ThawArbiter> for Arbiter {
+
+    fn handle(&mut self, _: ThawArbiter, _: &mut Context<Self>) -> Response<Self, ThawArbiter>
+    {
+        if let Some(tasks) = FROZEN.with(|cell| cell.borrow_mut().take()) {
+            for task in tasks {
+                task.notify();
+            }
+        }
+        Self::empty()
     }
 }
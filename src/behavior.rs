@@ -0,0 +1,179 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use actor::{Actor, Handler};
+use context::Context;
+use message::Response;
+
+/// Actor behavior
+///
+/// Behavior handles messages on behalf of the actor, current behavior
+/// is set with `Context::become_()`. Messages that behavior handles are
+/// registered with `Handlers::add()`, each of them requires
+/// `BehaviorHandler` implementation. Actor forwards message to current
+/// behavior with `Context::dispatch()` call from its own `Handler`.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate actix;
+/// extern crate futures;
+///
+/// use futures::Future;
+/// use actix::prelude::*;
+///
+/// struct Toggle;
+///
+/// impl ResponseType<Toggle> for Switch {
+///     type Item = bool;
+///     type Error = ();
+/// }
+///
+/// struct Switch;
+///
+/// impl Actor for Switch {
+///     type Context = Context<Self>;
+///
+///     fn started(&mut self, ctx: &mut Context<Self>) {
+///         ctx.become_(Off);
+///     }
+/// }
+///
+/// impl Handler<Toggle> for Switch {
+///     fn handle(&mut self, msg: Toggle, ctx: &mut Context<Self>) -> Response<Self, Toggle> {
+///         match ctx.dispatch(self, msg) {
+///             Ok(res) => res,
+///             Err(_) => Self::reply_error(()),
+///         }
+///     }
+/// }
+///
+/// struct Off;
+///
+/// impl Behavior<Switch> for Off {
+///     fn handlers(handlers: &mut Handlers<Switch, Self>) {
+///         handlers.add::<Toggle>();
+///     }
+/// }
+///
+/// impl BehaviorHandler<Switch, Toggle> for Off {
+///     fn handle(&mut self, _: &mut Switch, _: Toggle, ctx: &mut Context<Switch>)
+///               -> Response<Switch, Toggle>
+///     {
+///         ctx.become_(On);
+///         Switch::reply(true)
+///     }
+/// }
+///
+/// struct On;
+///
+/// impl Behavior<Switch> for On {
+///     fn handlers(handlers: &mut Handlers<Switch, Self>) {
+///         handlers.add::<Toggle>();
+///     }
+/// }
+///
+/// impl BehaviorHandler<Switch, Toggle> for On {
+///     fn handle(&mut self, _: &mut Switch, _: Toggle, ctx: &mut Context<Switch>)
+///               -> Response<Switch, Toggle>
+///     {
+///         ctx.become_(Off);
+///         Switch::reply(false)
+///     }
+/// }
+///
+/// fn main() {
+///     let sys = System::new("test");
+///
+///     let addr: Address<_> = Switch.start();
+///     Arbiter::handle().spawn(
+///         addr.call_fut(Toggle)
+///             .and_then(move |on| {
+///                 assert_eq!(on, Ok(true));
+///                 addr.call_fut(Toggle)
+///             })
+///             .then(|on| {
+///                 assert_eq!(on, Ok(Ok(false)));
+///                 Arbiter::system().send(msgs::SystemExit(0));
+///                 Ok(())
+///             }));
+///
+///     sys.run();
+/// }
+/// ```
+pub trait Behavior<A>: Sized + 'static where A: Actor<Context=Context<A>> {
+
+    /// Register messages handled by this behavior
+    fn handlers(handlers: &mut Handlers<A, Self>);
+}
+
+/// Message handler of the behavior
+pub trait BehaviorHandler<A, M>: Behavior<A>
+    where A: Actor<Context=Context<A>> + Handler<M>,
+{
+    /// Method is called for every message `M` while behavior is current
+    fn handle(&mut self, act: &mut A, msg: M, ctx: &mut Context<A>) -> Response<A, M>;
+}
+
+type DispatchFn<A, M> = fn(&mut Any, &mut A, M, &mut Context<A>) -> Response<A, M>;
+
+/// Dispatch table of the behavior `B`
+pub struct Handlers<A, B> {
+    table: HashMap<TypeId, Box<Any>>,
+    act: PhantomData<A>,
+    behavior: PhantomData<B>,
+}
+
+impl<A, B> Handlers<A, B> where A: Actor<Context=Context<A>>, B: Behavior<A> {
+
+    /// Handle messages `M` with behavior's `BehaviorHandler<A, M>` implementation
+    pub fn add<M: 'static>(&mut self) -> &mut Self
+        where A: Handler<M>, B: BehaviorHandler<A, M>
+    {
+        let f: DispatchFn<A, M> = dispatch::<A, B, M>;
+        self.table.insert(TypeId::of::<M>(), Box::new(f));
+        self
+    }
+}
+
+fn dispatch<A, B, M>(b: &mut Any, act: &mut A, msg: M, ctx: &mut Context<A>) -> Response<A, M>
+    where A: Actor<Context=Context<A>> + Handler<M>, B: BehaviorHandler<A, M>
+{
+    b.downcast_mut::<B>().unwrap().handle(act, msg, ctx)
+}
+
+/// Current behavior of the actor together with its dispatch table
+pub(crate) struct CurrentBehavior<A> {
+    behavior: Box<Any>,
+    table: HashMap<TypeId, Box<Any>>,
+    act: PhantomData<A>,
+}
+
+impl<A> CurrentBehavior<A> where A: Actor<Context=Context<A>> {
+
+    pub fn new<B: Behavior<A>>(behavior: B) -> CurrentBehavior<A> {
+        let mut handlers = Handlers{
+            table: HashMap::new(), act: PhantomData, behavior: PhantomData};
+        B::handlers(&mut handlers);
+        CurrentBehavior {
+            behavior: Box::new(behavior),
+            table: handlers.table,
+            act: PhantomData,
+        }
+    }
+
+    /// Handle message with behavior, message is returned back
+    /// if behavior does not handle messages of type `M`
+    pub fn handle<M: 'static>(&mut self, act: &mut A, msg: M, ctx: &mut Context<A>)
+                              -> Result<Response<A, M>, M>
+        where A: Handler<M>
+    {
+        match self.table.get(&TypeId::of::<M>())
+            .and_then(|f| f.downcast_ref::<DispatchFn<A, M>>())
+        {
+            Some(f) => Ok(f(&mut *self.behavior, act, msg, ctx)),
+            None => Err(msg),
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use futures::{Async, AsyncSink, Poll, Sink, StartSend, Stream};
+
+use queue::sync;
+
+/// Create bounded channel for sending messages from one actor to another.
+///
+/// Receiving end is a `Stream` and could be registered in actor's context
+/// with `ctx.add_stream()`. Sending end is a `Sink`, it stops accepting
+/// messages when channel is full, so producer gets backpressure. Channel
+/// capacity is `capacity` plus one message for each sender.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate actix;
+/// extern crate futures;
+///
+/// use futures::{stream, Future, Sink};
+/// use actix::prelude::*;
+///
+/// struct Consumer;
+///
+/// impl Actor for Consumer {
+///     type Context = Context<Self>;
+/// }
+///
+/// impl ResponseType<u32> for Consumer {
+///     type Item = ();
+///     type Error = ();
+/// }
+///
+/// impl Handler<u32> for Consumer {
+///     fn handle(&mut self, msg: u32, _: &mut Context<Self>) -> Response<Self, u32> {
+///         println!("received: {}", msg);
+///         Self::empty()
+///     }
+/// }
+///
+/// impl StreamHandler<u32> for Consumer {
+///     fn finished(&mut self, _: &mut Context<Self>) {
+///         Arbiter::system().send(msgs::SystemExit(0));
+///     }
+/// }
+///
+/// fn main() {
+///     let sys = System::new("test");
+///
+///     let (tx, rx) = actor_channel(2);
+///     let _: () = Consumer::create(|ctx| {
+///         ctx.add_stream(rx);
+///         Consumer
+///     });
+///
+///     Arbiter::handle().spawn(
+///         tx.send_all(stream::iter_ok(0..10)).map(|_| ()));
+///
+///     sys.run();
+/// }
+/// ```
+pub fn actor_channel<M>(capacity: usize) -> (ChannelSender<M>, ChannelReceiver<M>) {
+    let (tx, rx) = sync::channel(capacity);
+    (ChannelSender(tx), ChannelReceiver(rx))
+}
+
+/// Sending end of the actor channel
+pub struct ChannelSender<M>(sync::Sender<M>);
+
+impl<M> ChannelSender<M> {
+    /// Try to send message without waiting for channel capacity.
+    ///
+    /// Message is returned back if channel is full or receiver is dropped.
+    pub fn try_send(&mut self, msg: M) -> Result<(), M> {
+        self.0.try_send(msg).map_err(|err| err.into_inner())
+    }
+}
+
+impl<M> Clone for ChannelSender<M> {
+    fn clone(&self) -> Self {
+        ChannelSender(self.0.clone())
+    }
+}
+
+impl<M> Sink for ChannelSender<M> {
+    type SinkItem = M;
+    type SinkError = ();
+
+    fn start_send(&mut self, msg: M) -> StartSend<M, ()> {
+        match self.0.start_send(msg) {
+            Ok(AsyncSink::Ready) => Ok(AsyncSink::Ready),
+            Ok(AsyncSink::NotReady(msg)) => Ok(AsyncSink::NotReady(msg)),
+            Err(_) => Err(()),
+        }
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), ()> {
+        Ok(Async::Ready(()))
+    }
+
+    fn close(&mut self) -> Poll<(), ()> {
+        Ok(Async::Ready(()))
+    }
+}
+
+/// Receiving end of the actor channel
+///
+/// Stream finishes when all senders are dropped.
+pub struct ChannelReceiver<M>(sync::Receiver<M>);
+
+impl<M> ChannelReceiver<M> {
+    /// Close channel, senders can not send new messages.
+    /// Messages that are already in the channel are still delivered.
+    pub fn close(&mut self) {
+        self.0.close()
+    }
+}
+
+impl<M> Stream for ChannelReceiver<M> {
+    type Item = M;
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<Option<M>, ()> {
+        self.0.poll()
+    }
+}
@@ -0,0 +1,243 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use futures::{Async, Future, Poll, Stream};
+use futures::task::{self, Task};
+use tokio_core::reactor::Timeout;
+
+use arbiter::Arbiter;
+
+/// Manually driven clock for testing time based actors
+///
+/// Clock installed with `Arbiter::set_clock()` replaces time source of the
+/// current arbiter: `Arbiter::now()`, `AsyncContext::run_later()`,
+/// `Actor::reply_after()` and `Arbiter::spawn_interval()` use clock's time.
+/// Time does not pass by itself, it moves only with `TestClock::advance()` call.
+/// Called outside of running event loop, `advance()` fires due timers in
+/// order of their deadlines before it returns. Called from actor or future,
+/// it only wakes up due timers and they fire on next iteration of event loop.
+///
+/// Clock affects only timers created after it is installed.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate actix;
+///
+/// use std::time::Duration;
+/// use actix::prelude::*;
+///
+/// struct Timer(TestClock);
+///
+/// impl Actor for Timer {
+///    type Context = Context<Self>;
+///
+///    fn started(&mut self, ctx: &mut Context<Self>) {
+///        ctx.run_later(Duration::from_secs(3600), |_, _| {
+///            Arbiter::system().send(msgs::SystemExit(0));
+///        });
+///
+///        // one hour passes immediately
+///        self.0.advance(Duration::from_secs(3600));
+///    }
+/// }
+///
+/// fn main() {
+///    let sys = System::new("test");
+///
+///    let clock = TestClock::new();
+///    Arbiter::set_clock(clock.clone());
+///
+///    let _: () = Timer(clock).start();
+///
+///    sys.run();
+/// }
+/// ```
+#[derive(Clone)]
+pub struct TestClock {
+    inner: Rc<RefCell<ClockInner>>,
+}
+
+struct ClockInner {
+    now: Instant,
+    next_id: usize,
+    /// pending timers, one slot per timer
+    timers: HashMap<usize, (Instant, Task)>,
+}
+
+impl Default for TestClock {
+    fn default() -> Self {
+        TestClock::new()
+    }
+}
+
+impl TestClock {
+
+    /// Create clock, clock starts at current time
+    pub fn new() -> TestClock {
+        TestClock {
+            inner: Rc::new(RefCell::new(
+                ClockInner{now: Instant::now(), next_id: 0, timers: HashMap::new()}))}
+    }
+
+    /// Current time of the clock
+    pub fn now(&self) -> Instant {
+        self.inner.borrow().now
+    }
+
+    /// Move clock forward by `dur` and fire timers that become due.
+    ///
+    /// If system's event loop is not running, for example `advance()` is
+    /// called before `SystemRunner::run()`, clock steps through deadlines
+    /// of due timers in order and runs event loop at each step, so timers
+    /// and messages they send are handled before this call returns.
+    ///
+    /// Event loop can not be run from inside itself, so if `advance()` is
+    /// called from actor or future, due timers are only woken up and
+    /// fire on next iteration of arbiter's event loop.
+    pub fn advance(&self, dur: Duration) {
+        let target = self.now() + dur;
+
+        // let actors start and register their timers
+        if Arbiter::turn() {
+            while let Some(deadline) = self.next_deadline(target) {
+                self.set_now(deadline);
+                Arbiter::turn();
+            }
+        }
+        self.set_now(target);
+    }
+
+    /// Earliest deadline of pending timers that is not later than `target`
+    fn next_deadline(&self, target: Instant) -> Option<Instant> {
+        self.inner.borrow().timers.values()
+            .map(|timer| timer.0).filter(|deadline| *deadline <= target).min()
+    }
+
+    /// Move clock to `now` and wake up timers that become due
+    fn set_now(&self, now: Instant) {
+        let mut due = {
+            let mut inner = self.inner.borrow_mut();
+            if now > inner.now {
+                inner.now = now;
+            }
+            let now = inner.now;
+            let ids: Vec<_> = inner.timers.iter()
+                .filter(|&(_, timer)| timer.0 <= now).map(|(id, _)| *id).collect();
+            ids.into_iter()
+                .filter_map(|id| inner.timers.remove(&id).map(|(deadline, task)| (deadline, id, task)))
+                .collect::<Vec<_>>()
+        };
+        // timers with same deadline fire in order of creation
+        due.sort_by_key(|timer| (timer.0, timer.1));
+        for (_, _, task) in due {
+            task.notify();
+        }
+    }
+
+    /// Allocate timer slot
+    fn timer(&self) -> usize {
+        let mut inner = self.inner.borrow_mut();
+        inner.next_id += 1;
+        inner.next_id
+    }
+
+    /// Check if `deadline` of timer `id` is reached, otherwise current task
+    /// get notified when it is reached.
+    fn poll_deadline(&self, id: usize, deadline: Instant) -> bool {
+        let mut inner = self.inner.borrow_mut();
+        if inner.now >= deadline {
+            inner.timers.remove(&id);
+            true
+        } else {
+            inner.timers.insert(id, (deadline, task::current()));
+            false
+        }
+    }
+
+    fn cancel(&self, id: usize) {
+        self.inner.borrow_mut().timers.remove(&id);
+    }
+}
+
+enum DelayKind {
+    Timer(Timeout),
+    Clock(TestClock, usize),
+}
+
+/// Future that resolves at deadline, uses arbiter's test clock if it is installed
+pub(crate) struct Delay {
+    deadline: Instant,
+    kind: DelayKind,
+}
+
+impl Delay {
+    pub fn new(dur: Duration) -> Delay {
+        match Arbiter::clock() {
+            Some(clock) => Delay {
+                deadline: clock.now() + dur,
+                kind: DelayKind::Clock(clock.clone(), clock.timer()),
+            },
+            None => Delay {
+                deadline: Instant::now() + dur,
+                kind: DelayKind::Timer(Timeout::new(dur, Arbiter::handle()).unwrap()),
+            }
+        }
+    }
+
+    /// Move deadline forward by `dur`
+    fn extend(&mut self, dur: Duration) {
+        self.deadline += dur;
+        if let DelayKind::Timer(ref mut timeout) = self.kind {
+            timeout.reset(self.deadline);
+        }
+    }
+}
+
+impl Future for Delay {
+    type Item = ();
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<(), ()> {
+        match self.kind {
+            DelayKind::Timer(ref mut timeout) => timeout.poll().map_err(|_| ()),
+            DelayKind::Clock(ref clock, id) => if clock.poll_deadline(id, self.deadline) {
+                Ok(Async::Ready(()))
+            } else {
+                Ok(Async::NotReady)
+            },
+        }
+    }
+}
+
+impl Drop for Delay {
+    fn drop(&mut self) {
+        if let DelayKind::Clock(ref clock, id) = self.kind {
+            clock.cancel(id);
+        }
+    }
+}
+
+/// Stream that yields every `dur`, uses arbiter's test clock if it is installed
+pub(crate) struct Ticker {
+    delay: Delay,
+    dur: Duration,
+}
+
+impl Ticker {
+    pub fn new(dur: Duration) -> Ticker {
+        Ticker{delay: Delay::new(dur), dur: dur}
+    }
+}
+
+impl Stream for Ticker {
+    type Item = ();
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<Option<()>, ()> {
+        try_ready!(self.delay.poll());
+        self.delay.extend(self.dur);
+        Ok(Async::Ready(Some(())))
+    }
+}
@@ -1,18 +1,31 @@
 use std;
-use std::collections::VecDeque;
+use std::any::type_name;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
 
-use futures::{Async, Future, Poll, Stream};
+use futures::{executor, task, Async, Future, Poll, Stream};
 use futures::unsync::oneshot::Sender;
+use futures::sync::oneshot::Sender as SyncSender;
 use tokio_core::reactor::Handle;
+use uuid::Uuid;
 
-use fut::ActorFuture;
+use fut::{self, ActorFuture, WrapFuture};
 use queue::{sync, unsync};
 
-use actor::{Actor, Supervised, Handler, StreamHandler,
-            ActorState, ActorContext, AsyncContext, SpawnHandle};
-use address::{Address, SyncAddress, Subscriber};
-use envelope::Envelope;
+use actors::metrics::MetricsActor;
+use actor::{actor_name, Actor, Supervised, Handler, StreamHandler, Snapshotable,
+            ActorPriority, ActorState, ActorContext, AsyncContext, SpawnHandle, WatchHandle};
+use address::{Address, CallError, SyncAddress, Subscriber, Watchable};
+use arbiter::Arbiter;
+use behavior::{Behavior, CurrentBehavior};
+use dynamic::{DynamicHandlers, DynamicMessage};
+use envelope::{self, Envelope};
 use message::Response;
+use msgs::{Execute, Terminated};
+use registry::ActorCountGuard;
+use sync::BlockingError;
 
 pub trait AsyncContextApi<A> where A: Actor, A::Context: AsyncContext<A> {
     fn address_cell(&mut self) -> &mut ActorAddressCell<A>;
@@ -24,16 +37,30 @@ pub enum ContextProtocol<A: Actor> {
     Envelope(Envelope<A>),
     /// Request sync address
     Upgrade(Sender<SyncAddress<A>>),
+    /// Watch actor's lifecycle, sender is dropped when context stops
+    Watch(Sender<()>),
+    /// Stop actor
+    Stop,
+    /// Replace actor, queued messages are handled by new actor
+    TakeOver(A),
 }
 
 /// Actor execution context
 pub struct Context<A> where A: Actor<Context=Context<A>>,
 {
-    act: A,
+    act: ActorSlot<A>,
     state: ActorState,
     wait: ActorWaitCell<A>,
     items: ActorItemsCell<A>,
     address: ActorAddressCell<A>,
+    handle: Handle,
+    migratable: bool,
+    migration: Option<Box<Migration<A>>>,
+    behavior: Option<CurrentBehavior<A>>,
+    pub(crate) dynamic: DynamicHandlers<A>,
+    metrics: Option<SyncAddress<MetricsActor>>,
+    limited: HashMap<&'static str, Limiter<A>>,
+    _count: ActorCountGuard,
 }
 
 impl<A> ActorContext<A> for Context<A> where A: Actor<Context=Self>
@@ -50,6 +77,8 @@ impl<A> ActorContext<A> for Context<A> where A: Actor<Context=Self>
     fn terminate(&mut self) {
         self.address.close();
         self.items.close();
+        self.address.exclusive = 0;
+        self.limited.clear();
         self.state = ActorState::Stopped;
     }
 
@@ -76,6 +105,12 @@ impl<A> AsyncContext<A> for Context<A> where A: Actor<Context=Self>
     fn cancel_future(&mut self, handle: SpawnHandle) -> bool {
         self.items.cancel_future(handle)
     }
+
+    fn spawn_background<F>(&mut self, fut: F) -> SpawnHandle
+        where F: ActorFuture<Item=(), Error=(), Actor=A> + 'static
+    {
+        self.items.spawn_background(fut)
+    }
 }
 
 impl<A> AsyncContextApi<A> for Context<A> where A: Actor<Context=Self> {
@@ -86,6 +121,384 @@ impl<A> AsyncContextApi<A> for Context<A> where A: Actor<Context=Self> {
 
 impl<A> Context<A> where A: Actor<Context=Self>
 {
+    /// Start new actor in the same arbiter as current actor.
+    /// Returns local address of the started actor.
+    pub fn start_actor<C>(&mut self, actor: C) -> Address<C>
+        where C: Actor<Context=Context<C>>
+    {
+        actor.start()
+    }
+
+    /// Correlation id of the message that is being handled.
+    ///
+    /// Every `call` starts new correlation id, unless it is made while handling
+    /// message with correlation id, in that case id is inherited. Id is available
+    /// only during `Handler::handle()` call.
+    pub fn correlation_id(&self) -> Option<Uuid> {
+        envelope::correlation_id()
+    }
+
+    /// Time left until deadline of the message that is being handled.
+    ///
+    /// Deadline is set by `SyncAddress::call_timeout()` and
+    /// `SyncAddress::send_deadline()`, requests made while handling message
+    /// with deadline inherit it, so whole call chain respects original
+    /// timeout. Returns `None` if message has no deadline, budget is
+    /// available only during `Handler::handle()` call.
+    pub fn remaining_budget(&self) -> Option<Duration> {
+        envelope::deadline().map(|deadline| {
+            let now = Arbiter::now();
+            if deadline > now { deadline - now } else { Duration::from_secs(0) }
+        })
+    }
+
+    /// Spawn plain future into arbiter's event loop.
+    ///
+    /// Future is not bound to the actor, it keeps running after actor stops.
+    /// Use `AsyncContext::spawn` for `ActorFuture` that needs access to the actor.
+    pub fn spawn_future<F>(&mut self, fut: F)
+        where F: Future<Item=(), Error=()> + 'static
+    {
+        self.handle.spawn(fut)
+    }
+
+    /// Run blocking function in the system wide thread pool.
+    ///
+    /// Use this method for sync io or cpu bound work, so actor's event loop
+    /// does not get blocked. Returned future resolves to the result of `f`,
+    /// it could be spawned into context or returned with `async_reply`.
+    /// See `Arbiter::spawn_blocking()` for plain future version.
+    pub fn spawn_blocking<F, R>(&mut self, f: F)
+                                -> impl ActorFuture<Item=R, Error=BlockingError, Actor=A>
+        where F: FnOnce() -> R + Send + 'static,
+              R: Send + 'static,
+    {
+        Arbiter::system_registry().blocking_pool()
+            .call_fut(Execute::new(move || -> Result<R, ()> { Ok(f()) }))
+            .then(|res| match res {
+                Ok(Ok(res)) => Ok(res),
+                _ => Err(BlockingError::Canceled),
+            })
+            .actfuture()
+    }
+
+    /// Call `f` with handle of the event loop that runs this actor.
+    ///
+    /// Unlike `Arbiter::handle()` this does not require thread-local lookup.
+    pub fn with_handle<F, R>(&self, f: F) -> R where F: FnOnce(&Handle) -> R
+    {
+        f(&self.handle)
+    }
+
+    /// Run `f` after all currently spawned futures complete.
+    ///
+    /// Actor does not process messages from its mailbox until `f` get executed,
+    /// so no handler or spawned future runs in between futures completion
+    /// and `f`. Futures spawned after this call do not delay `f`, neither do
+    /// long lived items: streams, intervals and watches.
+    pub fn exclusive<F>(&mut self, f: F)
+        where F: FnOnce(&mut A, &mut Context<A>) + 'static
+    {
+        let handles = self.items.finite_handles();
+        self.address.exclusive += 1;
+        self.items.spawn(Exclusive{act: std::marker::PhantomData, handles: handles, f: Some(f)});
+    }
+
+    /// Spawn future with limited concurrency.
+    ///
+    /// At most `max` futures spawned with the same `key` run at the same
+    /// time, excess futures are queued and spawned in order as running
+    /// futures complete. Use it to avoid flooding downstream actors or
+    /// services with requests. `max` of `0` is treated as `1`. Limited
+    /// futures can not be canceled individually, they are dropped when
+    /// actor stops.
+    pub fn spawn_limited<F>(&mut self, fut: F, key: &'static str, max: usize)
+        where F: ActorFuture<Item=(), Error=(), Actor=A> + 'static
+    {
+        let limiter = self.limited.entry(key).or_default();
+        if limiter.running < std::cmp::max(max, 1) {
+            limiter.running += 1;
+            self.items.spawn(Limited{key: key, fut: fut});
+        } else {
+            limiter.queue.push_back(Box::new(fut));
+        }
+    }
+
+    /// Limited future completed, spawn next queued future with the same key
+    fn release_limited(&mut self, key: &'static str) {
+        let mut idle = false;
+        if let Some(limiter) = self.limited.get_mut(key) {
+            match limiter.queue.pop_front() {
+                Some(fut) => { self.items.spawn(Limited{key: key, fut: fut}); }
+                None => {
+                    limiter.running -= 1;
+                    idle = limiter.running == 0;
+                }
+            }
+        }
+        if idle {
+            self.limited.remove(key);
+        }
+    }
+
+    /// Log warning for message handlers that run longer than `threshold`.
+    ///
+    /// Handler time is measured for every message taken from actor's mailbox,
+    /// warning includes actor and message type names. Disabled by default.
+    pub fn set_slow_handler_threshold(&mut self, threshold: Duration) {
+        self.address.slow_handler = Some(threshold);
+    }
+
+    /// Set scheduling priority of the actor.
+    ///
+    /// While any `High` priority actor of the arbiter has messages in its
+    /// mailbox, `Normal` and `Low` priority actors do not handle messages
+    /// and let `High` priority actors run first, so critical actor does not
+    /// wait behind bulk workers that got their messages earlier. Priority
+    /// also defines how many messages actor handles before next actor
+    /// get polled. `Low` priority actor yields after every message,
+    /// `High` priority actor drains its mailbox on every poll.
+    ///
+    /// Priority is not preemptive, slow handler delays all other actors of
+    /// the arbiter regardless of priority. Busy `High` priority actors starve
+    /// other actors, so use it only for actors that handle few messages,
+    /// `Low` priority actor handles at most one message per event loop
+    /// iteration. Arbiter's control messages are not affected by priorities.
+    pub fn set_priority(&mut self, priority: ActorPriority) {
+        self.address.priority_level = priority;
+        self.address.high = match priority {
+            ActorPriority::High => Some(HighPriority::new()),
+            _ => None,
+        };
+        self.address.throughput = match priority {
+            ActorPriority::Low => Some(1),
+            ActorPriority::Normal => Arbiter::throughput(),
+            ActorPriority::High => None,
+        };
+    }
+
+    /// Scheduling priority of the actor
+    pub fn priority(&self) -> ActorPriority {
+        self.address.priority_level
+    }
+
+    /// Number of messages dropped because their deadline has passed
+    /// before actor started handling them.
+    pub fn dropped_messages(&self) -> usize {
+        self.address.dropped
+    }
+
+    /// Send message to actor `B`.
+    ///
+    /// Same as `addr.send(msg)`, but message type is checked against
+    /// `B`'s handlers at call site, so sending message that `B` does not
+    /// handle is a compile error.
+    ///
+    /// ```rust
+    /// # extern crate actix;
+    /// # use actix::prelude::*;
+    /// struct Ping;
+    ///
+    /// struct Pong;
+    ///
+    /// impl Actor for Pong {
+    ///     type Context = Context<Self>;
+    /// }
+    ///
+    /// impl ResponseType<Ping> for Pong {
+    ///     type Item = ();
+    ///     type Error = ();
+    /// }
+    ///
+    /// impl Handler<Ping> for Pong {
+    ///     fn handle(&mut self, _: Ping, _: &mut Context<Self>) -> Response<Self, Ping> {
+    ///         Arbiter::system().send(msgs::SystemExit(0));
+    ///         Self::empty()
+    ///     }
+    /// }
+    ///
+    /// struct Pinger(Address<Pong>);
+    ///
+    /// impl Actor for Pinger {
+    ///     type Context = Context<Self>;
+    ///
+    ///     fn started(&mut self, ctx: &mut Context<Self>) {
+    ///         ctx.send(&self.0, Ping);
+    ///     }
+    /// }
+    ///
+    /// fn main() {
+    ///     let sys = System::new("test");
+    ///     let pong: Address<_> = Pong.start();
+    ///     let _: () = Pinger(pong).start();
+    ///     sys.run();
+    /// }
+    /// ```
+    ///
+    /// Message without handler is rejected by compiler:
+    ///
+    /// ```rust,compile_fail
+    /// # extern crate actix;
+    /// # use actix::prelude::*;
+    /// struct Ping;
+    ///
+    /// struct Pong;
+    ///
+    /// impl Actor for Pong {
+    ///     type Context = Context<Self>;
+    /// }
+    ///
+    /// struct Pinger(Address<Pong>);
+    ///
+    /// impl Actor for Pinger {
+    ///     type Context = Context<Self>;
+    ///
+    ///     fn started(&mut self, ctx: &mut Context<Self>) {
+    ///         ctx.send(&self.0, Ping);
+    ///     }
+    /// }
+    /// # fn main() {}
+    /// ```
+    pub fn send<B, M>(&mut self, addr: &Address<B>, msg: M)
+        where B: Actor + Handler<M>,
+              B::Context: AsyncContext<B>,
+              M: 'static
+    {
+        addr.send(msg)
+    }
+
+    /// Send message to actor behind `addr` and wait for response.
+    ///
+    /// Returned future resolves with handler's result in this actor's
+    /// context, so it could be chained with actor and context access, i.e.
+    /// `ctx.request(&addr, Msg).and_then(|item, act, ctx| ...)`. Handler's
+    /// error is reported as `CallError::Handler`, stopped actor is reported
+    /// as `CallError::MailboxClosed`.
+    pub fn request<B, M>(&mut self, addr: &Address<B>, msg: M)
+                         -> impl ActorFuture<Item=B::Item, Error=CallError<B::Error>, Actor=A>
+        where B: Actor + Handler<M>,
+              B::Context: AsyncContext<B>,
+              M: 'static
+    {
+        addr.call_fut(msg).actfuture()
+            .then(|res, _: &mut A, _: &mut Context<A>| fut::result(match res {
+                Ok(Ok(item)) => Ok(item),
+                Ok(Err(err)) => Err(CallError::Handler(err)),
+                Err(_) => Err(CallError::MailboxClosed),
+            }))
+    }
+
+    /// Send message to self.
+    ///
+    /// Message is appended to the end of the actor's mailbox, so it get processed
+    /// after all messages that are already in the mailbox. Messages sent with
+    /// `Address<A>` are processed in the same order they were sent, messages
+    /// sent with `SyncAddress<A>` use separate queue and could be interleaved.
+    pub fn send_to_self<M: 'static>(&mut self, msg: M) where A: Handler<M>
+    {
+        let _ = self.address.unsync_sender().unbounded_send(
+            ContextProtocol::Envelope(Envelope::local(msg, None)));
+    }
+
+    /// Send message to self ahead of any other message.
+    ///
+    /// Message get processed before any message from the actor's mailbox,
+    /// including messages that are already there. High priority messages
+    /// are processed in the same order they were sent.
+    pub fn send_to_self_high_priority<M: 'static>(&mut self, msg: M) where A: Handler<M>
+    {
+        self.address.priority.push_back(Envelope::local(msg, None));
+    }
+
+    /// Watch lifecycle of the actor behind `addr`.
+    ///
+    /// When watched actor stops, this actor receives `Terminated` message.
+    /// If watched actor is stopped already, `Terminated` is delivered immediately.
+    /// Restart of the supervised actor is reported as termination as well.
+    /// Watch is an active item of the context, so actor does not stop
+    /// while it watches other actor. Use `unwatch` to cancel watch.
+    /// Both `Address<B>` and `SyncAddress<B>` could be watched.
+    pub fn watch<B, T>(&mut self, addr: &T) -> WatchHandle
+        where A: Handler<Terminated>,
+              B: Actor,
+              T: Watchable<B>,
+    {
+        let terminated = Terminated{actor_type: type_name::<B>()};
+        let fut = addr.terminated().then(move |_| Ok::<_, ()>(terminated));
+        WatchHandle::new(self.spawn_background(ActorFutureCell::new(fut)))
+    }
+
+    /// Cancel watch started with `watch`. Returns `false` if watched actor
+    /// is terminated already.
+    pub fn unwatch(&mut self, handle: WatchHandle) -> bool {
+        self.cancel_future(handle.into_inner())
+    }
+
+    /// Switch actor to behavior `behavior`.
+    ///
+    /// Messages passed to `dispatch()` after this call are handled
+    /// by the new behavior, previous behavior is dropped. If behavior
+    /// is switched during `dispatch()` call, current message is still
+    /// handled by the previous behavior.
+    pub fn become_<B>(&mut self, behavior: B) where B: Behavior<A> {
+        self.behavior = Some(CurrentBehavior::new(behavior));
+    }
+
+    /// Register handler for dynamic message `M`.
+    ///
+    /// Handler replaces previously registered handler of `M`. Registration
+    /// calls can be chained, so actor can register set of handlers at once,
+    /// i.e. in `started` or `service_started`. See `DynamicMessage`
+    /// for example.
+    pub fn handle<M, F>(&mut self, f: F) -> &mut Self
+        where M: DynamicMessage,
+              F: FnMut(&mut A, M, &mut Context<A>) -> Result<M::Item, M::Error> + 'static
+    {
+        self.dynamic.insert(f);
+        self
+    }
+
+    /// Remove handler of dynamic message `M`.
+    ///
+    /// Returns `true` if handler was registered. Messages of type `M`
+    /// received after this call are dropped.
+    pub fn unhandle<M: DynamicMessage>(&mut self) -> bool {
+        self.dynamic.remove::<M>()
+    }
+
+    /// Handle message with current behavior of the actor.
+    ///
+    /// Message is returned back if there is no current behavior
+    /// or current behavior does not handle messages of type `M`.
+    pub fn dispatch<M: 'static>(&mut self, act: &mut A, msg: M) -> Result<Response<A, M>, M>
+        where A: Handler<M>
+    {
+        let mut behavior = match self.behavior.take() {
+            Some(behavior) => behavior,
+            None => return Err(msg),
+        };
+        let res = behavior.handle(act, msg, self);
+
+        // keep behavior unless handler switched to new one
+        if self.behavior.is_none() {
+            self.behavior = Some(behavior);
+        }
+        res
+    }
+
+    /// Fields for log enrichment: actor name, arbiter name and type of
+    /// the message that is being handled, under `actor`, `arbiter` and
+    /// `message` keys. `message` is present only during handler call.
+    pub fn log_context(&self) -> HashMap<&'static str, String> {
+        let mut fields = HashMap::new();
+        fields.insert("actor", actor_name::<A>().to_owned());
+        fields.insert("arbiter", Arbiter::name());
+        if let Some(message) = self.address.current {
+            fields.insert("message", message.to_owned());
+        }
+        fields
+    }
+
     #[doc(hidden)]
     pub fn subscriber<M: 'static>(&mut self) -> Box<Subscriber<M>>
         where A: Handler<M>
@@ -106,6 +519,24 @@ impl<A> Context<A> where A: Actor<Context=Self>
 impl<A> Context<A> where A: Actor<Context=Self>
 {
     pub(crate) fn new(act: A) -> Context<A>
+    {
+        Context::with_guard(act, ActorCountGuard::new::<A>())
+    }
+
+    /// Create context without actor, actor has to be set with `set_actor`
+    /// before context starts
+    pub(crate) fn empty() -> Context<A>
+    {
+        Context::with_slot(ActorSlot::default(), ActorCountGuard::new::<A>())
+    }
+
+    /// Create context for actor that is already counted as live
+    pub(crate) fn with_guard(act: A, guard: ActorCountGuard) -> Context<A>
+    {
+        Context::with_slot(ActorSlot(Some(act)), guard)
+    }
+
+    fn with_slot(act: ActorSlot<A>, guard: ActorCountGuard) -> Context<A>
     {
         Context {
             act: act,
@@ -113,11 +544,41 @@ impl<A> Context<A> where A: Actor<Context=Self>
             wait: ActorWaitCell::default(),
             items: ActorItemsCell::default(),
             address: ActorAddressCell::default(),
+            handle: Arbiter::handle().clone(),
+            migratable: false,
+            migration: None,
+            behavior: None,
+            dynamic: DynamicHandlers::default(),
+            metrics: None,
+            limited: HashMap::new(),
+            _count: guard,
         }
     }
 
-    pub(crate) fn run(self, handle: &Handle) {
-        handle.spawn(self.map(|_| ()).map_err(|_| ()));
+    pub(crate) fn run(mut self, handle: &Handle) {
+        self.migratable = true;
+        handle.spawn(ContextRunner(Some(self)));
+    }
+
+    /// Start actor that is moved from other arbiter, `SyncAddress` mailbox
+    /// is moved together with the actor.
+    pub(crate) fn restore(act: A, mailbox: SyncMailbox<A>)
+    {
+        let mut ctx = Context::new(act);
+        ctx.address.set_sync_receiver(mailbox);
+        ctx.run(Arbiter::handle());
+    }
+
+    /// Envelope registers watcher, sender is dropped when context stops
+    pub(crate) fn sync_watch(tx: SyncSender<()>) -> Envelope<A> {
+        Envelope::new(WatchEnvelope{tx: Some(tx), act: std::marker::PhantomData})
+    }
+
+    /// Envelope stops actor and passes its state and sync mailbox to `f`
+    pub(crate) fn migration<F>(f: F) -> Envelope<A>
+        where F: FnOnce(A, SyncMailbox<A>) + 'static
+    {
+        Envelope::new(MigrateEnvelope{f: Some(Box::new(f))})
     }
 
     pub(crate) fn alive(&mut self) -> bool {
@@ -135,12 +596,40 @@ impl<A> Context<A> where A: Actor<Context=Self>
         self.act.restarting(ctx);
     }
 
-    pub(crate) fn replace_actor(&mut self, srv: A) -> A {
-        std::mem::replace(&mut self.act, srv)
+    /// Replace running actor with `act`, old actor is stopped together
+    /// with its futures and streams, mailbox is kept
+    fn take_over(&mut self, act: A) {
+        let ctx: &mut Context<A> = unsafe {
+            std::mem::transmute(self as &mut Context<A>)
+        };
+        let mut old = self.act.replace(act);
+        Actor::stopped(&mut old, ctx);
+
+        self.items.close();
+        self.address.exclusive = 0;
+        self.limited.clear();
+        self.wait = ActorWaitCell::default();
+        self.behavior = None;
+
+        Actor::started(&mut *self.act, ctx);
+        self.state = ActorState::Running;
+    }
+
+    /// Address of system's `MetricsActor`, it is looked up once per actor.
+    /// Returns `None` if actor does not run in a system.
+    pub(crate) fn metrics(&mut self) -> Option<SyncAddress<MetricsActor>> {
+        if self.metrics.is_none() {
+            self.metrics = Arbiter::try_system_registry().map(|reg| reg.get::<MetricsActor>());
+        }
+        self.metrics.clone()
+    }
+
+    pub(crate) fn set_actor(&mut self, act: A) {
+        self.act.set(act);
     }
 
-    pub(crate) fn into_inner(self) -> A {
-        self.act
+    pub(crate) fn into_parts(mut self) -> (A, ActorCountGuard) {
+        (self.act.take(), self._count)
     }
 }
 
@@ -158,15 +647,20 @@ impl<A> Future for Context<A> where A: Actor<Context=Self>
         // update state
         match self.state {
             ActorState::Started => {
-                Actor::started(&mut self.act, ctx);
+                Actor::started(&mut *self.act, ctx);
                 self.state = ActorState::Running;
             },
             ActorState::Stopping => {
-                Actor::stopping(&mut self.act, ctx);
+                Actor::stopping(&mut *self.act, ctx);
             }
             _ => ()
         }
 
+        // actor is polled, lower priority actors do not need to wait for it
+        if let Some(ref high) = self.address.high {
+            high.clear();
+        }
+
         // check wait futures
         if let Ok(Async::NotReady) = self.wait.poll(&mut self.act, ctx) {
             return Ok(Async::NotReady)
@@ -180,10 +674,19 @@ impl<A> Future for Context<A> where A: Actor<Context=Self>
                 not_ready = false
             }
 
+            // actor state get transferred by runner
+            if self.migration.is_some() {
+                return Ok(Async::Ready(()))
+            }
+
+            if let Some(act) = self.address.takeover.take() {
+                self.take_over(act);
+            }
+
             self.items.poll(&mut self.act, ctx);
 
             // are we done
-            if !not_ready {
+            if !not_ready || self.address.has_priority() {
                 continue
             }
 
@@ -191,7 +694,7 @@ impl<A> Future for Context<A> where A: Actor<Context=Self>
             match self.state {
                 ActorState::Stopped => {
                     self.state = ActorState::Stopped;
-                    Actor::stopped(&mut self.act, ctx);
+                    Actor::stopped(&mut *self.act, ctx);
                     return Ok(Async::Ready(()))
                 },
                 ActorState::Stopping => {
@@ -201,11 +704,11 @@ impl<A> Future for Context<A> where A: Actor<Context=Self>
                             continue
                         } else {
                             self.state = ActorState::Stopped;
-                            Actor::stopped(&mut self.act, ctx);
+                            Actor::stopped(&mut *self.act, ctx);
                             return Ok(Async::Ready(()))
                         }
                     } else {
-                        Actor::stopping(&mut self.act, ctx);
+                        Actor::stopping(&mut *self.act, ctx);
                         prep_stop = true;
                         continue
                     }
@@ -213,7 +716,7 @@ impl<A> Future for Context<A> where A: Actor<Context=Self>
                 ActorState::Running => {
                     if !self.address.connected() && self.items.is_empty() {
                         self.state = ActorState::Stopping;
-                        Actor::stopping(&mut self.act, ctx);
+                        Actor::stopping(&mut *self.act, ctx);
                         prep_stop = true;
                         continue
                     }
@@ -226,6 +729,131 @@ impl<A> Future for Context<A> where A: Actor<Context=Self>
     }
 }
 
+/// Actor storage of a context, context could be created before actor
+/// exists, so actor factory can use context
+pub(crate) struct ActorSlot<A>(Option<A>);
+
+impl<A> ActorSlot<A> {
+    pub(crate) fn set(&mut self, act: A) {
+        self.0 = Some(act);
+    }
+
+    pub(crate) fn replace(&mut self, act: A) -> A {
+        std::mem::replace(&mut **self, act)
+    }
+
+    pub(crate) fn take(&mut self) -> A {
+        self.0.take().expect("Actor is not set")
+    }
+}
+
+impl<A> Default for ActorSlot<A> {
+    fn default() -> Self {
+        ActorSlot(None)
+    }
+}
+
+impl<A> std::ops::Deref for ActorSlot<A> {
+    type Target = A;
+
+    fn deref(&self) -> &A {
+        self.0.as_ref().expect("Actor is not set")
+    }
+}
+
+impl<A> std::ops::DerefMut for ActorSlot<A> {
+    fn deref_mut(&mut self) -> &mut A {
+        self.0.as_mut().expect("Actor is not set")
+    }
+}
+
+/// Actor migration, receives actor state and sync mailbox
+/// Actor id and sync mailbox, moved together with migrating actor
+pub(crate) type SyncMailbox<A> = (Uuid, Option<sync::UnboundedReceiver<Envelope<A>>>);
+
+pub(crate) trait Migration<A: Actor<Context=Context<A>>>: 'static {
+    fn migrate(self: Box<Self>, act: A, mailbox: SyncMailbox<A>);
+}
+
+impl<A, F> Migration<A> for F
+    where A: Actor<Context=Context<A>>,
+          F: FnOnce(A, SyncMailbox<A>) + 'static
+{
+    #[cfg_attr(feature="cargo-clippy", allow(boxed_local))]
+    fn migrate(self: Box<Self>, act: A, mailbox: SyncMailbox<A>) {
+        (*self)(act, mailbox)
+    }
+}
+
+struct MigrateEnvelope<A: Actor<Context=Context<A>>> {
+    f: Option<Box<Migration<A>>>,
+}
+
+impl<A> envelope::EnvelopeProxy for MigrateEnvelope<A> where A: Actor<Context=Context<A>>
+{
+    type Actor = A;
+
+    fn handle(&mut self, _: &mut A, ctx: &mut Context<A>) {
+        if !ctx.migratable {
+            warn!("Actor {} can not be migrated", actor_name::<A>());
+        } else if ctx.migration.is_none() {
+            // stop processing messages, runner transfers actor after context returns
+            ctx.address.paused += 1;
+            ctx.migration = self.f.take();
+        }
+    }
+
+    fn message_type(&self) -> &'static str {
+        "Migrate"
+    }
+}
+
+struct WatchEnvelope<A: Actor<Context=Context<A>>> {
+    tx: Option<SyncSender<()>>,
+    act: std::marker::PhantomData<A>,
+}
+
+impl<A> envelope::EnvelopeProxy for WatchEnvelope<A> where A: Actor<Context=Context<A>>
+{
+    type Actor = A;
+
+    fn handle(&mut self, _: &mut A, ctx: &mut Context<A>) {
+        if let Some(tx) = self.tx.take() {
+            ctx.address.sync_watchers.retain(|tx| !tx.is_canceled());
+            ctx.address.sync_watchers.push(tx);
+        }
+    }
+
+    fn message_type(&self) -> &'static str {
+        "Watch"
+    }
+}
+
+/// Runs context in arbiter's event loop and moves actor
+/// to other arbiter if migration is requested
+struct ContextRunner<A: Actor<Context=Context<A>>>(Option<Context<A>>);
+
+impl<A> Future for ContextRunner<A> where A: Actor<Context=Context<A>>
+{
+    type Item = ();
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        if let Some(ref mut ctx) = self.0 {
+            if let Ok(Async::NotReady) = ctx.poll() {
+                return Ok(Async::NotReady)
+            }
+        }
+        if let Some(mut ctx) = self.0.take() {
+            if let Some(migration) = ctx.migration.take() {
+                let mailbox = (ctx.address.id, ctx.address.sync_msgs.take());
+                migration.migrate(ctx.act.take(), mailbox);
+            }
+        }
+        Ok(Async::Ready(()))
+    }
+}
+
 impl<A> std::fmt::Debug for Context<A> where A: Actor<Context=Self> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(f, "Context({:?}: actor:{:?}) {{ state: {:?}, connected: {}, items: {} }}",
@@ -237,18 +865,55 @@ impl<A> std::fmt::Debug for Context<A> where A: Actor<Context=Self> {
 
 pub struct ActorAddressCell<A> where A: Actor, A::Context: AsyncContext<A>
 {
+    priority: VecDeque<Envelope<A>>,
+    dropped: usize,
+    control: bool,
+    paused: usize,
+    /// Pending `Context::exclusive()` sections, sections are dropped
+    /// together with context's items
+    exclusive: usize,
+    slow_handler: Option<Duration>,
+    priority_level: ActorPriority,
+    high: Option<HighPriority>,
+    throughput: Option<usize>,
+    handled: usize,
+    yielded: bool,
     sync_alive: bool,
     sync_msgs: Option<sync::UnboundedReceiver<Envelope<A>>>,
     unsync_msgs: unsync::UnboundedReceiver<ContextProtocol<A>>,
+    watchers: Vec<Sender<()>>,
+    sync_watchers: Vec<SyncSender<()>>,
+    /// Type of the message that is being handled
+    current: Option<&'static str>,
+    /// Actor id, shared by all addresses
+    id: Uuid,
+    /// Actor that takes over the context
+    takeover: Option<A>,
 }
 
 impl<A> Default for ActorAddressCell<A> where A: Actor, A::Context: AsyncContext<A> {
 
     fn default() -> Self {
         ActorAddressCell {
+            priority: VecDeque::new(),
+            dropped: 0,
+            control: false,
+            paused: 0,
+            exclusive: 0,
+            slow_handler: None,
+            priority_level: ActorPriority::Normal,
+            high: None,
+            throughput: Arbiter::throughput(),
+            handled: 0,
+            yielded: false,
             sync_alive: false,
             sync_msgs: None,
-            unsync_msgs: unsync::unbounded(),
+            unsync_msgs: unsync::unbounded_with_capacity(Arbiter::mailbox_capacity()),
+            current: None,
+            id: Uuid::new_v4(),
+            watchers: Vec::new(),
+            sync_watchers: Vec::new(),
+            takeover: None,
         }
     }
 }
@@ -263,7 +928,44 @@ impl<A> ActorAddressCell<A> where A: Actor, A::Context: AsyncContext<A>
     }
 
     pub fn connected(&mut self) -> bool {
-        self.unsync_msgs.connected() || self.sync_alive
+        // mailbox could still have messages if actor yielded
+        self.yielded || self.unsync_msgs.connected() || self.sync_alive
+    }
+
+    /// Keep processing messages while arbiter is frozen
+    pub fn set_control(&mut self) {
+        self.control = true;
+    }
+
+    fn paused(&self) -> bool {
+        self.paused > 0 || self.exclusive > 0 || (!self.control && Arbiter::frozen())
+    }
+
+    fn has_priority(&self) -> bool {
+        !self.priority.is_empty() && !self.yielded && !self.paused()
+    }
+
+    fn exhausted(&self) -> bool {
+        self.throughput.map(|throughput| self.handled >= throughput).unwrap_or(false)
+    }
+
+    fn handle(&mut self, mut env: Envelope<A>, act: &mut A, ctx: &mut A::Context) {
+        self.handled += 1;
+        let start = self.slow_handler.map(|_| Arbiter::now());
+        self.current = Some(env.message_type());
+        let handled = env.handle(act, ctx);
+        if !handled {
+            self.dropped += 1;
+        }
+        Arbiter::message_handled(!handled);
+        self.current = None;
+        if let (Some(start), Some(threshold)) = (start, self.slow_handler) {
+            let elapsed = Arbiter::now() - start;
+            if elapsed > threshold {
+                warn!("Slow handler {} for message {}: {:?}",
+                      actor_name::<A>(), env.message_type(), elapsed);
+            }
+        }
     }
 
     pub fn unsync_sender(&mut self) -> unsync::UnboundedSender<ContextProtocol<A>> {
@@ -271,7 +973,18 @@ impl<A> ActorAddressCell<A> where A: Actor, A::Context: AsyncContext<A>
     }
 
     pub fn unsync_address(&mut self) -> Address<A> {
-        Address::new(self.unsync_msgs.sender())
+        Address::new(self.unsync_msgs.sender(), self.id)
+    }
+
+    /// Set actor id, supervised actor shares id with its supervisor
+    pub(crate) fn set_id(&mut self, id: Uuid) {
+        self.id = id;
+    }
+
+    fn set_sync_receiver(&mut self, mailbox: SyncMailbox<A>) {
+        self.id = mailbox.0;
+        self.sync_alive = mailbox.1.is_some();
+        self.sync_msgs = mailbox.1;
     }
 
     pub fn sync_address(&mut self) -> SyncAddress<A> {
@@ -279,10 +992,10 @@ impl<A> ActorAddressCell<A> where A: Actor, A::Context: AsyncContext<A>
             let (tx, rx) = sync::unbounded();
             self.sync_msgs = Some(rx);
             self.sync_alive = true;
-            SyncAddress::new(tx)
+            SyncAddress::new(tx, self.id)
         } else {
             if let Some(ref mut addr) = self.sync_msgs {
-                return SyncAddress::new(addr.sender())
+                return SyncAddress::new(addr.sender(), self.id)
             }
             unreachable!();
         }
@@ -294,6 +1007,9 @@ type Item<A> = (SpawnHandle, Box<ActorFuture<Item=(), Error=(), Actor=A>>);
 pub struct ActorItemsCell<A> where A: Actor, A::Context: AsyncContext<A> {
     index: SpawnHandle,
     items: Vec<Item<A>>,
+    polling: bool,
+    cancelled: Vec<SpawnHandle>,
+    background: Vec<SpawnHandle>,
 }
 
 impl<A> Default for ActorItemsCell<A> where A: Actor, A::Context: AsyncContext<A> {
@@ -302,6 +1018,9 @@ impl<A> Default for ActorItemsCell<A> where A: Actor, A::Context: AsyncContext<A
         ActorItemsCell {
             index: SpawnHandle::default(),
             items: Vec::new(),
+            polling: false,
+            cancelled: Vec::new(),
+            background: Vec::new(),
         }
     }
 }
@@ -313,7 +1032,9 @@ impl<A> ActorItemsCell<A> where A: Actor, A::Context: AsyncContext<A>
     }
 
     pub fn close(&mut self) {
-        self.items.clear()
+        Arbiter::futures_completed(self.items.len());
+        self.items.clear();
+        self.background.clear();
     }
 
     pub fn spawn<F>(&mut self, fut: F) -> SpawnHandle
@@ -321,13 +1042,48 @@ impl<A> ActorItemsCell<A> where A: Actor, A::Context: AsyncContext<A>
     {
         self.index = self.index.next();
         self.items.push((self.index, Box::new(fut)));
+        Arbiter::future_spawned();
         self.index
     }
 
+    pub fn spawn_background<F>(&mut self, fut: F) -> SpawnHandle
+        where F: ActorFuture<Item=(), Error=(), Actor=A> + 'static
+    {
+        // forget finished background items
+        let items = &self.items;
+        self.background.retain(|handle| items.iter().any(|item| item.0 == *handle));
+
+        let handle = self.spawn(fut);
+        self.background.push(handle);
+        handle
+    }
+
+    /// Handles of spawned items, except long lived ones
+    pub fn finite_handles(&self) -> Vec<SpawnHandle> {
+        self.items.iter()
+            .map(|item| item.0)
+            .filter(|handle| !self.background.contains(handle))
+            .collect()
+    }
+
+    pub fn contains(&self, handle: SpawnHandle) -> bool {
+        self.items.iter().any(|item| item.0 == handle) && !self.cancelled.contains(&handle)
+    }
+
     pub fn cancel_future(&mut self, handle: SpawnHandle) -> bool {
+        // item could cancel itself, it get removed after poll
+        if self.polling {
+            if self.contains(handle) {
+                self.cancelled.push(handle);
+                return true
+            }
+            return false
+        }
+
         for index in 0..self.items.len() {
             if self.items[index].0 == handle {
                 self.items.remove(index);
+                Arbiter::futures_completed(1);
                 return true
             }
         }
@@ -335,12 +1091,31 @@ impl<A> ActorItemsCell<A> where A: Actor, A::Context: AsyncContext<A>
     }
 
     pub fn poll(&mut self, act: &mut A, ctx: &mut A::Context) {
+        self.polling = true;
+        self.poll_items(act, ctx);
+        self.polling = false;
+
+        if !self.cancelled.is_empty() {
+            let cancelled = std::mem::replace(&mut self.cancelled, Vec::new());
+            let len = self.items.len();
+            self.items.retain(|item| !cancelled.contains(&item.0));
+            Arbiter::futures_completed(len - self.items.len());
+        }
+    }
+
+    fn poll_items(&mut self, act: &mut A, ctx: &mut A::Context) {
         loop {
             let mut idx = 0;
             let mut len = self.items.len();
             let mut not_ready = true;
 
             while idx < len {
+                // cancelled items are not polled anymore
+                if !self.cancelled.is_empty() && self.cancelled.contains(&self.items[idx].0) {
+                    idx += 1;
+                    continue
+                }
+
                 let (drop, item) = match self.items[idx].1.poll(act, ctx) {
                     Ok(val) => match val {
                         Async::Ready(_) => {
@@ -363,6 +1138,7 @@ impl<A> ActorItemsCell<A> where A: Actor, A::Context: AsyncContext<A>
                 // item finishes, we need to remove it,
                 // replace current item with last item
                 if drop {
+                    Arbiter::futures_completed(1);
                     len -= 1;
                     if idx >= len {
                         self.items.pop();
@@ -383,6 +1159,12 @@ impl<A> ActorItemsCell<A> where A: Actor, A::Context: AsyncContext<A>
     }
 }
 
+impl<A> Drop for ActorItemsCell<A> where A: Actor, A::Context: AsyncContext<A> {
+    fn drop(&mut self) {
+        Arbiter::futures_completed(self.items.len());
+    }
+}
+
 
 impl<A> ActorFuture for ActorAddressCell<A> where A: Actor, A::Context: AsyncContext<A>
 {
@@ -392,38 +1174,84 @@ impl<A> ActorFuture for ActorAddressCell<A> where A: Actor, A::Context: AsyncCon
 
     fn poll(&mut self, act: &mut A, ctx: &mut A::Context) -> Poll<Self::Item, Self::Error>
     {
+        self.handled = 0;
+        self.yielded = false;
+
         loop {
+            // context replaces actor before next message
+            if self.takeover.is_some() {
+                return Ok(Async::Ready(()))
+            }
+
+            // messages stay in mailbox until arbiter thaws,
+            // exclusive sections complete or while actor migrates
+            if self.paused() {
+                return Ok(Async::NotReady)
+            }
+
+            // high priority actors handle their messages first
+            if self.high.is_none() && !self.control && Arbiter::high_priority_ready() {
+                self.yielded = true;
+                task::current().notify();
+                return Ok(Async::NotReady)
+            }
+
+            // give other actors in arbiter's thread chance to run
+            if self.exhausted() {
+                self.yielded = true;
+                task::current().notify();
+                return Ok(Async::NotReady)
+            }
+
             let mut not_ready = true;
 
+            // high priority messages
+            if let Some(env) = self.priority.pop_front() {
+                self.handle(env, act, ctx);
+                continue
+            }
+
             // unsync messages
-            match self.unsync_msgs.poll() {
+            match poll_mailbox(&self.high, &mut self.unsync_msgs) {
                 Ok(Async::Ready(Some(msg))) => {
                     not_ready = false;
                     match msg {
-                        ContextProtocol::Envelope(mut env) => {
-                            env.handle(act, ctx)
-                        }
+                        ContextProtocol::Envelope(env) => self.handle(env, act, ctx),
                         ContextProtocol::Upgrade(tx) => {
                             let _ = tx.send(self.sync_address());
                         }
+                        ContextProtocol::Watch(tx) => {
+                            self.watchers.retain(|tx| !tx.is_canceled());
+                            self.watchers.push(tx);
+                        }
+                        ContextProtocol::Stop => ctx.stop(),
+                        ContextProtocol::TakeOver(act) => {
+                            // context replaces actor before next message
+                            self.takeover = Some(act);
+                            return Ok(Async::Ready(()))
+                        }
                     }
                 }
                 Ok(Async::Ready(None)) | Ok(Async::NotReady) | Err(_) => (),
             }
 
             // sync messages
-            if self.sync_alive {
-                if let Some(ref mut msgs) = self.sync_msgs {
-                    match msgs.poll() {
-                        Ok(Async::Ready(Some(mut msg))) => {
-                            not_ready = false;
-                            msg.handle(act, ctx);
-                        }
+            if self.sync_alive && !self.exhausted() {
+                let msg = if let Some(ref mut msgs) = self.sync_msgs {
+                    match poll_mailbox(&self.high, msgs) {
+                        Ok(Async::Ready(Some(msg))) => Some(msg),
                         Ok(Async::Ready(None)) | Err(_) => {
                             self.sync_alive = false;
+                            None
                         },
-                        Ok(Async::NotReady) => (),
+                        Ok(Async::NotReady) => None,
                     }
+                } else {
+                    None
+                };
+                if let Some(msg) = msg {
+                    not_ready = false;
+                    self.handle(msg, act, ctx);
                 }
             }
 
@@ -434,6 +1262,184 @@ impl<A> ActorFuture for ActorAddressCell<A> where A: Actor, A::Context: AsyncCon
     }
 }
 
+/// Poll actor's mailbox, `High` priority actor gets marked as ready
+/// when new message arrives
+fn poll_mailbox<S: Stream>(high: &Option<HighPriority>, msgs: &mut S)
+                           -> Poll<Option<S::Item>, S::Error>
+{
+    match *high {
+        Some(ref high) => high.poll(msgs),
+        None => msgs.poll(),
+    }
+}
+
+/// Ready state of `High` priority actor, shared with mailbox notifications
+struct HighState {
+    ready: bool,
+    alive: bool,
+}
+
+/// Tracks whether `High` priority actor has messages to handle
+struct HighPriority {
+    state: Arc<Mutex<HighState>>,
+    counter: Arc<AtomicUsize>,
+}
+
+impl HighPriority {
+    fn new() -> HighPriority {
+        HighPriority {
+            state: Arc::new(Mutex::new(HighState{ready: false, alive: true})),
+            counter: Arbiter::high_priority_counter(),
+        }
+    }
+
+    fn poll<S: Stream>(&self, msgs: &mut S) -> Poll<Option<S::Item>, S::Error> {
+        let notify = Arc::new(HighNotify {
+            task: task::current(),
+            state: Arc::clone(&self.state),
+            counter: Arc::clone(&self.counter),
+        });
+        executor::with_notify(&notify, 0, || msgs.poll())
+    }
+
+    fn clear(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            if state.ready {
+                state.ready = false;
+                self.counter.fetch_sub(1, Ordering::SeqCst);
+            }
+        }
+    }
+}
+
+impl Drop for HighPriority {
+    fn drop(&mut self) {
+        // late notifications must not mark dropped actor as ready
+        if let Ok(mut state) = self.state.lock() {
+            if state.ready {
+                self.counter.fetch_sub(1, Ordering::SeqCst);
+            }
+            state.ready = false;
+            state.alive = false;
+        }
+    }
+}
+
+/// Marks `High` priority actor as ready and wakes up its task
+struct HighNotify {
+    task: task::Task,
+    state: Arc<Mutex<HighState>>,
+    counter: Arc<AtomicUsize>,
+}
+
+impl executor::Notify for HighNotify {
+    fn notify(&self, _: usize) {
+        if let Ok(mut state) = self.state.lock() {
+            if state.alive && !state.ready {
+                state.ready = true;
+                self.counter.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+        self.task.notify();
+    }
+}
+
+type LimitedFuture<A> = Box<ActorFuture<Item=(), Error=(), Actor=A>>;
+
+/// Futures spawned with `Context::spawn_limited()` under the same key
+struct Limiter<A> {
+    running: usize,
+    queue: VecDeque<LimitedFuture<A>>,
+}
+
+impl<A> Default for Limiter<A> {
+    fn default() -> Self {
+        Limiter{running: 0, queue: VecDeque::new()}
+    }
+}
+
+/// Limited future releases its slot on completion
+struct Limited<F> {
+    key: &'static str,
+    fut: F,
+}
+
+impl<A, F> ActorFuture for Limited<F>
+    where A: Actor<Context=Context<A>>,
+          F: ActorFuture<Item=(), Error=(), Actor=A>
+{
+    type Item = ();
+    type Error = ();
+    type Actor = A;
+
+    fn poll(&mut self, act: &mut A, ctx: &mut Context<A>) -> Poll<(), ()> {
+        if let Ok(Async::NotReady) = self.fut.poll(act, ctx) {
+            return Ok(Async::NotReady)
+        }
+        ctx.release_limited(self.key);
+        Ok(Async::Ready(()))
+    }
+}
+
+/// Replace actor with new actor that restores state of running actor
+pub(crate) struct ReloadActor<A>(Option<A>);
+
+impl<A> ReloadActor<A> {
+    pub fn new(act: A) -> Self {
+        ReloadActor(Some(act))
+    }
+}
+
+impl<A> envelope::EnvelopeProxy for ReloadActor<A> where A: Snapshotable + Actor<Context=Context<A>>
+{
+    type Actor = A;
+
+    fn handle(&mut self, act: &mut A, ctx: &mut Context<A>) {
+        if let Some(mut new) = self.0.take() {
+            new.restore(&act.snapshot());
+            // context replaces actor before next message
+            ctx.address.takeover = Some(new);
+        }
+    }
+
+    fn message_type(&self) -> &'static str {
+        "ReloadActor"
+    }
+}
+
+struct Exclusive<A, F> where A: Actor<Context=Context<A>>,
+                              F: FnOnce(&mut A, &mut Context<A>)
+{
+    act: std::marker::PhantomData<A>,
+    handles: Vec<SpawnHandle>,
+    f: Option<F>,
+}
+
+impl<A, F> ActorFuture for Exclusive<A, F>
+    where A: Actor<Context=Context<A>>,
+          F: FnOnce(&mut A, &mut Context<A>)
+{
+    type Item = ();
+    type Error = ();
+    type Actor = A;
+
+    fn poll(&mut self, act: &mut A, ctx: &mut Context<A>) -> Poll<Self::Item, Self::Error>
+    {
+        self.handles.retain(|handle| ctx.items.contains(*handle));
+        if !self.handles.is_empty() {
+            return Ok(Async::NotReady)
+        }
+
+        if let Some(f) = self.f.take() {
+            f(act, ctx);
+            ctx.address.exclusive -= 1;
+            // messages could be waiting in mailbox
+            task::current().notify();
+        }
+        Ok(Async::Ready(()))
+    }
+}
+
 pub(crate)
 struct ActorFutureCell<A, M, F, E>
     where A: Actor + Handler<M, E>,
@@ -509,6 +1515,7 @@ struct ActorStreamCell<A, M, E, S>
 {
     act: std::marker::PhantomData<A>,
     started: bool,
+    failed: bool,
     fut: Option<Response<A, M>>,
     stream: S,
 }
@@ -520,14 +1527,26 @@ impl<A, M, E, S> ActorStreamCell<A, M, E, S>
 {
     pub fn new(fut: S) -> ActorStreamCell<A, M, E, S>
     {
+        Arbiter::stream_added();
         ActorStreamCell {
             act: std::marker::PhantomData,
             started: false,
+            failed: false,
             fut: None,
             stream: fut }
     }
 }
 
+impl<A, M, E, S> Drop for ActorStreamCell<A, M, E, S>
+    where S: Stream<Item=M, Error=E>,
+          A: Actor + Handler<M, E> + StreamHandler<M, E>,
+          A::Context: AsyncContext<A>
+{
+    fn drop(&mut self) {
+        Arbiter::stream_removed();
+    }
+}
+
 impl<A, M, E, S> ActorFuture for ActorStreamCell<A, M, E, S>
     where S: Stream<Item=M, Error=E>,
           A: Actor + Handler<M, E> + StreamHandler<M, E>,
@@ -556,10 +1575,14 @@ impl<A, M, E, S> ActorFuture for ActorStreamCell<A, M, E, S>
                 }
             }
 
+            if self.failed {
+                <A as StreamHandler<M, E>>::finished(act, ctx);
+                return Err(())
+            }
+
             match self.stream.poll() {
                 Ok(Async::Ready(Some(msg))) => {
-                    let fut = <Self::Actor as Handler<M, E>>::handle(act, msg, ctx);
-                    self.fut = Some(fut);
+                    self.fut = <A as StreamHandler<M, E>>::handle_result(act, Ok(msg), ctx);
                     continue
                 }
                 Ok(Async::Ready(None)) => {
@@ -569,9 +1592,9 @@ impl<A, M, E, S> ActorFuture for ActorStreamCell<A, M, E, S>
                 Ok(Async::NotReady) =>
                     return Ok(Async::NotReady),
                 Err(err) => {
-                    <Self::Actor as Handler<M, E>>::error(act, err, ctx);
-                    <A as StreamHandler<M, E>>::finished(act, ctx);
-                    return Err(())
+                    self.fut = <A as StreamHandler<M, E>>::handle_result(act, Err(err), ctx);
+                    self.failed = true;
+                    continue
                 }
             }
         }
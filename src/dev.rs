@@ -9,5 +9,6 @@
 //! ```
 pub use prelude::*;
 
+pub use actor::response_type_names;
 pub use address::{ActorAddress};
 pub use context::{AsyncContextApi, ActorAddressCell, ActorItemsCell, ActorWaitCell};
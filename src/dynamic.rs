@@ -0,0 +1,182 @@
+use std::any::{type_name, Any, TypeId};
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use futures::sync::oneshot::Sender as SyncSender;
+
+use actor::{actor_name, Actor};
+use context::Context;
+use envelope::EnvelopeProxy;
+
+/// Message that is handled by handler registered at runtime
+///
+/// Actor registers handler with `Context::handle()` and removes it with
+/// `Context::unhandle()`, so no `Handler` and `ResponseType` implementations
+/// are required. Message is sent with `SyncAddress::send_dynamic()` or
+/// `SyncAddress::call_dynamic()`. If actor has no handler for the message,
+/// message is dropped without response.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate actix;
+/// extern crate futures;
+///
+/// use futures::Future;
+/// use actix::prelude::*;
+///
+/// struct Add(u32);
+///
+/// impl DynamicMessage for Add {
+///     type Item = u32;
+///     type Error = ();
+/// }
+///
+/// struct Reset;
+///
+/// impl DynamicMessage for Reset {
+///     type Item = ();
+///     type Error = ();
+/// }
+///
+/// struct Counter(u32);
+///
+/// impl Actor for Counter {
+///     type Context = Context<Self>;
+///
+///     fn started(&mut self, ctx: &mut Context<Self>) {
+///         ctx.handle(|act: &mut Counter, msg: Add, _: &mut Context<Counter>| {
+///                 act.0 += msg.0;
+///                 Ok(act.0)
+///             })
+///             .handle(|act: &mut Counter, _: Reset, _: &mut Context<Counter>| {
+///                 act.0 = 0;
+///                 Ok(())
+///             });
+///     }
+/// }
+///
+/// fn main() {
+///     let sys = System::new("test");
+///
+///     let addr: SyncAddress<_> = Counter(0).start();
+///     addr.send_dynamic(Add(2));
+///     Arbiter::handle().spawn(
+///         addr.call_dynamic(Add(3))
+///             .then(|res| {
+///                 assert_eq!(res.unwrap(), Ok(5));
+///                 Arbiter::system().send(msgs::SystemExit(0));
+///                 Ok(())
+///             }));
+///
+///     sys.run();
+/// }
+/// ```
+pub trait DynamicMessage: Send + 'static {
+    /// The type of value that handler returns on success
+    type Item: Send + 'static;
+
+    /// The type of error that handler returns on failure
+    type Error: Send + 'static;
+}
+
+type DynamicFn<A, M> = Box<FnMut(&mut A, M, &mut Context<A>)
+                                 -> Result<<M as DynamicMessage>::Item,
+                                           <M as DynamicMessage>::Error>>;
+
+/// Handlers registered by actor at runtime
+pub(crate) struct DynamicHandlers<A> {
+    table: HashMap<TypeId, Box<Any>>,
+    running: Option<TypeId>,
+    removed: bool,
+    act: PhantomData<A>,
+}
+
+impl<A> Default for DynamicHandlers<A> {
+    fn default() -> Self {
+        DynamicHandlers{table: HashMap::new(), running: None, removed: false, act: PhantomData}
+    }
+}
+
+impl<A> DynamicHandlers<A> where A: Actor<Context=Context<A>> {
+
+    pub fn insert<M, F>(&mut self, f: F)
+        where M: DynamicMessage,
+              F: FnMut(&mut A, M, &mut Context<A>) -> Result<M::Item, M::Error> + 'static,
+    {
+        let f: DynamicFn<A, M> = Box::new(f);
+        self.table.insert(TypeId::of::<M>(), Box::new(f));
+    }
+
+    pub fn remove<M: DynamicMessage>(&mut self) -> bool {
+        // handler could be removed while it is running
+        if self.running == Some(TypeId::of::<M>()) && !self.removed {
+            self.removed = true;
+            self.table.remove(&TypeId::of::<M>());
+            return true
+        }
+        self.table.remove(&TypeId::of::<M>()).is_some()
+    }
+
+    fn take<M: DynamicMessage>(&mut self) -> Option<DynamicFn<A, M>> {
+        let f = self.table.remove(&TypeId::of::<M>())
+            .and_then(|f| f.downcast::<DynamicFn<A, M>>().ok())
+            .map(|f| *f);
+        if f.is_some() {
+            self.running = Some(TypeId::of::<M>());
+            self.removed = false;
+        }
+        f
+    }
+
+    /// Put handler back unless it was removed or replaced during the call
+    fn restore<M: DynamicMessage>(&mut self, f: DynamicFn<A, M>) {
+        let id = TypeId::of::<M>();
+        if !self.removed && !self.table.contains_key(&id) {
+            self.table.insert(id, Box::new(f));
+        }
+        self.running = None;
+    }
+}
+
+pub(crate) struct DynamicEnvelope<A, M: DynamicMessage> {
+    msg: Option<M>,
+    tx: Option<SyncSender<Result<M::Item, M::Error>>>,
+    act: PhantomData<A>,
+}
+
+impl<A, M: DynamicMessage> DynamicEnvelope<A, M> {
+    pub fn new(msg: M, tx: Option<SyncSender<Result<M::Item, M::Error>>>) -> Self {
+        DynamicEnvelope{msg: Some(msg), tx: tx, act: PhantomData}
+    }
+}
+
+impl<A, M> EnvelopeProxy for DynamicEnvelope<A, M>
+    where A: Actor<Context=Context<A>>, M: DynamicMessage
+{
+    type Actor = A;
+
+    fn handle(&mut self, act: &mut A, ctx: &mut Context<A>) {
+        let msg = match self.msg.take() {
+            Some(msg) => msg,
+            None => return,
+        };
+        let mut f = match ctx.dynamic.take::<M>() {
+            Some(f) => f,
+            None => {
+                warn!("Actor {} does not handle {}",
+                      actor_name::<A>(), type_name::<M>());
+                return
+            }
+        };
+        let res = f(act, msg, ctx);
+        ctx.dynamic.restore(f);
+
+        if let Some(tx) = self.tx.take() {
+            let _ = tx.send(res);
+        }
+    }
+
+    fn message_type(&self) -> &'static str {
+        type_name::<M>()
+    }
+}
@@ -1,8 +1,13 @@
+use std::any::type_name;
+use std::cell::Cell;
 use std::marker::PhantomData;
+use std::time::Instant;
+use uuid::Uuid;
 use futures::{Async, Poll};
 use futures::unsync::oneshot::Sender;
 use futures::sync::oneshot::Sender as SyncSender;
 
+use arbiter::Arbiter;
 use fut::ActorFuture;
 use actor::{Actor, ActorContext, AsyncContext, Handler, ResponseType};
 use message::Response;
@@ -27,18 +32,55 @@ impl<A, M> ToEnvelope<A, Context<A>, M> for A
 {
     fn pack(msg: M, tx: Option<SyncSender<Result<A::Item, A::Error>>>) -> Envelope<A>
     {
-        Envelope(Box::new(RemoteEnvelope{msg: Some(msg), tx: tx}))
+        let request = tx.is_some();
+        Envelope::with_request(RemoteEnvelope{msg: Some(msg), tx: tx}, request)
     }
 }
 
-pub struct Envelope<A>(Box<EnvelopeProxy<Actor=A>>);
+thread_local!(
+    /// Correlation id of the message that is being handled
+    static CID: Cell<Option<Uuid>> = Cell::new(None);
+);
+
+thread_local!(
+    /// Deadline of the message that is being handled
+    static DEADLINE: Cell<Option<Instant>> = Cell::new(None);
+);
+
+/// Correlation id of the message that is being handled in current thread
+pub(crate) fn correlation_id() -> Option<Uuid> {
+    CID.with(|cell| cell.get())
+}
+
+/// Deadline of the message that is being handled in current thread
+pub(crate) fn deadline() -> Option<Instant> {
+    DEADLINE.with(|cell| cell.get())
+}
+
+pub struct Envelope<A> {
+    proxy: Box<EnvelopeProxy<Actor=A>>,
+    cid: Option<Uuid>,
+    deadline: Option<Instant>,
+}
 
 impl<A> Envelope<A> where A: Actor {
 
     pub(crate) fn new<T>(envelop: T) -> Self
         where T: EnvelopeProxy<Actor=A> + Sized + 'static
     {
-        Envelope(Box::new(envelop))
+        Envelope::with_request(envelop, false)
+    }
+
+    /// Envelope inherits correlation id of the message that is being handled,
+    /// request starts new correlation id if there is no one. Request also
+    /// inherits deadline of the message that is being handled.
+    pub(crate) fn with_request<T>(envelop: T, request: bool) -> Self
+        where T: EnvelopeProxy<Actor=A> + Sized + 'static
+    {
+        let cid = correlation_id().or_else(
+            || if request { Some(Uuid::new_v4()) } else { None });
+        let deadline = if request { deadline() } else { None };
+        Envelope{proxy: Box::new(envelop), cid: cid, deadline: deadline}
     }
 
     pub(crate) fn local<M>(msg: M, tx: Option<Sender<Result<A::Item, A::Error>>>) -> Self
@@ -46,11 +88,42 @@ impl<A> Envelope<A> where A: Actor {
               A: Actor + Handler<M>,
               A::Context: AsyncContext<A>
     {
-        Envelope(Box::new(LocalEnvelope{msg: Some(msg), tx: tx, act: PhantomData}))
+        let request = tx.is_some();
+        Envelope::with_request(LocalEnvelope{msg: Some(msg), tx: tx, act: PhantomData}, request)
+    }
+
+    /// Message is dropped if it is not handled before deadline,
+    /// inherited deadline is kept if it is earlier
+    pub(crate) fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(match self.deadline {
+            Some(inherited) if inherited < deadline => inherited,
+            _ => deadline,
+        });
+        self
+    }
+
+    /// Handle message, returns `false` if message is dropped because
+    /// its deadline has passed.
+    pub(crate) fn handle(&mut self, act: &mut A, ctx: &mut A::Context) -> bool {
+        if let Some(deadline) = self.deadline {
+            let now = Arbiter::now();
+            if deadline < now {
+                debug!("Drop message, deadline passed {:?} ago", now - deadline);
+                return false
+            }
+        }
+
+        let prev = CID.with(|cell| cell.replace(self.cid));
+        let prev_deadline = DEADLINE.with(|cell| cell.replace(self.deadline));
+        self.proxy.handle(act, ctx);
+        DEADLINE.with(|cell| cell.set(prev_deadline));
+        CID.with(|cell| cell.set(prev));
+        true
     }
 
-    pub(crate) fn handle(&mut self, act: &mut A, ctx: &mut A::Context) {
-        self.0.handle(act, ctx)
+    /// Type name of the wrapped message
+    pub(crate) fn message_type(&self) -> &'static str {
+        self.proxy.message_type()
     }
 }
 
@@ -58,12 +131,52 @@ impl<A> Envelope<A> where A: Actor {
 unsafe impl<T> Send for Envelope<T> {}
 
 
+/// Envelope notifies sender after wrapped message get handled
+pub(crate) struct TrackedEnvelope<A: Actor> {
+    env: Envelope<A>,
+    tx: Option<SyncSender<()>>,
+}
+
+impl<A: Actor> TrackedEnvelope<A> {
+    pub fn new(env: Envelope<A>, tx: SyncSender<()>) -> TrackedEnvelope<A> {
+        TrackedEnvelope{env: env, tx: Some(tx)}
+    }
+}
+
+impl<A: Actor> EnvelopeProxy for TrackedEnvelope<A> {
+    type Actor = A;
+
+    fn handle(&mut self, act: &mut A, ctx: &mut A::Context) {
+        self.env.handle(act, ctx);
+        if let Some(tx) = self.tx.take() {
+            let _ = tx.send(());
+        }
+    }
+
+    fn message_type(&self) -> &'static str {
+        self.env.message_type()
+    }
+}
+
+/// Validate message and pass it to actor's handler
+pub(crate) fn handle_message<A, M>(act: &mut A, msg: M, ctx: &mut A::Context) -> Response<A, M>
+    where A: Actor + Handler<M>
+{
+    match <A as Handler<M>>::validate(act, &msg) {
+        Ok(()) => <A as Handler<M>>::handle(act, msg, ctx),
+        Err(err) => Response::error(err),
+    }
+}
+
 pub trait EnvelopeProxy {
 
     type Actor: Actor;
 
     /// handle message within new actor and context
     fn handle(&mut self, act: &mut Self::Actor, ctx: &mut <Self::Actor as Actor>::Context);
+
+    /// type name of the message
+    fn message_type(&self) -> &'static str;
 }
 
 struct LocalEnvelope<A, M> where A: Actor + Handler<M>, A::Context: AsyncContext<A> {
@@ -82,7 +195,7 @@ impl<A, M> EnvelopeProxy for LocalEnvelope<A, M>
     fn handle(&mut self, act: &mut Self::Actor, ctx: &mut <Self::Actor as Actor>::Context)
     {
         if let Some(msg) = self.msg.take() {
-            let fut = <Self::Actor as Handler<M>>::handle(act, msg, ctx);
+            let fut = handle_message(act, msg, ctx);
             let tx = if let Some(tx) = self.tx.take() {
                 Some(EnvelopFutureItem::Local(tx))
             } else {
@@ -93,6 +206,9 @@ impl<A, M> EnvelopeProxy for LocalEnvelope<A, M>
             ctx.spawn(f);
         }
     }
+    fn message_type(&self) -> &'static str {
+        type_name::<M>()
+    }
 }
 
 pub(crate) struct RemoteEnvelope<A, M>
@@ -125,7 +241,7 @@ impl<A, M> EnvelopeProxy for RemoteEnvelope<A, M>
     fn handle(&mut self, act: &mut Self::Actor, ctx: &mut <Self::Actor as Actor>::Context)
     {
         if let Some(msg) = self.msg.take() {
-            let fut = <Self::Actor as Handler<M>>::handle(act, msg, ctx);
+            let fut = handle_message(act, msg, ctx);
             let tx = if let Some(tx) = self.tx.take() {
                 Some(EnvelopFutureItem::Remote(tx))
             } else {
@@ -136,6 +252,9 @@ impl<A, M> EnvelopeProxy for RemoteEnvelope<A, M>
             ctx.spawn(f);
         }
     }
+    fn message_type(&self) -> &'static str {
+        type_name::<M>()
+    }
 }
 
 
@@ -161,6 +280,12 @@ impl<A, M> ActorFuture for EnvelopFuture<A, M> where A: Actor + Handler<M>
             act: &mut A,
             ctx: &mut <Self::Actor as Actor>::Context) -> Poll<Self::Item, Self::Error>
     {
+        // message is rejected, requester gets notified by dropped sender
+        if self.fut.is_canceled() {
+            self.tx.take();
+            return Ok(Async::Ready(()))
+        }
+
         match self.fut.poll(act, ctx) {
             Ok(Async::Ready(val)) => {
                 match self.tx.take() {
@@ -15,9 +15,10 @@ use actor::{Actor, Supervised,
             Handler, ResponseType, StreamHandler, SpawnHandle,
             FramedActor, ActorState, ActorContext, AsyncContext};
 use address::{Subscriber};
-use context::{ActorAddressCell, ActorItemsCell, ActorWaitCell, AsyncContextApi};
+use context::{ActorAddressCell, ActorItemsCell, ActorSlot, ActorWaitCell, AsyncContextApi};
 use envelope::{Envelope, ToEnvelope, RemoteEnvelope};
 use message::Response;
+use registry::ActorCountGuard;
 
 
 /// Actor execution context for
@@ -27,12 +28,13 @@ pub struct FramedContext<A>
           A: StreamHandler<<<A as FramedActor>::Codec as Decoder>::Item,
                            <<A as FramedActor>::Codec as Decoder>::Error>,
 {
-    act: A,
+    act: ActorSlot<A>,
     state: ActorState,
     address: ActorAddressCell<A>,
     framed: Option<ActorFramedCell<A>>,
     wait: ActorWaitCell<A>,
     items: ActorItemsCell<A>,
+    _count: ActorCountGuard,
 }
 
 type ToEnvelopeSender<A, M> = SyncSender<Result<<A as ResponseType<M>>::Item,
@@ -47,7 +49,8 @@ impl<A, M> ToEnvelope<A, FramedContext<A>, M> for A
 {
     fn pack(msg: M, tx: Option<ToEnvelopeSender<A, M>>) -> Envelope<A>
     {
-        Envelope::new(RemoteEnvelope::new(msg, tx))
+        let request = tx.is_some();
+        Envelope::with_request(RemoteEnvelope::new(msg, tx), request)
     }
 }
 
@@ -154,14 +157,25 @@ impl<A> FramedContext<A>
 {
     pub(crate) fn new(act: A, io: <A as FramedActor>::Io,
                       codec: <A as FramedActor>::Codec) -> FramedContext<A>
+    {
+        let mut ctx = FramedContext::empty(io, codec);
+        ctx.set_actor(act);
+        ctx
+    }
+
+    /// Create context without actor, actor has to be set with `set_actor`
+    /// before context starts
+    pub(crate) fn empty(io: <A as FramedActor>::Io,
+                        codec: <A as FramedActor>::Codec) -> FramedContext<A>
     {
         FramedContext {
-            act: act,
+            act: ActorSlot::default(),
             state: ActorState::Started,
             address: ActorAddressCell::default(),
             framed: Some(ActorFramedCell::new(io.framed(codec))),
             wait: ActorWaitCell::default(),
             items: ActorItemsCell::default(),
+            _count: ActorCountGuard::new::<A>(),
         }
     }
 
@@ -176,16 +190,16 @@ impl<A> FramedContext<A>
         self.act.restarting(ctx);
     }
 
-    pub(crate) fn replace_actor(&mut self, srv: A) -> A {
-        std::mem::replace(&mut self.act, srv)
+    pub(crate) fn set_actor(&mut self, act: A) {
+        self.act.set(act);
     }
 
     pub(crate) fn address_cell(&mut self) -> &mut ActorAddressCell<A> {
         &mut self.address
     }
 
-    pub(crate) fn into_inner(self) -> A {
-        self.act
+    pub(crate) fn into_inner(mut self) -> A {
+        self.act.take()
     }
 }
 
@@ -206,11 +220,11 @@ impl<A> Future for FramedContext<A>
         // update state
         match self.state {
             ActorState::Started => {
-                Actor::started(&mut self.act, ctx);
+                Actor::started(&mut *self.act, ctx);
                 self.state = ActorState::Running;
             },
             ActorState::Stopping => {
-                Actor::stopping(&mut self.act, ctx);
+                Actor::stopping(&mut *self.act, ctx);
             }
             _ => ()
         }
@@ -255,7 +269,7 @@ impl<A> Future for FramedContext<A>
             match self.state {
                 ActorState::Stopped => {
                     self.state = ActorState::Stopped;
-                    Actor::stopped(&mut self.act, ctx);
+                    Actor::stopped(&mut *self.act, ctx);
                     return Ok(Async::Ready(()))
                 },
                 ActorState::Stopping => {
@@ -268,11 +282,11 @@ impl<A> Future for FramedContext<A>
                             continue
                         } else {
                             self.state = ActorState::Stopped;
-                            Actor::stopped(&mut self.act, ctx);
+                            Actor::stopped(&mut *self.act, ctx);
                             return Ok(Async::Ready(()))
                         }
                     } else {
-                        Actor::stopping(&mut self.act, ctx);
+                        Actor::stopping(&mut *self.act, ctx);
                         prep_stop = true;
                         continue
                     }
@@ -282,7 +296,7 @@ impl<A> Future for FramedContext<A>
                         self.items.is_empty()
                     {
                         self.state = ActorState::Stopping;
-                        Actor::stopping(&mut self.act, ctx);
+                        Actor::stopping(&mut *self.act, ctx);
                         prep_stop = true;
                         continue
                     }
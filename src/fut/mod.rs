@@ -88,6 +88,18 @@ pub trait ActorFuture {
 
 }
 
+impl<F: ActorFuture + ?Sized> ActorFuture for Box<F> {
+    type Item = F::Item;
+    type Error = F::Error;
+    type Actor = F::Actor;
+
+    fn poll(&mut self, srv: &mut Self::Actor, ctx: &mut <Self::Actor as Actor>::Context)
+            -> Poll<Self::Item, Self::Error>
+    {
+        (**self).poll(srv, ctx)
+    }
+}
+
 /// A stream of values, not all of which may have been produced yet.
 ///
 /// This is similar to `futures::Stream` trait, except it works with `Actor`
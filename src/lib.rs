@@ -30,14 +30,23 @@ extern crate tokio_signal;
 #[cfg(feature="signal")]
 extern crate libc;
 
+#[cfg(feature="serde")]
+#[macro_use]
+extern crate serde;
+
 mod actor;
 mod arbiter;
 mod address;
+mod behavior;
+mod channel;
+mod clock;
 mod context;
+mod dynamic;
 mod envelope;
 mod framed;
 mod queue;
 mod message;
+mod pool;
 mod registry;
 mod system;
 mod supervisor;
@@ -51,15 +60,23 @@ pub mod sync;
 pub mod dev;
 
 pub use fut::{ActorFuture, ActorStream, WrapFuture, WrapStream};
-pub use actor::{Actor, ActorState, FramedActor, Supervised,
+pub use actor::{Actor, ActorPriority, ActorState, FramedActor, Supervised, Snapshotable,
                 Handler, ResponseType, StreamHandler,
-                ActorContext, AsyncContext, SpawnHandle};
-pub use arbiter::Arbiter;
-pub use address::{Address, SyncAddress, Subscriber, ActorAddress};
+                ActorContext, AsyncContext, SpawnHandle, StreamHandle, WatchHandle,
+                response_type_names};
+pub use arbiter::{Arbiter, ArbiterBuilder, ArbiterMetrics, ArbiterStartError, SystemInfo};
+pub use address::{Address, SyncAddress, Subscriber, Recipient, ActorAddress, CallError,
+                  TrySendError, Watchable};
+pub use behavior::{Behavior, BehaviorHandler, Handlers};
+pub use channel::{actor_channel, ChannelSender, ChannelReceiver};
+pub use clock::TestClock;
 pub use context::{Context, ContextFutureSpawner};
+pub use dynamic::DynamicMessage;
 pub use framed::FramedContext;
 pub use message::{Request, Response};
+pub use pool::ArbiterPool;
 pub use registry::{Registry, SystemRegistry, ArbiterService, SystemService};
-pub use system::{System, SystemRunner};
+pub use system::{System, SystemRunner, SpawnError};
 pub use utils::Condition;
 pub use supervisor::Supervisor;
+pub use sync::BlockingError;
@@ -1,12 +1,14 @@
 use std;
+use std::time::Duration;
 use std::marker::PhantomData;
 
 use futures::{Async, Future, Poll};
 use futures::unsync::oneshot::{Canceled, Receiver};
 use futures::sync::oneshot::{Receiver as SyncReceiver};
 
-use fut::ActorFuture;
+use fut::{self, ActorFuture};
 use actor::{Actor, Handler, ResponseType};
+use utils::TimeoutWrapper;
 
 enum RequestIo<M, A: Handler<M>> {
     Local(Receiver<Result<A::Item, A::Error>>),
@@ -93,6 +95,18 @@ impl<A, M, T> std::convert::From<T> for Response<A, M>
     }
 }
 
+/// Helper trait that converts `Result` to `Response`.
+impl<A, M> std::convert::From<Result<A::Item, A::Error>> for Response<A, M>
+    where A: Actor + ResponseType<M>,
+{
+    fn from(res: Result<A::Item, A::Error>) -> Response<A, M> {
+        match res {
+            Ok(item) => Response::reply(item),
+            Err(err) => Response::error(err),
+        }
+    }
+}
+
 impl<A, M> Response<A, M> where A: Actor + ResponseType<M>
 {
     /// Create response
@@ -107,6 +121,16 @@ impl<A, M> Response<A, M> where A: Actor + ResponseType<M>
         Response {inner: Some(ResponseTypeItem::Fut(Box::new(fut)))}
     }
 
+    /// Create response that replies after specified period of time.
+    ///
+    /// Handler does not block, requester receives `val` after `delay` elapses.
+    /// Timer runs in current arbiter's event loop.
+    pub fn reply_after(val: A::Item, delay: Duration) -> Self
+        where A::Item: 'static, A::Error: 'static
+    {
+        Response::async_reply(fut::wrap_future(TimeoutWrapper::new(val, delay)))
+    }
+
     /// Create unit response
     pub fn empty() -> Self where A: ResponseType<M, Item=()> {
         Response {inner: Some(ResponseTypeItem::Item(()))}
@@ -117,6 +141,15 @@ impl<A, M> Response<A, M> where A: Actor + ResponseType<M>
         Response {inner: Some(ResponseTypeItem::Error(err))}
     }
 
+    /// Response that drops the message without reply, requester gets `Canceled` error
+    pub(crate) fn canceled() -> Self {
+        Response {inner: None}
+    }
+
+    pub(crate) fn is_canceled(&self) -> bool {
+        self.inner.is_none()
+    }
+
     pub(crate) fn result(&mut self) -> Option<Result<A::Item, A::Error>> {
         if let Some(item) = self.inner.take() {
             match item {
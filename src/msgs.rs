@@ -1,16 +1,114 @@
 //! Actix system messages
+//!
+//! With `serde` feature enabled, messages that do not carry closures
+//! or channels implement `Serialize` and `Deserialize`.
 
-use actor::Actor;
-use address::SyncAddress;
+use std::any::TypeId;
+use futures::{Future, IntoFuture};
+use futures::sync::oneshot::Receiver;
+#[cfg(feature="test-utils")]
+use futures::sync::oneshot::Sender as SyncSender;
+
+use actor::{Actor, AsyncContext};
+use address::{Address, SyncAddress};
+use arbiter::Arbiter;
 use context::Context;
+#[cfg(feature="test-utils")]
+use envelope::EnvelopeProxy;
+use registry::SystemService;
 
 /// Stop system execution
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
 pub struct SystemExit(pub i32);
 
+/// Defer `SystemExit` processing until receiver resolves
+///
+/// `SystemExit` message received while processing is deferred get applied
+/// after all deferrals resolve, receiver cancellation resolves deferral as well.
+/// Deferral resolves anyway after shutdown timeout, see `System::set_shutdown_timeout()`.
+/// If several `SystemExit` messages are received, first exit code is used.
+pub struct DeferSystemExit(pub Receiver<()>);
+
 /// Stop arbiter execution
+///
+/// Arbiter rejects new `Execute`, `ExecuteAsync` and `StartActor` messages,
+/// in-flight `Execute` and `ExecuteAsync` jobs complete before event loop stops.
+/// Arbiter waits for in-flight jobs not longer than 30 seconds, unless
+/// drain timeout is set with `Arbiter::set_drain_timeout()`.
+/// Rejected `Execute` and `ExecuteAsync` messages are dropped without response.
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
 pub struct StopArbiter(pub i32);
 
+/// Freeze all arbiters of the system
+///
+/// Every registered arbiter stops draining actor mailboxes, incoming messages
+/// are buffered until `ThawSystem` is received. Arbiters and `System` keep
+/// processing their control messages. Response resolves after all arbiters
+/// are frozen, arbiters registered later start frozen.
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+pub struct FreezeSystem;
+
+/// Resume message processing in all arbiters frozen with `FreezeSystem`
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+pub struct ThawSystem;
+
+/// System arbiter address has changed
+///
+/// `System` actor broadcasts this message to all registered arbiters,
+/// each arbiter updates address returned by `Arbiter::system_arbiter()`.
+#[derive(Clone)]
+pub struct SystemArbiterUpdated(pub SyncAddress<Arbiter>);
+
+/// Get `Remote` handle of arbiter's event loop
+///
+/// `Arbiter` responds with `tokio_core::reactor::Remote`, other threads
+/// could use it to spawn futures onto arbiter's event loop directly,
+/// without `Execute` message.
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+pub struct GetRemote;
+
+/// Get number of live actors in the system
+///
+/// `System` actor responds with number of actors that are started and not
+/// stopped yet, in all arbiters. Count includes actix's own actors,
+/// i.e. `System`, `Arbiter` and services, and every sync actor thread.
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+pub struct ActorCount;
+
+/// Get runtime counters of all arbiters of the system
+///
+/// `System` actor responds with `ArbiterMetrics` that sums counters of all
+/// registered arbiters, including system arbiter. Uptime is the longest
+/// uptime among arbiters. See `Arbiter::metrics()`.
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+pub struct SystemMetrics;
+
+/// Check if system service is started, without starting it
+///
+/// `System` actor responds with `true` if service with given type id was
+/// started with `SystemRegistry::get()`. Unlike `SystemRegistry::get()`
+/// this message never starts the service.
+pub struct IsServiceRunning(pub TypeId);
+
+impl IsServiceRunning {
+    /// Create message for service `S`
+    pub fn of<S: SystemService>() -> IsServiceRunning {
+        IsServiceRunning(TypeId::of::<S>())
+    }
+}
+
+/// Watched actor stopped
+///
+/// Actor receives this message for every actor it watches with `Context::watch`.
+pub struct Terminated {
+    /// Type name of the stopped actor
+    pub actor_type: &'static str,
+}
+
 /// Start actor in arbiter's thread
+///
+/// Arbiter responds with error if it is stopping or if system
+/// reached actor limit set with `System::set_actor_limit()`.
 pub struct StartActor<A: Actor>(Box<FnBox<A>>);
 
 impl<A: Actor<Context=Context<A>>> StartActor<A>
@@ -39,7 +137,9 @@ impl<A: Actor, F: FnOnce() -> SyncAddress<A> + Send + 'static> FnBox<A> for F {
 
 /// Execute function in arbiter's thread
 ///
-/// Arbiter` actor handles Execute message.
+/// Arbiter` actor handles Execute message. Function returns either result
+/// or future, arbiter replies immediately if result is ready,
+/// otherwise it spawns the future and replies once it resolves.
 ///
 /// # Example
 ///
@@ -68,28 +168,163 @@ pub struct Execute<I: Send + 'static = (), E: Send + 'static = ()>(Box<FnExec<I,
 impl<I, E> Execute<I, E>
     where I: Send + 'static, E: Send + 'static
 {
-    pub fn new<F>(f: F) -> Self where F: FnOnce() -> Result<I, E> + Send + 'static
+    pub fn new<F, R>(f: F) -> Self
+        where F: FnOnce() -> R + Send + 'static,
+              R: IntoFuture<Item=I, Error=E> + 'static,
     {
         Execute(Box::new(f))
     }
 
-    /// Execute enclosed function
+    /// Execute enclosed function, if function returns future
+    /// current thread is blocked until future resolves.
+    ///
+    /// Use it only in threads without event loop, for example in
+    /// `SyncArbiter` workers. Future that needs arbiter's event loop
+    /// never resolves if the same loop is blocked waiting for it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called in arbiter's thread.
     pub fn exec(self) -> Result<I, E> {
+        if Arbiter::in_arbiter() {
+            panic!("Execute::exec() blocks arbiter's event loop, send message to arbiter instead");
+        }
+        self.0.call_box().wait()
+    }
+
+    /// Execute enclosed function and return its future
+    pub(crate) fn into_future(self) -> Box<Future<Item=I, Error=E>> {
         self.0.call_box()
     }
 }
 
 trait FnExec<I: Send + 'static, E: Send + 'static>: Send + 'static {
-    fn call_box(self: Box<Self>) -> Result<I, E>;
+    fn call_box(self: Box<Self>) -> Box<Future<Item=I, Error=E>>;
 }
 
-impl<I, E, F> FnExec<I, E> for F
+impl<I, E, F, R> FnExec<I, E> for F
     where I: Send + 'static,
           E: Send + 'static,
-          F: FnOnce() -> Result<I, E> + Send + 'static
+          F: FnOnce() -> R + Send + 'static,
+          R: IntoFuture<Item=I, Error=E> + 'static,
 {
     #[cfg_attr(feature="cargo-clippy", allow(boxed_local))]
-    fn call_box(self: Box<Self>) -> Result<I, E> {
-        (*self)()
+    fn call_box(self: Box<Self>) -> Box<Future<Item=I, Error=E>> {
+        Box::new((*self)().into_future())
+    }
+}
+
+/// Execute future in arbiter's thread
+///
+/// Arbiter` actor handles ExecuteAsync message, response is sent
+/// after future resolves. `Arbiter::run_in()` is a shortcut for this message.
+pub struct ExecuteAsync<I: Send + 'static = (), E: Send + 'static = ()>(
+    Box<Future<Item=I, Error=E> + Send>);
+
+impl<I, E> ExecuteAsync<I, E>
+    where I: Send + 'static, E: Send + 'static
+{
+    pub fn new<F>(fut: F) -> Self where F: Future<Item=I, Error=E> + Send + 'static
+    {
+        ExecuteAsync(Box::new(fut))
+    }
+
+    pub(crate) fn into_future(self) -> Box<Future<Item=I, Error=E> + Send> {
+        self.0
+    }
+}
+
+/// Forward message to actor's address
+///
+/// `Arbiter` actor handles `ForwardTo` by sending `msg` to `addr`, response
+/// of the target actor is not awaited. Proxy actors can use this message to
+/// forward messages of any type through `Arbiter::arbiter()` address.
+///
+/// # Example
+///
+/// ```rust
+/// extern crate actix;
+///
+/// use actix::prelude::*;
+///
+/// struct Ping;
+///
+/// struct MyActor;
+///
+/// impl Actor for MyActor {
+///    type Context = Context<Self>;
+/// }
+///
+/// impl ResponseType<Ping> for MyActor {
+///    type Item = ();
+///    type Error = ();
+/// }
+///
+/// impl Handler<Ping> for MyActor {
+///    fn handle(&mut self, _: Ping, _: &mut Context<Self>) -> Response<Self, Ping> {
+///        Arbiter::system().send(msgs::SystemExit(0));
+///        Self::empty()
+///    }
+/// }
+///
+/// fn main() {
+///    let sys = System::new("test");
+///
+///    let addr: Address<_> = MyActor.start();
+///    Arbiter::arbiter().send(msgs::ForwardTo::new(addr, Ping));
+///
+///    sys.run();
+/// }
+/// ```
+pub struct ForwardTo<A, M> where A: Actor, A::Context: AsyncContext<A> {
+    /// Address of the target actor
+    pub addr: Address<A>,
+    /// Message to forward
+    pub msg: M,
+}
+
+impl<A, M> ForwardTo<A, M> where A: Actor, A::Context: AsyncContext<A>
+{
+    pub fn new(addr: Address<A>, msg: M) -> Self {
+        ForwardTo{addr: addr, msg: msg}
+    }
+}
+
+#[cfg(feature="test-utils")]
+type InspectFn<A, R> = Box<FnOnce(&A) -> R + Send>;
+
+/// Inspect actor's state from test code
+///
+/// Closure is called with the actor between messages, its result is sent back
+/// to the caller. Message is sent with `Address::inspect()`,
+/// `SyncAddress::inspect()` or `SyncAddress::inspect_timeout()`, so actor does
+/// not need `Handler` implementation. Available with `test-utils` feature.
+#[cfg(feature="test-utils")]
+pub struct Inspect<A, R> {
+    f: Option<InspectFn<A, R>>,
+    tx: Option<SyncSender<R>>,
+}
+
+#[cfg(feature="test-utils")]
+impl<A, R> Inspect<A, R> {
+    pub(crate) fn new<F>(f: F, tx: SyncSender<R>) -> Self
+        where F: FnOnce(&A) -> R + Send + 'static
+    {
+        Inspect{f: Some(Box::new(f)), tx: Some(tx)}
+    }
+}
+
+#[cfg(feature="test-utils")]
+impl<A: Actor, R: Send + 'static> EnvelopeProxy for Inspect<A, R> {
+    type Actor = A;
+
+    fn handle(&mut self, act: &mut A, _: &mut A::Context) {
+        if let (Some(f), Some(tx)) = (self.f.take(), self.tx.take()) {
+            let _ = tx.send(f(act));
+        }
+    }
+
+    fn message_type(&self) -> &'static str {
+        "Inspect"
     }
 }
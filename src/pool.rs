@@ -0,0 +1,96 @@
+use address::SyncAddress;
+use arbiter::Arbiter;
+
+/// Pool of arbiters
+///
+/// Pool starts several arbiters and distributes work between them.
+/// `next()` method selects arbiters in round-robin fashion. Pool created with
+/// `ArbiterPool::with_weights()` uses smooth weighted round-robin, so arbiter
+/// with weight `2` get selected twice as often as arbiter with weight `1`.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate actix;
+///
+/// use actix::prelude::*;
+///
+/// fn main() {
+///     let sys = System::new("test");
+///
+///     let mut pool = ArbiterPool::with_weights(vec![3, 1]);
+///     pool.next().send(msgs::Execute::new(|| -> Result<(), ()> {
+///         Arbiter::system().send(msgs::SystemExit(0));
+///         Ok(())
+///     }));
+///
+///     sys.run();
+/// }
+/// ```
+pub struct ArbiterPool {
+    arbiters: Vec<SyncAddress<Arbiter>>,
+    weights: Vec<i64>,
+    current: Vec<i64>,
+    total: i64,
+}
+
+impl ArbiterPool {
+
+    /// Start pool of `size` arbiters with equal weights
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is `0`.
+    pub fn new(size: usize) -> ArbiterPool {
+        ArbiterPool::with_weights(vec![1; size])
+    }
+
+    /// Start pool with arbiter per weight. Each arbiter get selected
+    /// proportionally to its weight.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `weights` is empty or all weights are `0`.
+    pub fn with_weights(weights: Vec<u32>) -> ArbiterPool {
+        let total: i64 = weights.iter().map(|w| i64::from(*w)).sum();
+        if total == 0 {
+            panic!("ArbiterPool requires at least one arbiter with non zero weight");
+        }
+
+        ArbiterPool {
+            arbiters: weights.iter().map(|_| Arbiter::new(None)).collect(),
+            current: vec![0; weights.len()],
+            weights: weights.into_iter().map(i64::from).collect(),
+            total: total,
+        }
+    }
+
+    /// Number of arbiters in the pool
+    pub fn len(&self) -> usize {
+        self.arbiters.len()
+    }
+
+    /// Returns `true` if pool does not contain any arbiter
+    pub fn is_empty(&self) -> bool {
+        self.arbiters.is_empty()
+    }
+
+    #[cfg_attr(feature="cargo-clippy", allow(should_implement_trait))]
+    /// Select next arbiter
+    pub fn next(&mut self) -> &SyncAddress<Arbiter> {
+        let mut idx = 0;
+        for i in 0..self.current.len() {
+            self.current[i] += self.weights[i];
+            if self.current[i] > self.current[idx] {
+                idx = i;
+            }
+        }
+        self.current[idx] -= self.total;
+        &self.arbiters[idx]
+    }
+
+    /// Addresses of all arbiters in the pool
+    pub fn arbiters(&self) -> &[SyncAddress<Arbiter>] {
+        &self.arbiters
+    }
+}
@@ -12,14 +12,19 @@ pub use msgs;
 
 pub use fut::{self, ActorFuture, ActorStream, WrapFuture, WrapStream};
 
-pub use actor::{Actor, ActorState, FramedActor, Supervised,
+pub use actor::{Actor, ActorPriority, ActorState, FramedActor, Supervised, Snapshotable,
                 Handler, ResponseType, StreamHandler,
-                ActorContext, AsyncContext, SpawnHandle};
+                ActorContext, AsyncContext, SpawnHandle, StreamHandle, WatchHandle};
 pub use arbiter::Arbiter;
-pub use address::{Address, SyncAddress, Subscriber};
+pub use address::{Address, SyncAddress, Subscriber, Recipient};
+pub use behavior::{Behavior, BehaviorHandler, Handlers};
+pub use channel::{actor_channel, ChannelSender, ChannelReceiver};
+pub use clock::TestClock;
 pub use context::{Context, ContextFutureSpawner};
+pub use dynamic::DynamicMessage;
 pub use framed::FramedContext;
 pub use message::{Request, Response};
+pub use pool::ArbiterPool;
 pub use system::System;
 pub use supervisor::Supervisor;
 pub use sync::{SyncContext, SyncArbiter};
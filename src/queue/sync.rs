@@ -677,6 +677,16 @@ impl<T> UnboundedSender<T> {
     pub fn unbounded_send(&self, msg: T) -> Result<(), SendError<T>> {
         self.0.do_send_nb(msg)
     }
+
+    /// Number of messages in the channel
+    pub fn len(&self) -> usize {
+        decode_state(self.0.inner.state.load(SeqCst)).num_messages
+    }
+
+    /// Check if receiver is closed or dropped
+    pub fn is_closed(&self) -> bool {
+        !decode_state(self.0.inner.state.load(SeqCst)).is_open
+    }
 }
 
 impl<T> Sink for UnboundedSender<T> {
@@ -29,8 +29,12 @@ pub fn channel<T>(buffer: usize) -> Receiver<T> {
 }
 
 fn channel_<T>(buffer: Option<usize>) -> Receiver<T> {
+    channel_with_capacity(buffer, 0)
+}
+
+fn channel_with_capacity<T>(buffer: Option<usize>, prealloc: usize) -> Receiver<T> {
     let shared = Rc::new(RefCell::new(Shared {
-        buffer: VecDeque::new(),
+        buffer: VecDeque::with_capacity(prealloc),
         capacity: buffer,
         blocked_senders: VecDeque::new(),
         blocked_recv: None,
@@ -312,6 +316,14 @@ impl<T> UnboundedSender<T> {
         self.0.connected()
     }
 
+    /// Number of messages in the channel
+    pub fn len(&self) -> usize {
+        match self.0.shared.upgrade() {
+            Some(shared) => shared.borrow().buffer.len(),
+            None => 0,
+        }
+    }
+
     /// Sends the provided message along this channel.
     ///
     /// This is an unbounded sender, so this function differs from `Sink::send`
@@ -330,6 +342,22 @@ impl<T> UnboundedSender<T> {
         }
         Ok(())
     }
+
+    /// Same as `unbounded_send` but message is placed in front of
+    /// all queued messages.
+    pub fn unbounded_send_front(&self, msg: T) -> Result<(), SendError<T>> {
+        let shared = match self.0.shared.upgrade() {
+            Some(shared) => shared,
+            None => return Err(SendError(msg)),
+        };
+        let mut shared = shared.borrow_mut();
+        shared.buffer.push_front(msg);
+        if let Some(task) = shared.blocked_recv.take() {
+            drop(shared);
+            task.notify();
+        }
+        Ok(())
+    }
 }
 
 /// The receiving end of an unbounded channel.
@@ -379,6 +407,11 @@ pub fn unbounded<T>() -> UnboundedReceiver<T> {
     UnboundedReceiver(channel_(None))
 }
 
+/// Creates an unbounded channel with storage preallocated for `capacity` messages.
+pub fn unbounded_with_capacity<T>(capacity: usize) -> UnboundedReceiver<T> {
+    UnboundedReceiver(channel_with_capacity(None, capacity))
+}
+
 /// Error type for sending, used when the receiving end of a channel is
 /// dropped
 pub struct SendError<T>(T);
@@ -1,14 +1,20 @@
-use std::any::{Any, TypeId};
+use std::any::{type_name, Any, TypeId};
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::default::Default;
 use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use futures::sync::oneshot::{channel, Receiver, Sender};
 
-use actor::{Actor, Supervised};
+use actor::{actor_name, Actor, ActorContext, Supervised};
+use actors::events::{LifecycleEvent, SystemEvents};
 use arbiter::Arbiter;
 use address::{Address, SyncAddress};
 use context::Context;
+use envelope::{Envelope, EnvelopeProxy};
 use supervisor::Supervisor;
+use system::{Shutdown, StartHook};
+use sync::BlockingWorker;
 
 /// Actors registry
 ///
@@ -131,17 +137,40 @@ impl Registry {
 pub struct SystemRegistry {
     #[cfg_attr(feature="cargo-clippy", allow(type_complexity))]
     registry: Arc<Mutex<RefCell<HashMap<TypeId, Box<Any>>>>>,
+    /// Named arbiters, maintained by `System` actor
+    pub(crate) arbiters: Arc<Mutex<HashMap<String, SyncAddress<Arbiter>>>>,
+    /// Live actors of the system
+    pub(crate) actors: Arc<ActorsCounter>,
+    /// Address of the running `SystemEvents` service
+    pub(crate) events: Arc<Mutex<Option<SyncAddress<SystemEvents>>>>,
+    /// Arbiters shared with other system, see `System::new_with_arbiters()`
+    pub(crate) pool: Arc<Mutex<Vec<SyncAddress<Arbiter>>>>,
+    /// Shutdown hooks, see `System::add_shutdown_hook()`
+    pub(crate) shutdown: Arc<Mutex<Shutdown>>,
+    /// Startup hooks, `None` once system is running, see `System::on_start()`
+    pub(crate) startup: Arc<Mutex<Option<Vec<StartHook>>>>,
 }
 
 unsafe impl Send for SystemRegistry {}
 
 impl SystemRegistry {
     pub(crate) fn new() -> Self {
-        SystemRegistry{registry: Arc::new(Mutex::new(RefCell::new(HashMap::new())))}
+        SystemRegistry{registry: Arc::new(Mutex::new(RefCell::new(HashMap::new()))),
+                       arbiters: Arc::new(Mutex::new(HashMap::new())),
+                       actors: Arc::new(ActorsCounter::default()),
+                       events: Arc::new(Mutex::new(None)),
+                       pool: Arc::new(Mutex::new(Vec::new())),
+                       shutdown: Arc::new(Mutex::new(Shutdown::default())),
+                       startup: Arc::new(Mutex::new(Some(Vec::new())))}
     }
 
     /// Return addres of the service. If service actor is not running
     /// it get started in system arbiter.
+    ///
+    /// Service get started asynchronously, so `SystemService::service_started`
+    /// method could be not called yet at the moment when address is returned.
+    /// Messages sent to the service get processed after `service_started` call.
+    /// Use `get_started` method to wait for service initialization.
     pub fn get<A: SystemService + Actor<Context=Context<A>>>(&self) -> SyncAddress<A> {
         if let Ok(hm) = self.registry.lock() {
             if let Some(addr) = hm.borrow().get(&TypeId::of::<A>()) {
@@ -151,21 +180,176 @@ impl SystemRegistry {
                     None =>
                         error!("Got unknown value: {:?}", addr),
                 }
-            } else {
-                let addr = Supervisor::start_in(Arbiter::system_arbiter(), false, |ctx| {
-                    let mut act = A::default();
-                    act.service_started(ctx);
-                    act
-                });
-                return addr.expect("System is dead");
             }
+            let addr = Supervisor::start_in(Arbiter::system_arbiter(), false, |ctx| {
+                let mut act = A::default();
+                act.service_started(ctx);
+                act
+            }).expect("System is dead");
+
+            hm.borrow_mut().insert(TypeId::of::<A>(), Box::new(addr.clone()));
+            return addr
+        }
+        panic!("System registry lock is poisoned");
+    }
+
+    /// Check if service with type id `id` is started
+    pub(crate) fn contains(&self, id: TypeId) -> bool {
+        match self.registry.lock() {
+            Ok(hm) => hm.borrow().contains_key(&id),
+            Err(_) => false,
+        }
+    }
+
+    /// Return address of the thread pool for blocking operations.
+    /// Pool get started on first call, messages are dispatched from system arbiter.
+    pub(crate) fn blocking_pool(&self) -> SyncAddress<BlockingWorker> {
+        if let Ok(hm) = self.registry.lock() {
+            if let Some(addr) = hm.borrow().get(&TypeId::of::<BlockingWorker>()) {
+                if let Some(addr) = addr.downcast_ref::<SyncAddress<BlockingWorker>>() {
+                    return addr.clone()
+                }
+            }
+            let addr = BlockingWorker::start_pool(&Arbiter::system_arbiter());
+
+            hm.borrow_mut().insert(TypeId::of::<BlockingWorker>(), Box::new(addr.clone()));
+            return addr
         }
         panic!("System registry lock is poisoned");
     }
+
+    /// Return next arbiter of the shared pool, arbiters are used in turn.
+    pub(crate) fn pool_arbiter(&self) -> Option<SyncAddress<Arbiter>> {
+        match self.pool.lock() {
+            Ok(mut pool) => if pool.is_empty() {
+                None
+            } else {
+                pool.rotate_left(1);
+                pool.last().cloned()
+            },
+            Err(_) => None,
+        }
+    }
+
+    /// Return future that resolves to the address of the service
+    /// after `SystemService::service_started` method get called.
+    /// If service actor is not running it get started in system arbiter.
+    pub fn get_started<A>(&self) -> Receiver<SyncAddress<A>>
+        where A: SystemService + Actor<Context=Context<A>>
+    {
+        let (tx, rx) = channel();
+        self.get::<A>().send_envelope(
+            Envelope::new(ServiceStartedEnvelope{tx: Some(tx)}));
+        rx
+    }
+}
+
+/// Envelope get handled only after service initialization
+struct ServiceStartedEnvelope<A: Actor> {
+    tx: Option<Sender<SyncAddress<A>>>,
+}
+
+impl<A> EnvelopeProxy for ServiceStartedEnvelope<A>
+    where A: SystemService + Actor<Context=Context<A>>
+{
+    type Actor = A;
+
+    fn handle(&mut self, _: &mut A, ctx: &mut Context<A>) {
+        if let Some(tx) = self.tx.take() {
+            let _ = tx.send(ctx.address());
+        }
+    }
+
+    fn message_type(&self) -> &'static str {
+        "ServiceStarted"
+    }
 }
 
 impl Clone for SystemRegistry {
     fn clone(&self) -> Self {
-        SystemRegistry{registry: Arc::clone(&self.registry)}
+        SystemRegistry{registry: Arc::clone(&self.registry),
+                       arbiters: Arc::clone(&self.arbiters),
+                       actors: Arc::clone(&self.actors),
+                       events: Arc::clone(&self.events),
+                       pool: Arc::clone(&self.pool),
+                       shutdown: Arc::clone(&self.shutdown),
+                       startup: Arc::clone(&self.startup)}
+    }
+}
+
+/// Number of live actors and optional limit, shared by all arbiters
+#[derive(Default)]
+pub(crate) struct ActorsCounter {
+    count: AtomicUsize,
+    /// `0` means no limit
+    limit: AtomicUsize,
+}
+
+impl ActorsCounter {
+    pub fn count(&self) -> usize {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    pub fn set_limit(&self, limit: Option<usize>) {
+        self.limit.store(limit.unwrap_or(0), Ordering::Relaxed)
+    }
+
+    /// Check if one more actor would exceed the limit
+    pub fn exhausted(&self) -> bool {
+        let limit = self.limit.load(Ordering::Relaxed);
+        limit != 0 && self.count() >= limit
+    }
+}
+
+/// Keeps actor counted as live until dropped,
+/// reports actor start and stop to `SystemEvents` service.
+pub(crate) struct ActorCountGuard {
+    counter: Option<Arc<ActorsCounter>>,
+    events: Option<(&'static str, SyncAddress<SystemEvents>)>,
+    lifecycle: bool,
+}
+
+impl ActorCountGuard {
+    /// Count new actor in the current system,
+    /// actors started outside of a system are not counted
+    pub fn new<A: Actor>() -> ActorCountGuard {
+        let counter = Arbiter::actors_counter();
+        if let Some(ref counter) = counter {
+            counter.count.fetch_add(1, Ordering::Relaxed);
+        }
+        Arbiter::actor_lifecycle(true);
+
+        // events service does not report itself
+        let name = actor_name::<A>();
+        let events = if type_name::<A>() == type_name::<SystemEvents>() {
+            None
+        } else {
+            Arbiter::lifecycle_events()
+        };
+        if let Some(ref events) = events {
+            events.send(LifecycleEvent::ActorStarted(name));
+        }
+        ActorCountGuard{counter: counter, events: events.map(|events| (name, events)),
+                        lifecycle: true}
+    }
+
+    /// Guard of internal actor, internal actors are not counted
+    /// and not reported
+    pub fn internal() -> ActorCountGuard {
+        ActorCountGuard{counter: None, events: None, lifecycle: false}
+    }
+}
+
+impl Drop for ActorCountGuard {
+    fn drop(&mut self) {
+        if let Some(ref counter) = self.counter {
+            counter.count.fetch_sub(1, Ordering::Relaxed);
+        }
+        if self.lifecycle {
+            Arbiter::actor_lifecycle(false);
+        }
+        if let Some((name, ref events)) = self.events {
+            events.send(LifecycleEvent::ActorStopped(name));
+        }
     }
 }
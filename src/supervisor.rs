@@ -1,7 +1,8 @@
-use std;
 use futures::{Future, Async, Poll, Stream};
+use uuid::Uuid;
 
-use actor::{Actor, Supervised, AsyncContext};
+use actor::{actor_name, Actor, Supervised, AsyncContext};
+use actors::events::{self, LifecycleEvent};
 use arbiter::Arbiter;
 use address::{Address, SyncAddress};
 use context::{Context, ContextProtocol, AsyncContextApi};
@@ -76,6 +77,7 @@ pub struct Supervisor<A: Supervised> where A: Actor<Context=Context<A>> {
     msg: Option<ContextProtocol<A>>,
     sync_msg: Option<Envelope<A>>,
     sync_alive: bool,
+    id: Uuid,
 }
 
 struct ActorCell<A: Supervised> {
@@ -92,12 +94,13 @@ impl<A> Supervisor<A> where A: Supervised + Actor<Context=Context<A>>
               F: FnOnce(&mut A::Context) -> A + 'static
     {
         // create actor
+        let id = Uuid::new_v4();
         let (cell, factory) = if !lazy {
-            let mut ctx = Context::new(unsafe{std::mem::uninitialized()});
+            let mut ctx = Context::empty();
+            ctx.address_cell().set_id(id);
             let addr = ctx.address_cell().unsync_sender();
             let act = f(&mut ctx);
-            let old = ctx.replace_actor(act);
-            std::mem::forget(old);
+            ctx.set_actor(act);
             (Some(ActorCell{ctx: ctx, addr: addr}), None)
         } else {
             let f: Box<FnFactory<A>> = Box::new(f);
@@ -115,9 +118,10 @@ impl<A> Supervisor<A> where A: Supervised + Actor<Context=Context<A>>
             msg: None,
             sync_msg: None,
             sync_alive: true,
+            id: id,
         };
-        let addr = Address::new(supervisor.msgs.sender());
-        let saddr = SyncAddress::new(stx);
+        let addr = Address::new(supervisor.msgs.sender(), id);
+        let saddr = SyncAddress::new(stx, id);
         Arbiter::handle().spawn(supervisor);
 
         (addr, saddr)
@@ -131,15 +135,16 @@ impl<A> Supervisor<A> where A: Supervised + Actor<Context=Context<A>>
     {
         if addr.connected() {
             let (tx, rx) = sync::unbounded();
+            let id = Uuid::new_v4();
 
             addr.send(Execute::new(move || -> Result<(), ()> {
                 // create actor
                 let (cell, factory) = if lazy {
-                    let mut ctx = Context::new(unsafe{std::mem::uninitialized()});
+                    let mut ctx = Context::empty();
+                    ctx.address_cell().set_id(id);
                     let addr = ctx.address_cell().unsync_sender();
                     let act = f(&mut ctx);
-                    let old = ctx.replace_actor(act);
-                    std::mem::forget(old);
+                    ctx.set_actor(act);
                     (Some(ActorCell{ctx: ctx, addr: addr}), None)
                 } else {
                     let f: Box<FnFactory<A>> = Box::new(f);
@@ -155,13 +160,14 @@ impl<A> Supervisor<A> where A: Supervised + Actor<Context=Context<A>>
                     msg: None,
                     sync_msg: None,
                     sync_alive: true,
+                    id: id,
                 };
                 Arbiter::handle().spawn(supervisor);
                 Ok(())
             }));
 
             if addr.connected() {
-                Some(SyncAddress::new(tx))
+                Some(SyncAddress::new(tx, id))
             } else {
                 None
             }
@@ -173,12 +179,12 @@ impl<A> Supervisor<A> where A: Supervised + Actor<Context=Context<A>>
     fn get_cell(&mut self) -> &mut ActorCell<A> {
         if self.cell.is_none() {
             let f = self.factory.take().expect("Should be available");
-            let mut ctx = Context::new(unsafe{std::mem::uninitialized()});
+            let mut ctx = Context::empty();
+            ctx.address_cell().set_id(self.id);
 
             let addr = ctx.address_cell().unsync_sender();
             let act = f.call(&mut ctx);
-            let old = ctx.replace_actor(act);
-            std::mem::forget(old);
+            ctx.set_actor(act);
 
             self.cell = Some(ActorCell {ctx: ctx, addr: addr});
         }
@@ -187,12 +193,14 @@ impl<A> Supervisor<A> where A: Supervised + Actor<Context=Context<A>>
 
     fn restart(&mut self) {
         let cell = self.cell.take().unwrap();
-        let mut ctx = Context::new(unsafe{std::mem::uninitialized()});
+        // restarted actor is still the same live actor
+        let (act, guard) = cell.ctx.into_parts();
+        let mut ctx = Context::with_guard(act, guard);
+        ctx.address_cell().set_id(self.id);
 
         let addr = ctx.address_cell().unsync_sender();
-        let old = ctx.replace_actor(cell.ctx.into_inner());
-        std::mem::forget(old);
         ctx.restarting();
+        events::emit(LifecycleEvent::ActorRestarted(actor_name::<A>()));
 
         self.cell = Some(ActorCell {ctx: ctx, addr: addr});
     }
@@ -237,7 +245,15 @@ impl<A> Future for Supervisor<A> where A: Supervised + Actor<Context=Context<A>>
                         match msg {
                             ContextProtocol::Upgrade(tx) => {
                                 self.sync_alive = true;
-                                let _ = tx.send(SyncAddress::new(self.sync_msgs.sender()));
+                                let _ = tx.send(SyncAddress::new(self.sync_msgs.sender(), self.id));
+                            }
+                            // actor replacement skips queued messages
+                            ContextProtocol::TakeOver(act) => {
+                                if let Err(msg) = self.get_cell().addr.unbounded_send_front(
+                                    ContextProtocol::TakeOver(act))
+                                {
+                                    self.msg = Some(msg.into_inner());
+                                }
                             }
                             // if Actor message queue is dead, store in temp
                             msg => {
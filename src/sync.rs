@@ -70,21 +70,25 @@
 //! }
 //! ```
 use std;
-use std::thread;
+use std::any::type_name;
+use std::{panic, thread};
 use std::sync::Arc;
 
 use crossbeam::sync::MsQueue;
 use futures::{Async, Future, Poll, Stream};
 use futures::sync::oneshot::Sender as SyncSender;
 use tokio_core::reactor::Core;
+use uuid::Uuid;
 
 use actor::{Actor, ActorContext, ActorState, Handler, ResponseType};
 use arbiter::Arbiter;
 use address::SyncAddress;
 use context::Context;
-use envelope::{Envelope, EnvelopeProxy, ToEnvelope};
+use envelope::{self, Envelope, EnvelopeProxy, ToEnvelope};
 use message::Response;
+use msgs::Execute;
 use queue::sync;
+use registry::ActorCountGuard;
 
 /// Sync arbiter
 pub struct SyncArbiter<A> where A: Actor<Context=SyncContext<A>> {
@@ -99,24 +103,53 @@ impl<A> SyncArbiter<A> where A: Actor<Context=SyncContext<A>> + Send {
     /// Returns address of started actor.
     pub fn start<F>(threads: usize, f: F) -> SyncAddress<A>
         where F: Fn() -> A + 'static
+    {
+        let queue = SyncArbiter::start_threads(threads, ActorCountGuard::new::<A>, f);
+
+        let (tx, rx) = sync::unbounded();
+        Arbiter::handle().spawn(
+            SyncArbiter{queue: queue, msgs: rx, threads: threads});
+
+        SyncAddress::new(tx, Uuid::new_v4())
+    }
+
+    /// Start new sync arbiter for internal use, messages get dispatched from
+    /// the `arb` arbiter. Worker threads are not counted as system's actors
+    /// and do not emit lifecycle events.
+    pub(crate) fn start_internal<F>(arb: &SyncAddress<Arbiter>, threads: usize, f: F)
+                                    -> SyncAddress<A>
+        where F: Fn() -> A + 'static
+    {
+        let queue = SyncArbiter::start_threads(threads, ActorCountGuard::internal, f);
+
+        let (tx, rx) = sync::unbounded();
+        arb.send(Execute::new(move || -> Result<(), ()> {
+            Arbiter::handle().spawn(
+                SyncArbiter{queue: queue, msgs: rx, threads: threads});
+            Ok(())
+        }));
+
+        SyncAddress::new(tx, Uuid::new_v4())
+    }
+
+    fn start_threads<F>(threads: usize, guard: fn() -> ActorCountGuard, f: F)
+                        -> Arc<MsQueue<SyncContextProtocol<A>>>
+        where F: Fn() -> A + 'static
     {
         let queue = Arc::new(MsQueue::new());
 
         for _ in 0..threads {
             let actor = f();
             let actor_queue = Arc::clone(&queue);
+            // worker threads have no system, so actor gets counted here
+            let count = guard();
 
             thread::spawn(move || {
-                SyncContext::new(actor, actor_queue)
+                SyncContext::new(actor, actor_queue, count)
                     .run()
             });
         }
-
-        let (tx, rx) = sync::unbounded();
-        Arbiter::handle().spawn(
-            SyncArbiter{queue: queue, msgs: rx, threads: threads});
-
-        SyncAddress::new(tx)
+        queue
     }
 }
 
@@ -137,19 +170,24 @@ impl<A> Future for SyncArbiter<A> where A: Actor<Context=SyncContext<A>>
                     self.queue.push(SyncContextProtocol::Envelope(msg));
                 }
                 Ok(Async::NotReady) => break,
-                Ok(Async::Ready(None)) | Err(_) => {
-                    // stop sync arbiters
-                    for _ in 0..self.threads {
-                        self.queue.push(SyncContextProtocol::Stop);
-                    }
-                    return Ok(Async::Ready(()))
-                },
+                // worker threads get stopped on drop
+                Ok(Async::Ready(None)) | Err(_) => return Ok(Async::Ready(())),
             }
         }
         Ok(Async::NotReady)
     }
 }
 
+impl<A> Drop for SyncArbiter<A> where A: Actor<Context=SyncContext<A>> {
+    /// Stop worker threads, this also happens if arbiter that dispatches
+    /// messages stops, otherwise worker threads would wait forever.
+    fn drop(&mut self) {
+        for _ in 0..self.threads {
+            self.queue.push(SyncContextProtocol::Stop);
+        }
+    }
+}
+
 impl<A, M> ToEnvelope<A, SyncContext<A>, M> for A
     where A: Actor<Context=SyncContext<A>> + Handler<M>,
           M: Send + 'static,
@@ -158,7 +196,8 @@ impl<A, M> ToEnvelope<A, SyncContext<A>, M> for A
 {
     fn pack(msg: M, tx: Option<SyncSender<Result<A::Item, A::Error>>>) -> Envelope<A>
     {
-        Envelope::new(SyncEnvelope::new(msg, tx))
+        let request = tx.is_some();
+        Envelope::with_request(SyncEnvelope::new(msg, tx), request)
     }
 }
 
@@ -174,17 +213,19 @@ pub struct SyncContext<A> where A: Actor<Context=SyncContext<A>> {
     queue: Arc<MsQueue<SyncContextProtocol<A>>>,
     stopping: bool,
     state: ActorState,
+    _count: ActorCountGuard,
 }
 
 impl<A> SyncContext<A> where A: Actor<Context=Self> {
     /// Create new SyncContext
-    fn new(act: A, queue: Arc<MsQueue<SyncContextProtocol<A>>>) -> Self {
+    fn new(act: A, queue: Arc<MsQueue<SyncContextProtocol<A>>>, count: ActorCountGuard) -> Self {
         SyncContext {
             act: act,
             core: None,
             queue: queue,
             stopping: false,
             state: ActorState::Started,
+            _count: count,
         }
     }
 
@@ -207,7 +248,7 @@ impl<A> SyncContext<A> where A: Actor<Context=Self> {
                     return
                 },
                 SyncContextProtocol::Envelope(mut env) => {
-                    env.handle(&mut self.act, ctx)
+                    env.handle(&mut self.act, ctx);
                 },
             }
 
@@ -265,7 +306,11 @@ impl<A, M> EnvelopeProxy for SyncEnvelope<A, M>
     fn handle(&mut self, act: &mut Self::Actor, ctx: &mut <Self::Actor as Actor>::Context)
     {
         if let Some(msg) = self.msg.take() {
-            let mut response = <Self::Actor as Handler<M>>::handle(act, msg, ctx);
+            let mut response = envelope::handle_message(act, msg, ctx);
+            if response.is_canceled() {
+                // requester gets `Canceled` error
+                return
+            }
 
             let result = if response.is_async() {
                 response.result().unwrap()
@@ -287,6 +332,10 @@ impl<A, M> EnvelopeProxy for SyncEnvelope<A, M>
             }
         }
     }
+
+    fn message_type(&self) -> &'static str {
+        type_name::<M>()
+    }
 }
 
 
@@ -311,3 +360,64 @@ impl<A, M> Future for ResponseFuture<A, M>
         self.fut.poll(act, ctx)
     }
 }
+
+/// Number of threads in the thread pool for blocking operations
+const BLOCKING_THREADS: usize = 4;
+
+/// Error of the function sent to the thread pool with `Context::spawn_blocking()`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BlockingError {
+    /// Function panicked or system stopped before function get executed
+    Canceled,
+}
+
+impl std::error::Error for BlockingError {
+    fn description(&self) -> &str {
+        match *self {
+            BlockingError::Canceled => "blocking operation canceled",
+        }
+    }
+}
+
+impl std::fmt::Display for BlockingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            BlockingError::Canceled => write!(f, "blocking operation canceled"),
+        }
+    }
+}
+
+/// Worker of the thread pool for blocking operations,
+/// see `Arbiter::spawn_blocking()`
+pub(crate) struct BlockingWorker;
+
+impl BlockingWorker {
+    pub(crate) fn start_pool(arb: &SyncAddress<Arbiter>) -> SyncAddress<BlockingWorker> {
+        SyncArbiter::start_internal(arb, BLOCKING_THREADS, || BlockingWorker)
+    }
+}
+
+impl Actor for BlockingWorker {
+    type Context = SyncContext<Self>;
+}
+
+impl<I: Send, E: Send> ResponseType<Execute<I, E>> for BlockingWorker {
+    type Item = I;
+    type Error = E;
+}
+
+impl<I: Send, E: Send> Handler<Execute<I, E>> for BlockingWorker {
+
+    fn handle(&mut self, msg: Execute<I, E>, _: &mut SyncContext<Self>)
+              -> Response<Self, Execute<I, E>>
+    {
+        // panic must not kill worker thread, pool has fixed number of threads
+        match panic::catch_unwind(panic::AssertUnwindSafe(|| msg.exec())) {
+            Ok(res) => Self::reply_result(res),
+            Err(_) => {
+                error!("Blocking operation panicked");
+                Response::canceled()
+            }
+        }
+    }
+}
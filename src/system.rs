@@ -1,13 +1,26 @@
+use std;
+use std::{error, fmt, panic};
 use std::string::ToString;
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tokio_core::reactor::{Core, Handle};
+use futures::future::{self, Future, IntoFuture};
 use futures::sync::oneshot::{channel, Receiver, Sender};
 
 use actor::{Actor, Handler, ResponseType};
+use actors::events::{self, LifecycleEvent};
 use address::SyncAddress;
-use arbiter::Arbiter;
-use context::Context;
-use msgs::{SystemExit, StopArbiter};
+use clock::Delay;
+use registry::SystemRegistry;
+use arbiter::{Arbiter, ArbiterGuard, ArbiterMetrics, FreezeArbiter, GetArbiterMetrics,
+              ThawArbiter};
+use context::{AsyncContextApi, Context, ContextFutureSpawner};
+use fut::{self, ActorFuture, WrapFuture};
+use msgs::{ActorCount, DeferSystemExit, FreezeSystem, IsServiceRunning, StartActor,
+           SystemExit, SystemMetrics, StopArbiter, SystemArbiterUpdated, ThawSystem};
 use message::Response;
 
 /// System is an actor which manages process.
@@ -53,10 +66,23 @@ use message::Response;
 pub struct System {
     stop: Option<Sender<i32>>,
     arbiters: HashMap<String, SyncAddress<Arbiter>>,
+    names: HashMap<String, String>,
+    named: Arc<Mutex<HashMap<String, SyncAddress<Arbiter>>>>,
+    deferred: usize,
+    exit_code: Option<i32>,
+    frozen: bool,
+    shutting_down: bool,
+    /// Exit code, while system waits for arbiters to stop
+    stopping: Option<i32>,
 }
 
 impl Actor for System {
     type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Context<Self>) {
+        // system has to handle control messages while frozen
+        ctx.address_cell().set_control();
+    }
 }
 
 impl System {
@@ -64,25 +90,303 @@ impl System {
     #[cfg_attr(feature="cargo-clippy", allow(new_ret_no_self))]
     /// Create new system
     pub fn new<T: ToString>(name: T) -> SystemRunner {
-        let core = Arbiter::new_system(name.to_string());
+        System::new_with_arbiters(name, Vec::new())
+    }
+
+    #[cfg_attr(feature="cargo-clippy", allow(new_ret_no_self))]
+    /// Create new system that uses arbiters of other system.
+    ///
+    /// Actors get started in `arbiters` with `System::spawn_actor()`, so
+    /// several systems can share fixed set of worker threads. Each system
+    /// keeps its own registry and named arbiters. Shared arbiters stay
+    /// registered in the system that created them: that system stops them,
+    /// and actors running in them see that system's `Arbiter::system()` and
+    /// `Arbiter::system_registry()`.
+    pub fn new_with_arbiters<T: ToString>(name: T, arbiters: Vec<SyncAddress<Arbiter>>)
+                                          -> SystemRunner
+    {
+        System::create(name, Core::new().unwrap(), None, arbiters)
+    }
+
+    #[cfg_attr(feature="cargo-clippy", allow(new_ret_no_self))]
+    /// Create new system on top of existing event loop.
+    ///
+    /// System arbiter runs on `core`, so other futures could be spawned
+    /// on the same reactor, `SystemRunner::run()` drives `core` until system
+    /// exits. If `registry` is provided, system uses it instead of creating
+    /// new `SystemRegistry`, i.e. registry of other system, so system services,
+    /// named arbiters and shutdown hooks are shared between both systems.
+    /// Arbiter's `Registry` is always created by system arbiter.
+    pub fn new_with_core<T: ToString>(name: T, core: Core, registry: Option<SystemRegistry>)
+                                      -> SystemRunner
+    {
+        System::create(name, core, registry, Vec::new())
+    }
+
+    fn create<T: ToString>(name: T, core: Core, registry: Option<SystemRegistry>,
+                           arbiters: Vec<SyncAddress<Arbiter>>) -> SystemRunner
+    {
+        let core = Arbiter::new_system(name.to_string(), core, registry);
         let (stop_tx, stop_rx) = channel();
 
         // start system
         let sys = System {
-            arbiters: HashMap::new(), stop: Some(stop_tx)}.start();
+            arbiters: HashMap::new(),
+            names: HashMap::new(),
+            named: Arc::clone(&Arbiter::system_registry().arbiters),
+            deferred: 0,
+            exit_code: None,
+            frozen: false,
+            shutting_down: false,
+            stopping: None,
+            stop: Some(stop_tx)}.start();
         Arbiter::set_system(sys, name.to_string());
+        if let Ok(mut pool) = Arbiter::system_registry().pool.lock() {
+            *pool = arbiters;
+        }
 
         SystemRunner {
             core: core,
             stop: stop_rx,
         }
     }
+
+    /// Returns address of the arbiter started with `Arbiter::new(Some(name))`.
+    ///
+    /// Arbiter get registered asynchronously after start, so it could be
+    /// not available immediately after `Arbiter::new()` call.
+    /// If several arbiters use same name, last registered arbiter is returned.
+    pub fn arbiter_for_name(name: &str) -> Option<SyncAddress<Arbiter>> {
+        match Arbiter::system_registry().arbiters.lock() {
+            Ok(arbiters) => arbiters.get(name).cloned(),
+            Err(_) => None,
+        }
+    }
+
+    /// Start actor in the arbiter registered with `name`.
+    ///
+    /// Arbiter is looked up with `System::arbiter_for_name()`, actor is moved
+    /// to arbiter's thread and started there with `StartActor` message.
+    /// Returned future resolves to the address of the started actor.
+    pub fn spawn_actor_in<A>(name: &str, actor: A)
+                             -> impl Future<Item=SyncAddress<A>, Error=SpawnError>
+        where A: Actor<Context=Context<A>> + Send
+    {
+        future::result(System::arbiter_for_name(name)
+                       .ok_or_else(|| SpawnError::UnknownArbiter(name.to_owned())))
+            .and_then(|arb| {
+                arb.call_fut(StartActor::new(|_| actor))
+                    .map_err(|_| SpawnError::ArbiterStopped)
+            })
+            .and_then(|res| res.map_err(|_| {
+                if Arbiter::system_registry().actors.exhausted() {
+                    SpawnError::ActorLimit
+                } else {
+                    SpawnError::ArbiterStopped
+                }
+            }))
+    }
+
+    /// Start actor in one of the arbiters passed to `System::new_with_arbiters()`.
+    ///
+    /// Arbiters are used in turn. Returned future resolves to the address
+    /// of the started actor.
+    pub fn spawn_actor<A>(actor: A) -> impl Future<Item=SyncAddress<A>, Error=SpawnError>
+        where A: Actor<Context=Context<A>> + Send
+    {
+        future::result(Arbiter::system_registry().pool_arbiter().ok_or(SpawnError::NoArbiters))
+            .and_then(|arb| {
+                arb.call_fut(StartActor::new(|_| actor))
+                    .map_err(|_| SpawnError::ArbiterStopped)
+            })
+            .and_then(|res| res.map_err(|_| SpawnError::ArbiterStopped))
+    }
+
+    /// Limit number of live actors in the system.
+    ///
+    /// Once limit is reached, arbiters reject `StartActor` messages until
+    /// some actors stop. Actors started directly with `Actor::start()` are
+    /// still counted but never rejected. `None` removes limit.
+    pub fn set_actor_limit(limit: Option<usize>) {
+        Arbiter::system_registry().actors.set_limit(limit)
+    }
+
+    /// Register hook that runs when system receives `SystemExit` message.
+    ///
+    /// Hook is called in system arbiter with exit code, system stops arbiters
+    /// after futures returned by all hooks resolve, or after shutdown timeout,
+    /// see `System::set_shutdown_timeout()`. Hooks run concurrently.
+    pub fn add_shutdown_hook<F, R>(f: F)
+        where F: FnOnce(i32) -> R + Send + 'static,
+              R: IntoFuture<Item=(), Error=()> + 'static,
+    {
+        if let Ok(mut shutdown) = Arbiter::system_registry().shutdown.lock() {
+            shutdown.hooks.push(Box::new(move |code| Box::new(f(code).into_future())));
+        }
+    }
+
+    /// Register named hook that runs after system stops.
+    ///
+    /// Hooks are called in system arbiter after all arbiters stopped, or
+    /// after shutdown timeout, but before `SystemRunner::run()` returns.
+    /// Use it for cleanup, i.e. flushing caches or closing connections.
+    /// Hooks are called in registration order, hook registered with the name
+    /// of existing hook replaces it.
+    pub fn on_shutdown<F>(name: &str, f: F) where F: FnOnce() + Send + 'static {
+        if let Ok(mut shutdown) = Arbiter::system_registry().shutdown.lock() {
+            if let Some(hook) = shutdown.named.iter_mut().find(|hook| hook.0 == name) {
+                hook.1 = Box::new(f);
+                return
+            }
+            shutdown.named.push((name.to_owned(), Box::new(f)));
+        }
+    }
+
+    /// Register hook that runs once when system starts.
+    ///
+    /// Hooks are called in system arbiter's thread by `SystemRunner::run()`
+    /// in registration order, before event loop starts, so before `started()`
+    /// of any actor in system arbiter. Use it for global initialization,
+    /// i.e. panic hook or logging setup. If system is running already, `f`
+    /// is called immediately in current thread.
+    pub fn on_start<F>(f: F) where F: FnOnce() + Send + 'static {
+        if let Ok(mut startup) = Arbiter::system_registry().startup.lock() {
+            if let Some(ref mut hooks) = *startup {
+                hooks.push(Box::new(f));
+                return
+            }
+        }
+        f()
+    }
+
+    /// Run startup hooks, hooks registered later are called immediately
+    fn run_start_hooks() {
+        let hooks = match Arbiter::system_registry().startup.lock() {
+            Ok(mut startup) => startup.take().unwrap_or_default(),
+            Err(_) => Vec::new(),
+        };
+        for hook in hooks {
+            hook()
+        }
+    }
+
+    /// Set max time system waits for shutdown hooks and for subscribers
+    /// of `LifecycleEvent::SystemShutdown` event. Default is 5 seconds.
+    ///
+    /// Each `DeferSystemExit` deferral is limited by the same timeout.
+    pub fn set_shutdown_timeout(timeout: Duration) {
+        if let Ok(mut shutdown) = Arbiter::system_registry().shutdown.lock() {
+            shutdown.timeout = timeout;
+        }
+    }
+
+    /// Install panic hook that sends `SystemExit(101)` message to the current system.
+    ///
+    /// Panic in any thread initiates orderly system shutdown.
+    /// Previously installed panic hook get called after message is sent.
+    ///
+    /// # Panics
+    ///
+    /// Panics if it is called outside of running system.
+    pub fn with_panic_shutdown() {
+        System::with_panic_shutdown_code(101)
+    }
+
+    /// Install panic hook that sends `SystemExit(code)` message to the current system.
+    ///
+    /// # Panics
+    ///
+    /// Panics if it is called outside of running system.
+    pub fn with_panic_shutdown_code(code: i32) {
+        let sys = Arbiter::system();
+        let prev = panic::take_hook();
+
+        panic::set_hook(Box::new(move |info| {
+            sys.send(SystemExit(code));
+            prev(info);
+        }));
+    }
+}
+
+#[cfg(feature="pause")]
+impl System {
+
+    /// Pause message processing in all arbiters of the system.
+    ///
+    /// Shortcut for `FreezeSystem` message, messages sent to actors are queued
+    /// until `System::resume()` call. Returned future resolves after all
+    /// arbiters are paused. Intended for debugging, requires `pause` feature.
+    pub fn pause() -> impl Future<Item=(), Error=()> {
+        Arbiter::system().call_fut(FreezeSystem).then(|_| Ok(()))
+    }
+
+    /// Resume message processing paused with `System::pause()`.
+    pub fn resume() {
+        Arbiter::system().send(ThawSystem)
+    }
+}
+
+/// Startup hook, see `System::on_start()`
+pub(crate) type StartHook = Box<FnOnce() + Send>;
+
+/// Shutdown hook, returned future is awaited before arbiters stop
+pub(crate) type ShutdownHook = Box<FnOnce(i32) -> Box<Future<Item=(), Error=()>> + Send>;
+
+/// Named hook that runs after arbiters stop, see `System::on_shutdown()`
+pub(crate) type StopHook = Box<FnOnce() + Send>;
+
+/// Shutdown hooks of the system
+pub(crate) struct Shutdown {
+    pub hooks: Vec<ShutdownHook>,
+    pub named: Vec<(String, StopHook)>,
+    pub timeout: Duration,
+}
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        Shutdown{hooks: Vec::new(), named: Vec::new(), timeout: Duration::from_secs(5)}
+    }
+}
+
+/// Errors that can occur while starting actor with `System::spawn_actor_in()`
+#[derive(Debug, PartialEq)]
+pub enum SpawnError {
+    /// Arbiter with requested name is not registered
+    UnknownArbiter(String),
+    /// Arbiter stopped before actor get started
+    ArbiterStopped,
+    /// System reached limit set with `System::set_actor_limit()`
+    ActorLimit,
+    /// System was created without shared arbiters
+    NoArbiters,
+}
+
+impl error::Error for SpawnError {
+    fn description(&self) -> &str {
+        match *self {
+            SpawnError::UnknownArbiter(_) => "unknown arbiter",
+            SpawnError::ArbiterStopped => "arbiter stopped",
+            SpawnError::ActorLimit => "actor limit reached",
+            SpawnError::NoArbiters => "no shared arbiters",
+        }
+    }
+}
+
+impl fmt::Display for SpawnError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SpawnError::UnknownArbiter(ref name) => write!(f, "unknown arbiter: {}", name),
+            SpawnError::ArbiterStopped => write!(f, "arbiter stopped"),
+            SpawnError::ActorLimit => write!(f, "actor limit reached"),
+            SpawnError::NoArbiters => write!(f, "no shared arbiters"),
+        }
+    }
 }
 
 /// Helper object that runs System's event loop
 #[must_use="SystemRunner must be run"]
 pub struct SystemRunner {
-    core: Core,
+    core: Rc<RefCell<Core>>,
     stop: Receiver<i32>,
 }
 
@@ -96,14 +400,47 @@ impl SystemRunner {
     /// This function will start event loop and will finish once the `SystemExit`
     /// message get received.
     pub fn run(self) -> i32 {
-        let SystemRunner { mut core, stop, ..} = self;
+        let SystemRunner { core, stop, ..} = self;
 
-        // run loop
+        // clear thread-locals before event loop get dropped
+        let _guard = ArbiterGuard::new();
+        System::run_start_hooks();
+
+        // run loop, borrow marks loop as running for `TestClock::advance()`
+        let mut core = core.borrow_mut();
         match core.run(stop) {
             Ok(code) => code,
             Err(_) => 1,
         }
     }
+
+    /// This function will start event loop and will finish once future `f`
+    /// resolves, future's error is converted to exit code `1`.
+    ///
+    /// `SystemExit` message does not stop event loop while `f` is running, it
+    /// still stops arbiters. After `f` resolves system exits with `f`'s result.
+    pub fn run_future<F>(self, f: F) -> i32
+        where F: Future<Item=i32, Error=()>
+    {
+        let SystemRunner { core, stop, ..} = self;
+
+        // clear thread-locals before event loop get dropped
+        let _guard = ArbiterGuard::new();
+        System::run_start_hooks();
+
+        // run loop
+        let mut core = core.borrow_mut();
+        let code = match core.run(f) {
+            Ok(code) => code,
+            Err(_) => 1,
+        };
+
+        // stop arbiters, system could be stopped already
+        Arbiter::system().send(SystemExit(code));
+        let _ = core.run(stop);
+
+        code
+    }
 }
 
 #[doc(hidden)]
@@ -112,24 +449,271 @@ impl ResponseType<SystemExit> for System {
     type Error = ();
 }
 
+/// Shutdown system.
+///
+/// System broadcasts `LifecycleEvent::SystemShutdown` event and runs shutdown
+/// hooks, then it stops all arbiters and `SystemRunner::run()` returns exit code.
 impl Handler<SystemExit> for System {
 
-    fn handle(&mut self, msg: SystemExit, _: &mut Context<Self>) -> Response<Self, SystemExit>
+    fn handle(&mut self, msg: SystemExit, ctx: &mut Context<Self>)
+              -> Response<Self, SystemExit>
+    {
+        if self.deferred > 0 {
+            if self.exit_code.is_none() {
+                self.exit_code = Some(msg.0);
+            }
+        } else {
+            self.shutdown(msg.0, ctx);
+        }
+        Self::empty()
+    }
+}
+
+#[doc(hidden)]
+impl ResponseType<ActorCount> for System {
+    type Item = usize;
+    type Error = ();
+}
+
+impl Handler<ActorCount> for System {
+
+    fn handle(&mut self, _: ActorCount, _: &mut Context<Self>) -> Response<Self, ActorCount>
+    {
+        Self::reply(Arbiter::system_registry().actors.count())
+    }
+}
+
+#[doc(hidden)]
+impl ResponseType<SystemMetrics> for System {
+    type Item = ArbiterMetrics;
+    type Error = ();
+}
+
+impl Handler<SystemMetrics> for System {
+
+    fn handle(&mut self, _: SystemMetrics, _: &mut Context<Self>) -> Response<Self, SystemMetrics>
+    {
+        // stopped arbiters do not respond
+        let metrics: Vec<_> = self.arbiters.values()
+            .map(|addr| addr.call_fut(GetArbiterMetrics).then(|res| match res {
+                Ok(Ok(metrics)) => Ok::<_, ()>(Some(metrics)),
+                _ => Ok(None),
+            }))
+            .collect();
+
+        Self::async_reply(fut::wrap_future(future::join_all(metrics).map(|metrics| {
+            metrics.into_iter().flatten().fold(
+                ArbiterMetrics::default(), |mut total, m| {
+                    total.messages_processed += m.messages_processed;
+                    total.messages_dropped += m.messages_dropped;
+                    total.actors_started += m.actors_started;
+                    total.actors_stopped += m.actors_stopped;
+                    total.uptime = total.uptime.max(m.uptime);
+                    total
+                })
+        })))
+    }
+}
+
+#[doc(hidden)]
+impl ResponseType<IsServiceRunning> for System {
+    type Item = bool;
+    type Error = ();
+}
+
+impl Handler<IsServiceRunning> for System {
+
+    fn handle(&mut self, msg: IsServiceRunning, _: &mut Context<Self>)
+              -> Response<Self, IsServiceRunning>
+    {
+        Self::reply(Arbiter::system_registry().contains(msg.0))
+    }
+}
+
+#[doc(hidden)]
+impl ResponseType<DeferSystemExit> for System {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<DeferSystemExit> for System {
+
+    fn handle(&mut self, msg: DeferSystemExit, ctx: &mut Context<Self>)
+              -> Response<Self, DeferSystemExit>
     {
+        let timeout = match Arbiter::system_registry().shutdown.lock() {
+            Ok(shutdown) => shutdown.timeout,
+            Err(_) => Duration::from_secs(0),
+        };
+
+        self.deferred += 1;
+        msg.0.then(|_| Ok(()))
+            .select(Delay::new(timeout).map(|_| {
+                warn!("SystemExit deferral timeout");
+            }))
+            .actfuture()
+            .then(|_, act: &mut System, ctx: &mut Context<Self>| {
+                act.deferred -= 1;
+                if act.deferred == 0 {
+                    if let Some(code) = act.exit_code.take() {
+                        act.shutdown(code, ctx);
+                    }
+                }
+                fut::ok(())
+            })
+            .spawn(ctx);
+        Self::empty()
+    }
+}
+
+impl System {
+    /// Notify subscribers and run shutdown hooks, then stop arbiters
+    fn shutdown(&mut self, code: i32, ctx: &mut Context<Self>) {
+        if self.shutting_down {
+            return
+        }
+        self.shutting_down = true;
+
+        let (hooks, timeout) = match Arbiter::system_registry().shutdown.lock() {
+            Ok(mut shutdown) => (std::mem::replace(&mut shutdown.hooks, Vec::new()),
+                                 shutdown.timeout),
+            Err(_) => (Vec::new(), Duration::from_secs(0)),
+        };
+
+        let mut pending: Vec<Box<Future<Item=(), Error=()>>> = Vec::new();
+        if let Some(addr) = Arbiter::lifecycle_events() {
+            pending.push(Box::new(
+                addr.call_fut(LifecycleEvent::SystemShutdown(code)).then(|_| Ok(()))));
+        }
+        for hook in hooks {
+            pending.push(hook(code));
+        }
+        if pending.is_empty() {
+            return self.exit(code, ctx)
+        }
+
+        future::join_all(pending).map(|_| ())
+            .select(Delay::new(timeout).map(|_| {
+                warn!("System shutdown timeout, stopping arbiters");
+            }))
+            .actfuture()
+            .then(move |_, act: &mut System, ctx: &mut Context<Self>| {
+                act.exit(code, ctx);
+                fut::ok(())
+            })
+            .spawn(ctx);
+    }
+
+    fn exit(&mut self, code: i32, ctx: &mut Context<Self>) {
         // stop rbiters
         for addr in self.arbiters.values() {
-            addr.send(StopArbiter(msg.0));
+            addr.send(StopArbiter(code));
+        }
+
+        // named hooks run after arbiters stop
+        let timeout = match Arbiter::system_registry().shutdown.lock() {
+            Ok(ref shutdown) if !shutdown.named.is_empty() => Some(shutdown.timeout),
+            _ => None,
+        };
+        let timeout = match timeout {
+            Some(timeout) if self.arbiters_running() => timeout,
+            _ => return self.stop(code),
+        };
+        self.stopping = Some(code);
+        Delay::new(timeout)
+            .actfuture()
+            .then(move |_, act: &mut System, _: &mut Context<Self>| {
+                if act.stopping.is_some() {
+                    warn!("System shutdown timeout, arbiters are still running");
+                    act.stop(code);
+                }
+                fut::ok(())
+            })
+            .spawn(ctx);
+    }
+
+    /// Check if any arbiter besides system arbiter is registered
+    fn arbiters_running(&self) -> bool {
+        let sys_arbiter = Arbiter::system_arbiter().id();
+        self.arbiters.values().any(|addr| addr.id() != sys_arbiter)
+    }
+
+    /// Run named shutdown hooks and stop event loop
+    fn stop(&mut self, code: i32) {
+        self.stopping = None;
+
+        let hooks = match Arbiter::system_registry().shutdown.lock() {
+            Ok(mut shutdown) => std::mem::replace(&mut shutdown.named, Vec::new()),
+            Err(_) => Vec::new(),
+        };
+        for (_, hook) in hooks {
+            hook()
         }
+
         // stop event loop
         if let Some(stop) = self.stop.take() {
-            let _ = stop.send(msg.0);
+            let _ = stop.send(code);
+        }
+    }
+}
+
+#[doc(hidden)]
+impl ResponseType<SystemArbiterUpdated> for System {
+    type Item = ();
+    type Error = ();
+}
+
+/// Broadcast new system arbiter address to all registered arbiters
+impl Handler<SystemArbiterUpdated> for System {
+
+    fn handle(&mut self, msg: SystemArbiterUpdated, _: &mut Context<Self>)
+              -> Response<Self, SystemArbiterUpdated>
+    {
+        for addr in self.arbiters.values() {
+            addr.send(msg.clone());
         }
         Self::empty()
     }
 }
 
-/// Register Arbiter within system
-pub(crate) struct RegisterArbiter(pub String, pub SyncAddress<Arbiter>);
+#[doc(hidden)]
+impl ResponseType<FreezeSystem> for System {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<FreezeSystem> for System {
+
+    fn handle(&mut self, _: FreezeSystem, _: &mut Context<Self>) -> Response<Self, FreezeSystem>
+    {
+        self.frozen = true;
+        let frozen: Vec<_> = self.arbiters.values()
+            .map(|addr| addr.call_fut(FreezeArbiter).then(|_| Ok::<_, ()>(())))
+            .collect();
+        Self::async_reply(fut::wrap_future(future::join_all(frozen).map(|_| ())))
+    }
+}
+
+#[doc(hidden)]
+impl ResponseType<ThawSystem> for System {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<ThawSystem> for System {
+
+    fn handle(&mut self, _: ThawSystem, _: &mut Context<Self>) -> Response<Self, ThawSystem>
+    {
+        self.frozen = false;
+        for addr in self.arbiters.values() {
+            addr.send(ThawArbiter);
+        }
+        Self::empty()
+    }
+}
+
+/// Register Arbiter within system, second field is arbiter's name
+pub(crate) struct RegisterArbiter(pub String, pub Option<String>, pub SyncAddress<Arbiter>);
 
 #[doc(hidden)]
 impl ResponseType<RegisterArbiter> for System {
@@ -143,7 +727,18 @@ impl Handler<RegisterArbiter> for System {
     fn handle(&mut self, msg: RegisterArbiter, _: &mut Context<Self>)
               -> Response<Self, RegisterArbiter>
     {
-        self.arbiters.insert(msg.0, msg.1);
+        events::emit(LifecycleEvent::ArbiterRegistered{id: msg.0.clone(), name: msg.1.clone()});
+
+        if let Some(name) = msg.1 {
+            if let Ok(mut named) = self.named.lock() {
+                named.insert(name.clone(), msg.2.clone());
+            }
+            self.names.insert(msg.0.clone(), name);
+        }
+        if self.frozen {
+            msg.2.send(FreezeArbiter);
+        }
+        self.arbiters.insert(msg.0, msg.2);
         Self::empty()
     }
 }
@@ -163,7 +758,57 @@ impl Handler<UnregisterArbiter> for System {
     fn handle(&mut self, msg: UnregisterArbiter, _: &mut Context<Self>)
               -> Response<Self, UnregisterArbiter>
     {
-        self.arbiters.remove(&msg.0);
+        self.unregister(&msg.0);
         Self::empty()
     }
 }
+
+/// Arbiter's thread terminated with panic
+pub(crate) struct ArbiterDied(pub String);
+
+#[doc(hidden)]
+impl ResponseType<ArbiterDied> for System {
+    type Item = ();
+    type Error = ();
+}
+
+#[doc(hidden)]
+impl Handler<ArbiterDied> for System {
+
+    fn handle(&mut self, msg: ArbiterDied, _: &mut Context<Self>) -> Response<Self, ArbiterDied>
+    {
+        match self.names.get(&msg.0) {
+            Some(name) => error!("Arbiter {:?} ({}) thread panicked", name, msg.0),
+            None => error!("Arbiter {} thread panicked", msg.0),
+        }
+        // arbiter could die before it unregisters itself
+        self.unregister(&msg.0);
+        Self::empty()
+    }
+}
+
+impl System {
+    fn unregister(&mut self, id: &str) {
+        if self.arbiters.remove(id).is_some() {
+            events::emit(LifecycleEvent::ArbiterUnregistered{
+                id: id.to_owned(), name: self.names.get(id).cloned()});
+        }
+        if let Some(code) = self.stopping {
+            if !self.arbiters_running() {
+                self.stop(code);
+            }
+        }
+        if let Some(name) = self.names.remove(id) {
+            // other arbiter could be registered with the same name
+            let other = self.names.iter()
+                .find(|&(_, n)| *n == name)
+                .and_then(|(id, _)| self.arbiters.get(id).cloned());
+            if let Ok(mut named) = self.named.lock() {
+                match other {
+                    Some(addr) => named.insert(name, addr),
+                    None => named.remove(&name),
+                };
+            }
+        }
+    }
+}
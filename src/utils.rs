@@ -1,12 +1,12 @@
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use std::marker::PhantomData;
-use futures::{Async, Future, Poll};
+use futures::{Async, Future, Poll, Stream};
 use futures::unsync::oneshot;
-use tokio_core::reactor::Timeout;
 
 use fut::ActorFuture;
 use actor::Actor;
 use arbiter::Arbiter;
+use clock::{Delay, Ticker};
 
 
 #[doc(hidden)]
@@ -38,7 +38,7 @@ impl<T> Default for Condition<T> where T: Clone {
 pub(crate) struct TimeoutWrapper<M, E> {
     msg: Option<M>,
     err: PhantomData<E>,
-    timeout: Timeout,
+    timeout: Delay,
 }
 
 impl<M, E> TimeoutWrapper<M, E> {
@@ -46,7 +46,7 @@ impl<M, E> TimeoutWrapper<M, E> {
         TimeoutWrapper{
             msg: Some(msg),
             err: PhantomData,
-            timeout: Timeout::new(timeout, Arbiter::handle()).unwrap()}
+            timeout: Delay::new(timeout)}
     }
 }
 
@@ -69,7 +69,7 @@ impl<M, E> Future for TimeoutWrapper<M, E>
 pub(crate)
 struct TimerFunc<A> where A: Actor {
     f: Option<Box<TimerFuncBox<A>>>,
-    timeout: Timeout,
+    timeout: Delay,
 }
 
 impl<A> TimerFunc<A> where A: Actor {
@@ -78,7 +78,7 @@ impl<A> TimerFunc<A> where A: Actor {
     {
         TimerFunc {
             f: Some(Box::new(f)),
-            timeout: Timeout::new(timeout, Arbiter::handle()).unwrap()}
+            timeout: Delay::new(timeout)}
     }
 }
 
@@ -115,3 +115,69 @@ impl<A> ActorFuture for TimerFunc<A> where A: Actor {
         }
     }
 }
+
+type IntervalFn<A> = Box<FnMut(&mut A, &mut <A as Actor>::Context)>;
+
+pub(crate) struct IntervalFunc<A> where A: Actor {
+    f: IntervalFn<A>,
+    ticker: Ticker,
+    interval: Duration,
+    next: Instant,
+    deadline: Instant,
+    timeout: Delay,
+    remaining: usize,
+}
+
+impl<A> IntervalFunc<A> where A: Actor {
+    pub fn new<F>(dur: Duration, interval: Duration, max_iterations: usize, f: F)
+                  -> IntervalFunc<A>
+        where F: FnMut(&mut A, &mut A::Context) + 'static
+    {
+        let now = Arbiter::now();
+        IntervalFunc {
+            f: Box::new(f),
+            ticker: Ticker::new(interval),
+            interval: interval,
+            next: now + interval,
+            deadline: now + dur,
+            timeout: Delay::new(dur),
+            remaining: max_iterations,
+        }
+    }
+}
+
+#[doc(hidden)]
+impl<A> ActorFuture for IntervalFunc<A> where A: Actor {
+    type Item = ();
+    type Error = ();
+    type Actor = A;
+
+    fn poll(&mut self, act: &mut Self::Actor, ctx: &mut <Self::Actor as Actor>::Context)
+            -> Poll<Self::Item, Self::Error>
+    {
+        loop {
+            if self.remaining == 0 {
+                return Ok(Async::Ready(()))
+            }
+            match self.ticker.poll() {
+                Ok(Async::Ready(_)) => {
+                    // several ticks could be due at once
+                    if self.next > self.deadline {
+                        return Ok(Async::Ready(()))
+                    }
+                    self.next += self.interval;
+                    self.remaining -= 1;
+                    (self.f)(act, ctx);
+                }
+                Ok(Async::NotReady) => break,
+                Err(_) => unreachable!(),
+            }
+        }
+
+        // stop at deadline, even if next tick is later
+        match self.timeout.poll() {
+            Ok(Async::NotReady) => Ok(Async::NotReady),
+            _ => Ok(Async::Ready(())),
+        }
+    }
+}
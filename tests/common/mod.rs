@@ -0,0 +1,9 @@
+//! Helpers shared by integration tests
+use std::time::Duration;
+use tokio_core::reactor::Timeout;
+use actix::Arbiter;
+
+/// Let arbiter's event loop run for a while
+pub fn sleep() -> Timeout {
+    Timeout::new(Duration::from_millis(20), Arbiter::handle()).unwrap()
+}
@@ -2,12 +2,14 @@ extern crate actix;
 extern crate futures;
 extern crate tokio_core;
 
+use std::panic;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::Duration;
 use futures::{future, Future};
 use tokio_core::reactor::Timeout;
 use actix::prelude::*;
+use actix::CallError;
 
 #[derive(Debug)]
 struct Ping(usize);
@@ -132,3 +134,449 @@ fn test_address_upgrade() {
     sys.run();
     assert_eq!(count.load(Ordering::Relaxed), 3);
 }
+
+struct Pong(usize);
+
+#[test]
+fn test_subscriber_map() {
+    let sys = System::new("test");
+    let count = Arc::new(AtomicUsize::new(0));
+
+    let addr: Address<_> = MyActor(Arc::clone(&count)).start();
+    let subscr = addr.subscriber::<Ping>().map(|msg: &Pong| Ping(msg.0));
+    assert!(subscr.send(Pong(1)).is_ok());
+
+    let saddr: SyncAddress<_> = MyActor(Arc::clone(&count)).start();
+    let subscr2 = saddr.subscriber::<Ping>().sync_map(|msg: &Pong| Ping(msg.0));
+    assert!(subscr2.send(Pong(2)).is_ok());
+
+    Arbiter::handle().spawn_fn(move || {
+        Timeout::new(Duration::new(0, 1000), Arbiter::handle()).unwrap()
+            .then(move |_| {
+                Arbiter::system().send(msgs::SystemExit(0));
+                future::result(Ok(()))
+            })
+    });
+
+    sys.run();
+    assert_eq!(count.load(Ordering::Relaxed), 2);
+}
+
+struct StoppedActor;
+
+impl Actor for StoppedActor {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Context<Self>) {
+        ctx.stop();
+    }
+}
+
+impl ResponseType<Ping> for StoppedActor {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<Ping> for StoppedActor {
+    fn handle(&mut self, _: Ping, _: &mut Context<Self>) -> Response<Self, Ping> {
+        Self::empty()
+    }
+}
+
+#[test]
+fn test_subscriber_debug() {
+    let sys = System::new("test");
+
+    let addr: Address<_> = StoppedActor.start();
+    let subscriber = addr.subscriber::<Ping>();
+    assert_eq!(format!("{:?}", subscriber), "Subscriber<test_address::Ping>(connected: true)");
+
+    Arbiter::handle().spawn(
+        Timeout::new(Duration::from_millis(100), Arbiter::handle()).unwrap()
+            .then(move |_| {
+                assert_eq!(format!("{:?}", subscriber),
+                           "Subscriber<test_address::Ping>(connected: false)");
+                Arbiter::system().send(msgs::SystemExit(0));
+                future::result(Ok(()))
+            }));
+
+    sys.run();
+}
+
+#[test]
+fn test_send_or_log() {
+    let sys = System::new("test");
+
+    let addr: SyncAddress<_> = StoppedActor.start();
+    assert!(addr.connected());
+
+    Arbiter::handle().spawn(
+        Timeout::new(Duration::from_millis(100), Arbiter::handle()).unwrap()
+            .then(move |_| {
+                // lost message is logged, address gets disconnected
+                addr.send_or_log(Ping(1));
+                assert!(!addr.connected());
+                Arbiter::system().send(msgs::SystemExit(0));
+                future::result(Ok(()))
+            }));
+
+    sys.run();
+}
+
+fn ping<T: Into<Address<MyActor>>>(addr: T, n: usize) {
+    addr.into().send(Ping(n))
+}
+
+fn sync_ping<T: Into<SyncAddress<MyActor>>>(addr: T, n: usize) {
+    addr.into().send(Ping(n))
+}
+
+#[test]
+fn test_address_from_ref() {
+    let sys = System::new("test");
+    let count = Arc::new(AtomicUsize::new(0));
+
+    let (addr, saddr): (Address<_>, SyncAddress<_>) = MyActor(Arc::clone(&count)).start();
+    ping(&addr, 0);
+    ping(addr, 1);
+    sync_ping(&saddr, 2);
+    sync_ping(saddr, 3);
+
+    Arbiter::handle().spawn(
+        Timeout::new(Duration::from_millis(50), Arbiter::handle()).unwrap()
+            .then(|_| {
+                Arbiter::system().send(msgs::SystemExit(0));
+                future::result(Ok(()))
+            }));
+
+    sys.run();
+    assert_eq!(count.load(Ordering::Relaxed), 4);
+}
+
+#[test]
+fn test_address_unwind_safe() {
+    let sys = System::new("test");
+    let count = Arc::new(AtomicUsize::new(0));
+
+    let (addr, saddr): (Address<_>, SyncAddress<_>) = MyActor(Arc::clone(&count)).start();
+    let res = panic::catch_unwind(|| {
+        addr.send(Ping(0));
+        saddr.send(Ping(1));
+        panic!("handled");
+    });
+    assert!(res.is_err());
+
+    // addresses are usable after panic
+    addr.send(Ping(2));
+    let res = panic::catch_unwind(move || saddr.send(Ping(3)));
+    assert!(res.is_ok());
+
+    Arbiter::handle().spawn(
+        Timeout::new(Duration::from_millis(50), Arbiter::handle()).unwrap()
+            .then(|_| {
+                Arbiter::system().send(msgs::SystemExit(0));
+                future::result(Ok(()))
+            }));
+
+    sys.run();
+    assert_eq!(count.load(Ordering::Relaxed), 4);
+}
+
+#[test]
+fn test_subscriber_send_tracked() {
+    let sys = System::new("test");
+    let count = Arc::new(AtomicUsize::new(0));
+
+    let addr: SyncAddress<_> = MyActor(Arc::clone(&count)).start();
+    let subscriber = addr.subscriber::<Ping>();
+    let counter = Arc::clone(&count);
+
+    Arbiter::handle().spawn(
+        subscriber.send_tracked(Ping(0)).unwrap()
+            .then(move |res| {
+                assert!(res.is_ok());
+                // ack is sent after message is handled
+                assert_eq!(counter.load(Ordering::Relaxed), 1);
+                Arbiter::system().send(msgs::SystemExit(0));
+                future::result(Ok(()))
+            }));
+
+    sys.run();
+}
+
+#[test]
+fn test_subscriber_downcast() {
+    let sys = System::new("test");
+
+    let addr: Address<_> = MyActor(Arc::new(AtomicUsize::new(0))).start();
+    let subscriber = addr.subscriber::<Ping>();
+    assert_eq!(subscriber.type_name(), "test_address::MyActor");
+    assert!(subscriber.downcast_ref::<Address<MyActor>>().is_some());
+    assert!(subscriber.downcast_ref::<SyncAddress<MyActor>>().is_none());
+
+    let addr: SyncAddress<_> = MyActor(Arc::new(AtomicUsize::new(0))).start();
+    let subscriber = addr.subscriber::<Ping>();
+    let mapped = addr.subscriber::<Ping>().sync_map(|n: &usize| Ping(*n));
+    assert_eq!(mapped.type_name(), "test_address::MyActor");
+
+    let boxed: Box<Subscriber<Ping>> = Box::new(subscriber);
+    assert_eq!(boxed.type_name(), "test_address::MyActor");
+    assert!(boxed.downcast_ref::<SyncAddress<MyActor>>().is_some());
+
+    Arbiter::system().send(msgs::SystemExit(0));
+    sys.run();
+}
+
+#[test]
+fn test_recipient_clone() {
+    let sys = System::new("test");
+    let count = Arc::new(AtomicUsize::new(0));
+
+    let addr: SyncAddress<_> = MyActor(Arc::clone(&count)).start();
+    let recipient = addr.recipient::<Ping>();
+    let recipient2 = recipient.clone();
+    assert_eq!(format!("{:?}", recipient2), "Recipient<test_address::Ping>(connected: true)");
+    assert_eq!(recipient2.type_name(), "test_address::MyActor");
+
+    // both clones deliver to the same actor
+    recipient.send(Ping(1)).unwrap();
+    recipient2.send(Ping(2)).unwrap();
+    let subscriber: Box<Subscriber<Ping> + Send> = Box::new(recipient2);
+    subscriber.send(Ping(3)).unwrap();
+
+    Arbiter::handle().spawn(
+        Timeout::new(Duration::from_millis(50), Arbiter::handle()).unwrap()
+            .then(|_| {
+                Arbiter::system().send(msgs::SystemExit(0));
+                future::result(Ok(()))
+            }));
+
+    sys.run();
+    assert_eq!(count.load(Ordering::Relaxed), 3);
+}
+
+struct Half(u32);
+
+struct Hang;
+
+struct Calc;
+
+impl Actor for Calc {
+    type Context = Context<Self>;
+}
+
+impl ResponseType<Half> for Calc {
+    type Item = u32;
+    type Error = &'static str;
+}
+
+impl Handler<Half> for Calc {
+    fn handle(&mut self, msg: Half, _: &mut Context<Self>) -> Response<Self, Half> {
+        if msg.0 % 2 == 0 {
+            Self::reply(msg.0 / 2)
+        } else {
+            Self::reply_error("odd")
+        }
+    }
+}
+
+impl ResponseType<Hang> for Calc {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<Hang> for Calc {
+    fn handle(&mut self, _: Hang, _: &mut Context<Self>) -> Response<Self, Hang> {
+        Self::async_reply(fut::wrap_future(future::empty()))
+    }
+}
+
+#[test]
+fn test_call_timeout() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let sys = std::thread::spawn(move || {
+        let sys = System::new("test");
+        let addr: SyncAddress<_> = Calc.start();
+        tx.send((addr, Arbiter::system())).unwrap();
+        sys.run();
+    });
+    let (addr, system) = rx.recv().unwrap();
+    let timeout = Duration::from_secs(5);
+
+    assert_eq!(addr.call_timeout(Half(4), timeout), Ok(2));
+    assert_eq!(addr.call_timeout(Half(3), timeout), Err(CallError::Handler("odd")));
+    assert_eq!(addr.call_timeout(Hang, Duration::from_millis(50)), Err(CallError::Timeout));
+
+    system.send(msgs::SystemExit(0));
+    sys.join().unwrap();
+    assert_eq!(addr.call_timeout(Half(4), timeout), Err(CallError::MailboxClosed));
+}
+
+#[test]
+#[cfg(feature="test-utils")]
+fn test_inspect() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let sys = std::thread::spawn(move || {
+        let sys = System::new("test");
+        let (addr, saddr): (Address<_>, SyncAddress<_>) =
+            MyActor(Arc::new(AtomicUsize::new(0))).start();
+        addr.send(Ping(0));
+        Arbiter::handle().spawn(
+            addr.inspect(|act: &MyActor| act.0.load(Ordering::Relaxed))
+                .then(move |res| {
+                    assert_eq!(res, Ok(1));
+                    tx.send((saddr, Arbiter::system())).unwrap();
+                    Ok(())
+                }));
+        sys.run();
+    });
+    let (addr, system) = rx.recv().unwrap();
+    let timeout = Duration::from_secs(5);
+
+    addr.send(Ping(1));
+    assert_eq!(addr.inspect_timeout(|act| act.0.load(Ordering::Relaxed), timeout), Ok(2));
+
+    system.send(msgs::SystemExit(0));
+    sys.join().unwrap();
+    assert_eq!(addr.inspect_timeout(|act| act.0.load(Ordering::Relaxed), timeout),
+               Err(CallError::MailboxClosed));
+}
+
+struct Budget;
+
+struct Back;
+
+impl Actor for Back {
+    type Context = Context<Self>;
+}
+
+impl ResponseType<Budget> for Back {
+    type Item = Option<Duration>;
+    type Error = ();
+}
+
+impl Handler<Budget> for Back {
+    fn handle(&mut self, _: Budget, ctx: &mut Context<Self>) -> Response<Self, Budget> {
+        Self::reply(ctx.remaining_budget())
+    }
+}
+
+struct Front(SyncAddress<Back>);
+
+impl Actor for Front {
+    type Context = Context<Self>;
+}
+
+impl ResponseType<Budget> for Front {
+    type Item = (Option<Duration>, Option<Duration>);
+    type Error = ();
+}
+
+impl Handler<Budget> for Front {
+    fn handle(&mut self, _: Budget, ctx: &mut Context<Self>) -> Response<Self, Budget> {
+        let budget = ctx.remaining_budget();
+        Self::async_reply(
+            self.0.call_fut(Budget).actfuture()
+                .then(move |res, _: &mut Self, _: &mut Context<Self>| {
+                    actix::fut::ok((budget, res.unwrap().unwrap()))
+                }))
+    }
+}
+
+#[test]
+fn test_remaining_budget() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let (budget_tx, budget_rx) = std::sync::mpsc::channel();
+    let sys = std::thread::spawn(move || {
+        let sys = System::new("test");
+        let back: SyncAddress<_> = Back.start();
+        let front: SyncAddress<_> = Front(back.clone()).start();
+
+        // message without deadline has no budget
+        Arbiter::handle().spawn(back.call_fut(Budget).then(move |res| {
+            budget_tx.send(res.unwrap()).unwrap();
+            Ok(())
+        }));
+        tx.send((front, Arbiter::system())).unwrap();
+        sys.run();
+    });
+    let (front, system) = rx.recv().unwrap();
+    assert_eq!(budget_rx.recv().unwrap(), Ok(None));
+
+    // nested call inherits shrinking deadline of the caller
+    let timeout = Duration::from_secs(5);
+    let (front_budget, back_budget) = front.call_timeout(Budget, timeout).unwrap();
+    let front_budget = front_budget.unwrap();
+    let back_budget = back_budget.unwrap();
+    assert!(front_budget <= timeout);
+    assert!(back_budget <= front_budget);
+    assert!(back_budget > Duration::from_secs(4));
+
+    system.send(msgs::SystemExit(0));
+    sys.join().unwrap();
+}
+
+#[test]
+fn test_address_display() {
+    let sys = System::new("test");
+
+    let (addr, saddr): (Address<_>, SyncAddress<_>) = Calc.start();
+    assert_eq!(addr.id(), saddr.id());
+
+    let short = &addr.id().simple().to_string()[..4];
+    assert_eq!(format!("{}", addr), format!("Address<Calc>({})", short));
+    assert_eq!(format!("{}", saddr), format!("SyncAddress<Calc>({})", short));
+    assert_eq!(format!("{:?}", addr),
+               format!("Address<test_address::Calc>(id: {})", addr.id().hyphenated()));
+
+    Arbiter::system().send(msgs::SystemExit(0));
+    sys.run();
+}
+
+#[test]
+fn test_call_error() {
+    use std::error::Error;
+    use std::fmt;
+
+    #[derive(Debug)]
+    struct Odd;
+
+    impl fmt::Display for Odd {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "odd")
+        }
+    }
+
+    impl Error for Odd {
+        fn description(&self) -> &str { "odd" }
+    }
+
+    assert_eq!(CallError::<Odd>::Timeout.to_string(), "call timed out");
+    assert_eq!(CallError::<Odd>::MailboxClosed.to_string(), "mailbox closed");
+    assert!(CallError::<Odd>::Timeout.source().is_none());
+
+    let err: Box<Error> = Box::new(CallError::Handler(Odd));
+    assert_eq!(err.to_string(), "handler error: odd");
+    assert_eq!(err.source().unwrap().to_string(), "odd");
+}
+
+/// Subscriber that can not deliver messages
+struct Rejecting;
+
+impl Subscriber<Ping> for Rejecting {
+    fn send(&self, msg: Ping) -> Result<(), Ping> {
+        Err(msg)
+    }
+}
+
+#[test]
+fn test_subscriber_map_error() {
+    // connection state is unknown
+    assert!(Rejecting.connected());
+
+    let mapped = Rejecting.map(|msg: &Pong| Ping(msg.0));
+    match mapped.send(Pong(3)) {
+        Err(Pong(3)) => (),
+        _ => panic!("original message should be returned"),
+    }
+}
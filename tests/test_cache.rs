@@ -0,0 +1,104 @@
+extern crate actix;
+extern crate futures;
+extern crate tokio_core;
+
+mod common;
+
+use std::time::Duration;
+use futures::Future;
+use actix::prelude::*;
+use actix::actors::cache::{Cache, CacheStats, Get, Invalidate, Put, Stats};
+use common::sleep;
+
+fn secs(secs: u64) -> Duration {
+    Duration::from_secs(secs)
+}
+
+#[test]
+fn test_get_put_invalidate() {
+    let sys = System::new("test");
+
+    let cache: Address<_> = Cache::<&'static str, u32>::new().start();
+    cache.send(Put("a", 1, secs(10)));
+    cache.send(Put("b", 2, secs(10)));
+
+    let c1 = cache.clone();
+    let c2 = cache.clone();
+    let c3 = cache.clone();
+    let c4 = cache.clone();
+    Arbiter::handle().spawn(
+        cache.call_fut(Get("a"))
+            .then(move |res| {
+                assert_eq!(res.unwrap(), Ok(Some(1)));
+                c1.call_fut(Get("c"))
+            })
+            .then(move |res| {
+                assert_eq!(res.unwrap(), Ok(None));
+                c2.call_fut(Invalidate("b"))
+            })
+            .then(move |res| {
+                assert_eq!(res.unwrap(), Ok(true));
+                c3.call_fut(Invalidate("b"))
+            })
+            .then(move |res| {
+                assert_eq!(res.unwrap(), Ok(false));
+                c4.call_fut(Stats)
+            })
+            .then(|res| {
+                assert_eq!(res.unwrap(),
+                           Ok(CacheStats{hits: 1, misses: 1, evicted: 0, entries: 1}));
+                Arbiter::system().send(msgs::SystemExit(0));
+                Ok(())
+            }));
+    sys.run();
+}
+
+#[test]
+fn test_ttl_eviction() {
+    let sys = System::new("test");
+    let clock = TestClock::new();
+    Arbiter::set_clock(clock.clone());
+
+    let cache: Address<_> = Cache::<&'static str, u32>::new().start();
+    cache.send(Put("a", 1, secs(10)));
+    cache.send(Put("b", 2, secs(30)));
+
+    // replaced entry is not evicted by timer of previous one
+    cache.send(Put("c", 3, secs(10)));
+    cache.send(Put("c", 4, secs(60)));
+
+    let clock2 = clock.clone();
+    let c1 = cache.clone();
+    let c2 = cache.clone();
+    let c3 = cache.clone();
+    Arbiter::handle().spawn(
+        sleep()
+            .then(move |_| {
+                clock.advance(secs(10));
+                sleep()
+            })
+            .then(move |_| c1.call_fut(Stats))
+            .then(move |res| {
+                // timer evicts expired entry without reads
+                assert_eq!(res.unwrap(),
+                           Ok(CacheStats{hits: 0, misses: 0, evicted: 1, entries: 2}));
+                c2.call_fut(Get("c"))
+            })
+            .then(move |res| {
+                assert_eq!(res.unwrap(), Ok(Some(4)));
+                clock2.advance(secs(20));
+                c3.call_fut(Get("b"))
+            })
+            .then(move |res| {
+                // expired entry is never returned, even before timer fires
+                assert_eq!(res.unwrap(), Ok(None));
+                cache.call_fut(Stats)
+            })
+            .then(|res| {
+                assert_eq!(res.unwrap(),
+                           Ok(CacheStats{hits: 1, misses: 1, evicted: 2, entries: 1}));
+                Arbiter::system().send(msgs::SystemExit(0));
+                Ok(())
+            }));
+    sys.run();
+}
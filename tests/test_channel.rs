@@ -0,0 +1,53 @@
+extern crate actix;
+extern crate futures;
+
+use std::sync::{Arc, Mutex};
+use actix::prelude::*;
+use actix::msgs::SystemExit;
+
+struct Consumer(Arc<Mutex<Vec<usize>>>);
+
+impl Actor for Consumer {
+    type Context = Context<Self>;
+}
+
+impl ResponseType<usize> for Consumer {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<usize> for Consumer {
+    fn handle(&mut self, msg: usize, _: &mut Context<Self>) -> Response<Self, usize> {
+        self.0.lock().unwrap().push(msg);
+        Self::empty()
+    }
+}
+
+impl StreamHandler<usize> for Consumer {
+    fn finished(&mut self, _: &mut Context<Self>) {
+        Arbiter::system().send(SystemExit(0));
+    }
+}
+
+#[test]
+fn test_actor_channel_backpressure() {
+    let sys = System::new("test");
+
+    let (mut tx, rx) = actor_channel(1);
+
+    // capacity plus one message for the sender
+    assert!(tx.try_send(1).is_ok());
+    assert!(tx.try_send(2).is_ok());
+    assert_eq!(tx.try_send(3), Err(3));
+
+    let items = Arc::new(Mutex::new(Vec::new()));
+    let consumer_items = Arc::clone(&items);
+    let _: () = Consumer::create(move |ctx| {
+        ctx.add_stream(rx);
+        Consumer(consumer_items)
+    });
+    drop(tx);
+
+    sys.run();
+    assert_eq!(*items.lock().unwrap(), vec![1, 2]);
+}
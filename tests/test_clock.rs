@@ -0,0 +1,191 @@
+extern crate actix;
+extern crate futures;
+
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use futures::{future, Async, Future, Poll};
+use futures::task;
+use actix::prelude::*;
+
+/// Future that lets other tasks of event loop run once
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+    type Item = ();
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<(), ()> {
+        if self.0 {
+            Ok(Async::Ready(()))
+        } else {
+            self.0 = true;
+            task::current().notify();
+            Ok(Async::NotReady)
+        }
+    }
+}
+
+struct Delayed(Arc<AtomicUsize>);
+
+impl Actor for Delayed {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Context<Self>) {
+        ctx.run_later(Duration::from_secs(10), |act, _| {
+            act.0.fetch_add(1, Ordering::Relaxed);
+        });
+    }
+}
+
+#[test]
+fn test_run_later() {
+    let sys = System::new("test");
+
+    let clock = TestClock::new();
+    Arbiter::set_clock(clock.clone());
+
+    let fired = Arc::new(AtomicUsize::new(0));
+    let fired2 = Arc::clone(&fired);
+    let _addr: Address<_> = Delayed(Arc::clone(&fired)).start();
+
+    let start = Arbiter::now();
+    Arbiter::handle().spawn(
+        YieldNow(false)
+            .and_then(move |_| {
+                clock.advance(Duration::from_secs(9));
+                assert_eq!(Arbiter::now() - start, Duration::from_secs(9));
+                YieldNow(false).map(move |_| clock)
+            })
+            .and_then(move |clock| {
+                assert_eq!(fired2.load(Ordering::Relaxed), 0);
+                clock.advance(Duration::from_secs(1));
+                // timer fires on next iteration of event loop
+                assert_eq!(fired2.load(Ordering::Relaxed), 0);
+                YieldNow(false)
+            })
+            .then(|_| {
+                Arbiter::system().send(msgs::SystemExit(0));
+                future::result(Ok(()))
+            }));
+
+    sys.run();
+    assert_eq!(fired.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn test_advance_fires_timers() {
+    let sys = System::new("test");
+
+    let clock = TestClock::new();
+    Arbiter::set_clock(clock.clone());
+
+    let fired = Arc::new(AtomicUsize::new(0));
+    let _addr: Address<_> = Delayed(Arc::clone(&fired)).start();
+
+    // each tick sees time of its own deadline
+    let start = Arbiter::now();
+    let ticks = Arc::new(Mutex::new(Vec::new()));
+    let ticks2 = Arc::clone(&ticks);
+    Arbiter::spawn_interval(Duration::from_secs(4), move || {
+        ticks2.lock().unwrap().push((Arbiter::now() - start).as_secs());
+    });
+
+    // event loop is not running, timers fire during `advance()` call
+    clock.advance(Duration::from_secs(9));
+    assert_eq!(fired.load(Ordering::Relaxed), 0);
+    assert_eq!(*ticks.lock().unwrap(), vec![4, 8]);
+
+    clock.advance(Duration::from_secs(1));
+    assert_eq!(fired.load(Ordering::Relaxed), 1);
+    assert_eq!(Arbiter::now() - start, Duration::from_secs(10));
+
+    Arbiter::system().send(msgs::SystemExit(0));
+    sys.run();
+}
+
+#[test]
+fn test_spawn_interval() {
+    let sys = System::new("test");
+
+    let clock = TestClock::new();
+    Arbiter::set_clock(clock.clone());
+
+    let ticks = Arc::new(AtomicUsize::new(0));
+    let ticks2 = Arc::clone(&ticks);
+    Arbiter::spawn_interval(Duration::from_secs(10), move || {
+        ticks2.fetch_add(1, Ordering::Relaxed);
+    });
+
+    Arbiter::handle().spawn(
+        YieldNow(false)
+            .and_then(move |_| {
+                // all due ticks fire at once
+                clock.advance(Duration::from_secs(35));
+                YieldNow(false)
+            })
+            .then(|_| {
+                Arbiter::system().send(msgs::SystemExit(0));
+                future::result(Ok(()))
+            }));
+
+    sys.run();
+    assert_eq!(ticks.load(Ordering::Relaxed), 3);
+}
+
+struct Polling {
+    max: Option<usize>,
+    calls: Arc<AtomicUsize>,
+}
+
+impl Actor for Polling {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Context<Self>) {
+        let (dur, interval) = (Duration::from_secs(25), Duration::from_secs(10));
+        let poll = |act: &mut Polling, _: &mut Context<Polling>| {
+            act.calls.fetch_add(1, Ordering::Relaxed);
+        };
+        match self.max {
+            Some(max) => ctx.for_duration_max(dur, interval, max, poll),
+            None => ctx.for_duration(dur, interval, poll),
+        };
+    }
+}
+
+fn run_polling(max: Option<usize>) -> usize {
+    let sys = System::new("test");
+
+    let clock = TestClock::new();
+    Arbiter::set_clock(clock.clone());
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let calls2 = Arc::clone(&calls);
+    let _: () = Polling{max: max, calls: Arc::clone(&calls)}.start();
+
+    Arbiter::handle().spawn(
+        YieldNow(false)
+            .and_then(move |_| {
+                clock.advance(Duration::from_secs(15));
+                YieldNow(false).map(move |_| clock)
+            })
+            .and_then(move |clock| {
+                assert_eq!(calls2.load(Ordering::Relaxed), 1);
+                // calls stop after 25 seconds
+                clock.advance(Duration::from_secs(60));
+                YieldNow(false)
+            })
+            .then(|_| {
+                Arbiter::system().send(msgs::SystemExit(0));
+                future::result(Ok(()))
+            }));
+
+    sys.run();
+    calls.load(Ordering::Relaxed)
+}
+
+#[test]
+fn test_for_duration() {
+    assert_eq!(run_polling(None), 2);
+    assert_eq!(run_polling(Some(1)), 1);
+}
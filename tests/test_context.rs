@@ -3,9 +3,10 @@ extern crate actix;
 extern crate futures;
 extern crate tokio_core;
 
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use futures::{future, Future};
-use tokio_core::reactor::Timeout;
+use tokio_core::reactor::{Interval, Timeout};
 use actix::prelude::*;
 use actix::msgs::SystemExit;
 
@@ -92,3 +93,1225 @@ fn test_run_after() {
 
     sys.run();
 }
+
+struct ChildActor;
+
+impl Actor for ChildActor {
+    type Context = Context<Self>;
+
+    fn started(&mut self, _: &mut Context<Self>) {
+        Arbiter::system().send(SystemExit(0));
+    }
+}
+
+struct ParentActor(Option<Address<ChildActor>>);
+
+impl Actor for ParentActor {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Context<Self>) {
+        self.0 = Some(ctx.start_actor(ChildActor));
+    }
+}
+
+#[test]
+fn test_start_actor() {
+    let sys = System::new("test");
+
+    let _addr: Address<_> = ParentActor(None).start();
+
+    sys.run();
+}
+
+struct Delayed;
+
+struct DelayActor;
+
+impl Actor for DelayActor {
+    type Context = Context<Self>;
+}
+
+impl ResponseType<Delayed> for DelayActor {
+    type Item = usize;
+    type Error = ();
+}
+
+impl Handler<Delayed> for DelayActor {
+    fn handle(&mut self, _: Delayed, _: &mut Context<Self>) -> Response<Self, Delayed> {
+        Self::reply_after(5, Duration::from_millis(50))
+    }
+}
+
+#[test]
+fn test_reply_after() {
+    let sys = System::new("test");
+
+    let addr: Address<_> = DelayActor.start();
+    let start = Instant::now();
+
+    Arbiter::handle().spawn(
+        addr.call_fut(Delayed).then(move |res| {
+            assert_eq!(res.unwrap(), Ok(5));
+            assert!(start.elapsed() >= Duration::from_millis(50));
+            Arbiter::system().send(SystemExit(0));
+            future::result(Ok(()))
+        }));
+
+    sys.run();
+}
+
+struct Order(&'static str);
+
+struct OrderActor(Arc<Mutex<Vec<&'static str>>>);
+
+impl Actor for OrderActor {
+    type Context = Context<Self>;
+}
+
+impl ResponseType<Order> for OrderActor {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<Order> for OrderActor {
+    fn handle(&mut self, msg: Order, ctx: &mut Context<Self>) -> Response<Self, Order> {
+        self.0.lock().unwrap().push(msg.0);
+        match msg.0 {
+            "first" => {
+                ctx.send_to_self(Order("low"));
+                ctx.send_to_self_high_priority(Order("high1"));
+                ctx.send_to_self_high_priority(Order("high2"));
+            }
+            "low" => {
+                Arbiter::system().send(SystemExit(0));
+            }
+            _ => (),
+        }
+        Self::empty()
+    }
+}
+
+#[test]
+fn test_send_to_self_ordering() {
+    let sys = System::new("test");
+    let order = Arc::new(Mutex::new(Vec::new()));
+
+    let addr: Address<_> = OrderActor(Arc::clone(&order)).start();
+    addr.send(Order("first"));
+    addr.send(Order("second"));
+    addr.send(Order("third"));
+
+    sys.run();
+    assert_eq!(*order.lock().unwrap(),
+               vec!["first", "high1", "high2", "second", "third", "low"]);
+}
+
+#[test]
+fn test_slow_handler_threshold() {
+    let sys = System::new("test");
+    let order = Arc::new(Mutex::new(Vec::new()));
+
+    // every handler is reported as slow, dispatch order stays the same
+    let order2 = Arc::clone(&order);
+    let addr: Address<_> = OrderActor::create(move |ctx| {
+        ctx.set_slow_handler_threshold(Duration::from_millis(0));
+        OrderActor(order2)
+    });
+    addr.send(Order("first"));
+    addr.send(Order("second"));
+
+    sys.run();
+    assert_eq!(*order.lock().unwrap(), vec!["first", "high1", "high2", "second", "low"]);
+}
+
+struct Step(&'static str);
+
+impl ResponseType<Step> for OrderActor {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<Step> for OrderActor {
+    fn handle(&mut self, msg: Step, ctx: &mut Context<Self>) -> Response<Self, Step> {
+        self.0.lock().unwrap().push(msg.0);
+        match msg.0 {
+            "start" => {
+                Timeout::new(Duration::from_millis(50), Arbiter::handle()).unwrap()
+                    .actfuture()
+                    .then(|_, act: &mut OrderActor, _: &mut Context<Self>| {
+                        act.0.lock().unwrap().push("future");
+                        fut::ok(())
+                    })
+                    .spawn(ctx);
+                ctx.exclusive(|act, _| act.0.lock().unwrap().push("exclusive"));
+            }
+            "next" => {
+                Arbiter::system().send(SystemExit(0));
+            }
+            _ => (),
+        }
+        Self::empty()
+    }
+}
+
+#[test]
+fn test_exclusive() {
+    let sys = System::new("test");
+    let order = Arc::new(Mutex::new(Vec::new()));
+
+    let addr: Address<_> = OrderActor(Arc::clone(&order)).start();
+    addr.send(Step("start"));
+    addr.send(Step("next"));
+
+    sys.run();
+    assert_eq!(*order.lock().unwrap(), vec!["start", "future", "exclusive", "next"]);
+}
+
+struct BusyActor(Arc<Mutex<Vec<&'static str>>>);
+
+impl Actor for BusyActor {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Context<Self>) {
+        // long lived items
+        ctx.add_stream(Interval::new(Duration::from_secs(3600), Arbiter::handle()).unwrap());
+        ctx.for_duration(Duration::from_secs(3600), Duration::from_secs(60), |_, _| ());
+        ctx.exclusive(|act, _| act.0.lock().unwrap().push("exclusive"));
+    }
+}
+
+impl ResponseType<()> for BusyActor {
+    type Item = ();
+    type Error = ();
+}
+
+impl StreamHandler<(), std::io::Error> for BusyActor {}
+
+impl Handler<(), std::io::Error> for BusyActor {
+    fn handle(&mut self, _: (), _: &mut Context<Self>) -> Response<Self, ()> {
+        Self::empty()
+    }
+}
+
+impl ResponseType<Step> for BusyActor {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<Step> for BusyActor {
+    fn handle(&mut self, msg: Step, _: &mut Context<Self>) -> Response<Self, Step> {
+        self.0.lock().unwrap().push(msg.0);
+        Arbiter::system().send(SystemExit(0));
+        Self::empty()
+    }
+}
+
+#[test]
+fn test_exclusive_skips_long_lived_items() {
+    let sys = System::new("test");
+    let order = Arc::new(Mutex::new(Vec::new()));
+
+    let addr: Address<_> = BusyActor(Arc::clone(&order)).start();
+    addr.send(Step("next"));
+
+    sys.run();
+    assert_eq!(*order.lock().unwrap(), vec!["exclusive", "next"]);
+}
+
+struct Compute(bool);
+
+struct ComputeActor;
+
+impl Actor for ComputeActor {
+    type Context = Context<Self>;
+}
+
+impl ResponseType<Compute> for ComputeActor {
+    type Item = usize;
+    type Error = ();
+}
+
+impl Handler<Compute> for ComputeActor {
+    fn handle(&mut self, msg: Compute, _: &mut Context<Self>) -> Response<Self, Compute> {
+        if msg.0 {
+            Ok(1).into()
+        } else {
+            let fut: Box<ActorFuture<Item=usize, Error=(), Actor=Self>> =
+                Box::new(fut::ok(2));
+            fut.into()
+        }
+    }
+}
+
+#[test]
+fn test_response_into() {
+    let sys = System::new("test");
+
+    let addr: Address<_> = ComputeActor.start();
+
+    Arbiter::handle().spawn(
+        addr.call_fut(Compute(true))
+            .join(addr.call_fut(Compute(false)))
+            .then(|res| {
+                let (sync, deferred) = res.unwrap();
+                assert_eq!(sync, Ok(1));
+                assert_eq!(deferred, Ok(2));
+                Arbiter::system().send(SystemExit(0));
+                future::result(Ok(()))
+            }));
+
+    sys.run();
+}
+
+struct GetCorrelationId;
+
+struct Inner;
+
+impl Actor for Inner {
+    type Context = Context<Self>;
+}
+
+impl ResponseType<GetCorrelationId> for Inner {
+    type Item = Option<String>;
+    type Error = ();
+}
+
+impl Handler<GetCorrelationId> for Inner {
+    fn handle(&mut self, _: GetCorrelationId, ctx: &mut Context<Self>)
+              -> Response<Self, GetCorrelationId>
+    {
+        Self::reply(ctx.correlation_id().map(|id| id.to_string()))
+    }
+}
+
+struct Outer(Address<Inner>);
+
+impl Actor for Outer {
+    type Context = Context<Self>;
+}
+
+impl ResponseType<GetCorrelationId> for Outer {
+    type Item = (Option<String>, Option<String>);
+    type Error = ();
+}
+
+impl Handler<GetCorrelationId> for Outer {
+    fn handle(&mut self, msg: GetCorrelationId, ctx: &mut Context<Self>)
+              -> Response<Self, GetCorrelationId>
+    {
+        let own = ctx.correlation_id().map(|id| id.to_string());
+        Self::async_reply(
+            ActorFuture::then(self.0.call(self, msg), move |res, _, _| fut::result(match res {
+                Ok(Ok(inner)) => Ok((own, inner)),
+                _ => Err(()),
+            })))
+    }
+}
+
+#[test]
+fn test_correlation_id() {
+    let sys = System::new("test");
+
+    let addr: Address<_> = Outer(Inner.start()).start();
+
+    Arbiter::handle().spawn(
+        addr.call_fut(GetCorrelationId)
+            .join(addr.call_fut(GetCorrelationId))
+            .then(|res| {
+                let (first, second) = res.unwrap();
+                let (outer, inner) = first.unwrap();
+                assert!(outer.is_some());
+                assert_eq!(outer, inner);
+
+                let (outer2, inner2) = second.unwrap();
+                assert!(outer2.is_some());
+                assert_eq!(outer2, inner2);
+                assert_ne!(outer, outer2);
+
+                Arbiter::system().send(SystemExit(0));
+                future::result(Ok(()))
+            }));
+
+    sys.run();
+}
+
+struct StreamActor(Arc<Mutex<Vec<Result<usize, usize>>>>);
+
+impl Actor for StreamActor {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Context<Self>) {
+        ctx.add_stream(futures::stream::iter_result(vec![Ok(1), Ok(2), Err(3)]));
+    }
+}
+
+impl ResponseType<usize> for StreamActor {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<usize, usize> for StreamActor {
+    fn handle(&mut self, _: usize, _: &mut Context<Self>) -> Response<Self, usize> {
+        unreachable!()
+    }
+}
+
+impl StreamHandler<usize, usize> for StreamActor {
+    fn handle_result(&mut self, item: Result<usize, usize>, _: &mut Context<Self>)
+                     -> Option<Response<Self, usize>>
+    {
+        self.0.lock().unwrap().push(item);
+        Some(Self::empty())
+    }
+
+    fn finished(&mut self, _: &mut Context<Self>) {
+        Arbiter::system().send(SystemExit(0));
+    }
+}
+
+#[test]
+fn test_stream_handle_result() {
+    let sys = System::new("test");
+
+    let items = Arc::new(Mutex::new(Vec::new()));
+    let _: () = StreamActor(Arc::clone(&items)).start();
+    sys.run();
+
+    assert_eq!(*items.lock().unwrap(), vec![Ok(1), Ok(2), Err(3)]);
+}
+
+struct SelfAddressed {
+    addr: Address<SelfAddressed>,
+    pings: usize,
+}
+
+struct SelfPing;
+
+impl Actor for SelfAddressed {
+    type Context = Context<Self>;
+}
+
+impl ResponseType<SelfPing> for SelfAddressed {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<SelfPing> for SelfAddressed {
+    fn handle(&mut self, _: SelfPing, _: &mut Context<Self>) -> Response<Self, SelfPing> {
+        self.pings += 1;
+        if self.pings == 2 {
+            Arbiter::system().send(SystemExit(0));
+        } else {
+            self.addr.send(SelfPing);
+        }
+        Self::empty()
+    }
+}
+
+#[test]
+fn test_create_with_context() {
+    let sys = System::new("test");
+
+    let addr = SelfAddressed::create_with_context(|ctx| {
+        let addr: Address<_> = ctx.address();
+        addr.send(SelfPing);
+        SelfAddressed{addr: addr, pings: 0}
+    });
+    assert!(addr.connected());
+
+    sys.run();
+}
+
+struct Command;
+struct GetStats;
+
+struct DeadlineActor(usize);
+
+impl Actor for DeadlineActor {
+    type Context = Context<Self>;
+}
+
+impl ResponseType<Command> for DeadlineActor {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<Command> for DeadlineActor {
+    fn handle(&mut self, _: Command, _: &mut Context<Self>) -> Response<Self, Command> {
+        self.0 += 1;
+        Self::empty()
+    }
+}
+
+impl ResponseType<GetStats> for DeadlineActor {
+    type Item = (usize, usize);
+    type Error = ();
+}
+
+impl Handler<GetStats> for DeadlineActor {
+    fn handle(&mut self, _: GetStats, ctx: &mut Context<Self>) -> Response<Self, GetStats> {
+        Self::reply((self.0, ctx.dropped_messages()))
+    }
+}
+
+#[test]
+fn test_send_deadline() {
+    let sys = System::new("test");
+
+    let addr: SyncAddress<_> = DeadlineActor(0).start();
+    let now = Instant::now();
+    addr.send_deadline(Command, now - Duration::from_millis(10));
+    addr.send_deadline(Command, now + Duration::from_secs(10));
+    addr.send_deadline(Command, now - Duration::from_millis(10));
+
+    Arbiter::handle().spawn(
+        addr.call_fut(GetStats).then(|res| {
+            assert_eq!(res.unwrap(), Ok((1, 2)));
+            Arbiter::system().send(SystemExit(0));
+            future::result(Ok(()))
+        }));
+
+    sys.run();
+}
+
+struct SpawnFuture;
+
+impl Actor for SpawnFuture {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Context<Self>) {
+        ctx.spawn_future(
+            Timeout::new(Duration::from_millis(10), Arbiter::handle()).unwrap()
+                .then(|_| {
+                    Arbiter::system().send(SystemExit(0));
+                    Ok(())
+                }));
+        ctx.stop();
+    }
+}
+
+#[test]
+fn test_spawn_future() {
+    let sys = System::new("test");
+
+    let _: () = SpawnFuture.start();
+
+    // future keeps running after actor stops
+    sys.run();
+}
+
+struct WithHandle;
+
+impl Actor for WithHandle {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Context<Self>) {
+        let timeout = ctx.with_handle(
+            |handle| Timeout::new(Duration::from_millis(10), handle).unwrap());
+        timeout.actfuture()
+            .then(|_, _: &mut WithHandle, _: &mut Context<Self>| {
+                Arbiter::system().send(SystemExit(0));
+                actix::fut::ok(())
+            })
+            .wait(ctx);
+    }
+}
+
+#[test]
+fn test_with_handle() {
+    let sys = System::new("test");
+
+    let _: () = WithHandle.start();
+
+    sys.run();
+}
+
+struct Withdraw(u32);
+
+struct Account(u32);
+
+impl Actor for Account {
+    type Context = Context<Self>;
+}
+
+impl ResponseType<Withdraw> for Account {
+    type Item = u32;
+    type Error = &'static str;
+}
+
+impl Handler<Withdraw> for Account {
+    fn validate(&self, msg: &Withdraw) -> Result<(), &'static str> {
+        if msg.0 > self.0 {
+            Err("insufficient funds")
+        } else {
+            Ok(())
+        }
+    }
+
+    fn handle(&mut self, msg: Withdraw, _: &mut Context<Self>) -> Response<Self, Withdraw> {
+        self.0 -= msg.0;
+        Self::reply(self.0)
+    }
+}
+
+#[test]
+fn test_validate() {
+    let sys = System::new("test");
+
+    let addr: Address<_> = Account(100).start();
+    let saddr: SyncAddress<_> = Account(10).start();
+
+    Arbiter::handle().spawn(
+        addr.call_fut(Withdraw(500))
+            .then(move |res| {
+                assert_eq!(res.unwrap(), Err("insufficient funds"));
+                addr.call_fut(Withdraw(30))
+            })
+            .then(move |res| {
+                assert_eq!(res.unwrap(), Ok(70));
+                saddr.call_fut(Withdraw(20))
+            })
+            .then(|res| {
+                assert_eq!(res.unwrap(), Err("insufficient funds"));
+                Arbiter::system().send(SystemExit(0));
+                future::result(Ok(()))
+            }));
+
+    sys.run();
+}
+
+struct Feed {
+    items: Arc<Mutex<usize>>,
+    stream: Option<StreamHandle>,
+}
+
+struct GetItems;
+
+impl Actor for Feed {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Context<Self>) {
+        let interval = Interval::new(Duration::from_millis(10), Arbiter::handle()).unwrap();
+        self.stream = Some(ctx.add_stream(interval));
+    }
+}
+
+impl ResponseType<()> for Feed {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<(), std::io::Error> for Feed {
+    fn handle(&mut self, _: (), ctx: &mut Context<Self>) -> Response<Self, ()> {
+        let mut items = self.items.lock().unwrap();
+        *items += 1;
+        if *items == 2 {
+            let handle = self.stream.take().unwrap();
+            assert!(ctx.close_stream(handle));
+            assert!(!ctx.close_stream(handle));
+        }
+        Self::empty()
+    }
+}
+
+impl StreamHandler<(), std::io::Error> for Feed {}
+
+impl ResponseType<GetItems> for Feed {
+    type Item = usize;
+    type Error = ();
+}
+
+impl Handler<GetItems> for Feed {
+    fn handle(&mut self, _: GetItems, _: &mut Context<Self>) -> Response<Self, GetItems> {
+        Self::reply(*self.items.lock().unwrap())
+    }
+}
+
+#[test]
+fn test_close_stream() {
+    let sys = System::new("test");
+
+    let items = Arc::new(Mutex::new(0));
+    let addr: Address<_> = Feed{items: Arc::clone(&items), stream: None}.start();
+
+    Arbiter::handle().spawn(
+        Timeout::new(Duration::from_millis(100), Arbiter::handle()).unwrap()
+            .then(move |_| addr.call_fut(GetItems))
+            .then(|res| {
+                // actor is still running, but stream is closed
+                assert_eq!(res.unwrap(), Ok(2));
+                Arbiter::system().send(SystemExit(0));
+                future::result(Ok(()))
+            }));
+
+    sys.run();
+    assert_eq!(*items.lock().unwrap(), 2);
+}
+
+struct Die;
+
+struct Target;
+
+impl Actor for Target {
+    type Context = Context<Self>;
+}
+
+impl ResponseType<Die> for Target {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<Die> for Target {
+    fn handle(&mut self, _: Die, ctx: &mut Context<Self>) -> Response<Self, Die> {
+        ctx.stop();
+        Self::empty()
+    }
+}
+
+struct Watcher {
+    watched: Address<Target>,
+    ignored: Address<Target>,
+    terminated: Arc<Mutex<Vec<&'static str>>>,
+}
+
+impl Actor for Watcher {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Context<Self>) {
+        ctx.watch(&self.watched);
+        let handle = ctx.watch(&self.ignored);
+        assert!(ctx.unwatch(handle));
+
+        self.watched.send(Die);
+        self.ignored.send(Die);
+    }
+}
+
+impl ResponseType<msgs::Terminated> for Watcher {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<msgs::Terminated> for Watcher {
+    fn handle(&mut self, msg: msgs::Terminated, _: &mut Context<Self>)
+              -> Response<Self, msgs::Terminated>
+    {
+        self.terminated.lock().unwrap().push(msg.actor_type);
+        Self::empty()
+    }
+}
+
+#[test]
+fn test_watch() {
+    let sys = System::new("test");
+    let terminated = Arc::new(Mutex::new(Vec::new()));
+
+    let _: () = Watcher{
+        watched: Target.start(),
+        ignored: Target.start(),
+        terminated: Arc::clone(&terminated)}.start();
+
+    Arbiter::handle().spawn(
+        Timeout::new(Duration::from_millis(50), Arbiter::handle()).unwrap()
+            .then(|_| {
+                Arbiter::system().send(SystemExit(0));
+                future::result(Ok(()))
+            }));
+    sys.run();
+
+    assert_eq!(*terminated.lock().unwrap(), vec!["test_context::Target"]);
+}
+
+struct SyncWatcher(SyncAddress<Target>, Arc<Mutex<Vec<&'static str>>>);
+
+impl Actor for SyncWatcher {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Context<Self>) {
+        ctx.watch(&self.0);
+        self.0.send(Die);
+    }
+}
+
+impl ResponseType<msgs::Terminated> for SyncWatcher {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<msgs::Terminated> for SyncWatcher {
+    fn handle(&mut self, msg: msgs::Terminated, _: &mut Context<Self>)
+              -> Response<Self, msgs::Terminated>
+    {
+        self.1.lock().unwrap().push(msg.actor_type);
+        Arbiter::system().send(SystemExit(0));
+        Self::empty()
+    }
+}
+
+#[test]
+fn test_watch_sync_address() {
+    let sys = System::new("test");
+    let terminated = Arc::new(Mutex::new(Vec::new()));
+
+    // watched actor runs in other arbiter
+    let arb = Arbiter::new(None);
+    let terminated2 = Arc::clone(&terminated);
+    Arbiter::handle().spawn(
+        arb.call_fut(msgs::StartActor::new(|_| Target))
+            .then(move |res| {
+                let target = res.unwrap().unwrap();
+                let _: () = SyncWatcher(target, terminated2).start();
+                Ok(())
+            }));
+    sys.run();
+
+    assert_eq!(*terminated.lock().unwrap(), vec!["test_context::Target"]);
+}
+
+struct GetLogContext;
+
+struct Logged;
+
+impl Actor for Logged {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Context<Self>) {
+        assert!(!ctx.log_context().contains_key("message"));
+    }
+}
+
+impl ResponseType<GetLogContext> for Logged {
+    type Item = std::collections::HashMap<&'static str, String>;
+    type Error = ();
+}
+
+impl Handler<GetLogContext> for Logged {
+    fn handle(&mut self, _: GetLogContext, ctx: &mut Context<Self>)
+              -> Response<Self, GetLogContext>
+    {
+        Self::reply(ctx.log_context())
+    }
+}
+
+#[test]
+fn test_log_context() {
+    let sys = System::new("test");
+
+    let addr: Address<_> = Logged.start();
+    Arbiter::handle().spawn(
+        addr.call_fut(GetLogContext).then(|res| {
+            let fields = res.unwrap().unwrap();
+            assert_eq!(fields["actor"], "test_context::Logged");
+            assert_eq!(fields["arbiter"], "test");
+            assert!(fields["message"].ends_with("GetLogContext"));
+
+            Arbiter::system().send(SystemExit(0));
+            future::result(Ok(()))
+        }));
+    sys.run();
+}
+
+struct Open;
+struct Ping;
+
+struct Fsm;
+
+impl Actor for Fsm {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Context<Self>) {
+        ctx.become_(Closed);
+    }
+}
+
+impl ResponseType<Open> for Fsm {
+    type Item = ();
+    type Error = ();
+}
+
+impl ResponseType<Ping> for Fsm {
+    type Item = usize;
+    type Error = &'static str;
+}
+
+impl Handler<Open> for Fsm {
+    fn handle(&mut self, msg: Open, ctx: &mut Context<Self>) -> Response<Self, Open> {
+        ctx.dispatch(self, msg).unwrap_or_else(|_| Self::reply_error(()))
+    }
+}
+
+impl Handler<Ping> for Fsm {
+    fn handle(&mut self, msg: Ping, ctx: &mut Context<Self>) -> Response<Self, Ping> {
+        ctx.dispatch(self, msg).unwrap_or_else(|_| Self::reply_error("unhandled"))
+    }
+}
+
+struct Closed;
+
+impl Behavior<Fsm> for Closed {
+    fn handlers(handlers: &mut Handlers<Fsm, Self>) {
+        handlers.add::<Open>();
+    }
+}
+
+impl BehaviorHandler<Fsm, Open> for Closed {
+    fn handle(&mut self, _: &mut Fsm, _: Open, ctx: &mut Context<Fsm>) -> Response<Fsm, Open> {
+        ctx.become_(Opened(0));
+        Fsm::empty()
+    }
+}
+
+struct Opened(usize);
+
+impl Behavior<Fsm> for Opened {
+    fn handlers(handlers: &mut Handlers<Fsm, Self>) {
+        handlers.add::<Ping>();
+    }
+}
+
+impl BehaviorHandler<Fsm, Ping> for Opened {
+    fn handle(&mut self, _: &mut Fsm, _: Ping, _: &mut Context<Fsm>) -> Response<Fsm, Ping> {
+        self.0 += 1;
+        Fsm::reply(self.0)
+    }
+}
+
+#[test]
+fn test_become() {
+    let sys = System::new("test");
+    let results = Arc::new(Mutex::new(Vec::new()));
+    let results2 = Arc::clone(&results);
+
+    let addr: Address<_> = Fsm.start();
+    let ping1 = addr.call_fut(Ping);
+    addr.send(Open);
+    let ping2 = addr.call_fut(Ping);
+    let ping3 = addr.call_fut(Ping);
+    let open = addr.call_fut(Open);
+
+    Arbiter::handle().spawn(
+        ping1.join4(ping2, ping3, open)
+            .then(move |res| {
+                *results2.lock().unwrap() = vec![res.unwrap()];
+                Arbiter::system().send(SystemExit(0));
+                Ok(())
+            }));
+    sys.run();
+
+    // messages before switch are handled by previous behavior
+    let results = results.lock().unwrap();
+    assert_eq!(results[0], (Err("unhandled"), Ok(1), Ok(2), Err(())));
+}
+
+struct Work;
+
+struct Bulk(ActorPriority, Arc<Mutex<usize>>);
+
+impl Actor for Bulk {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Context<Self>) {
+        ctx.set_priority(self.0);
+        assert_eq!(ctx.priority(), self.0);
+    }
+}
+
+impl ResponseType<Work> for Bulk {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<Work> for Bulk {
+    fn handle(&mut self, _: Work, _: &mut Context<Self>) -> Response<Self, Work> {
+        *self.1.lock().unwrap() += 1;
+        Self::empty()
+    }
+}
+
+struct Check;
+
+struct ControlPlane(Arc<Mutex<usize>>, Arc<Mutex<Option<usize>>>);
+
+impl Actor for ControlPlane {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Context<Self>) {
+        ctx.set_priority(ActorPriority::High);
+    }
+}
+
+impl ResponseType<Check> for ControlPlane {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<Check> for ControlPlane {
+    fn handle(&mut self, _: Check, _: &mut Context<Self>) -> Response<Self, Check> {
+        *self.1.lock().unwrap() = Some(*self.0.lock().unwrap());
+        Arbiter::system().send(SystemExit(0));
+        Self::empty()
+    }
+}
+
+/// Number of bulk messages handled before control message
+fn handled_before_control(priority: ActorPriority) -> usize {
+    let sys = System::new("test");
+    let handled = Arc::new(Mutex::new(0));
+    let seen = Arc::new(Mutex::new(None));
+
+    let bulk: Address<_> = Bulk(priority, Arc::clone(&handled)).start();
+    let control: Address<_> = ControlPlane(Arc::clone(&handled), Arc::clone(&seen)).start();
+    for _ in 0..100 {
+        bulk.send(Work);
+    }
+    control.send(Check);
+    sys.run();
+
+    let seen = seen.lock().unwrap().unwrap();
+    seen
+}
+
+#[test]
+fn test_priority() {
+    // bulk worker drains its mailbox before control message
+    assert_eq!(handled_before_control(ActorPriority::Normal), 100);
+
+    // low priority worker yields to control actor
+    assert!(handled_before_control(ActorPriority::Low) <= 1);
+}
+
+struct Trigger(Address<Bulk>, Address<ControlPlane>);
+
+impl Actor for Trigger {
+    type Context = Context<Self>;
+}
+
+struct Burst;
+
+impl ResponseType<Burst> for Trigger {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<Burst> for Trigger {
+    fn handle(&mut self, _: Burst, _: &mut Context<Self>) -> Response<Self, Burst> {
+        for _ in 0..100 {
+            self.0.send(Work);
+        }
+        self.1.send(Check);
+        Self::empty()
+    }
+}
+
+#[test]
+fn test_priority_inversion() {
+    let sys = System::new("test");
+    let handled = Arc::new(Mutex::new(0));
+    let seen = Arc::new(Mutex::new(None));
+
+    // running bulk worker gets its messages before control actor
+    let bulk: Address<_> = Bulk(ActorPriority::Normal, Arc::clone(&handled)).start();
+    let control: Address<_> = ControlPlane(Arc::clone(&handled), Arc::clone(&seen)).start();
+    let trigger: Address<_> = Trigger(bulk, control).start();
+    trigger.send(Burst);
+    sys.run();
+
+    // high priority actor is served first
+    assert_eq!(seen.lock().unwrap().unwrap(), 0);
+}
+
+struct Double(u32);
+
+struct Doubler;
+
+impl Actor for Doubler {
+    type Context = Context<Self>;
+}
+
+impl ResponseType<Double> for Doubler {
+    type Item = u32;
+    type Error = &'static str;
+}
+
+impl Handler<Double> for Doubler {
+    fn handle(&mut self, msg: Double, _: &mut Context<Self>) -> Response<Self, Double> {
+        if msg.0 % 2 == 0 {
+            Self::reply(msg.0 * 2)
+        } else {
+            Self::reply_error("odd")
+        }
+    }
+}
+
+impl ResponseType<Die> for Doubler {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<Die> for Doubler {
+    fn handle(&mut self, _: Die, ctx: &mut Context<Self>) -> Response<Self, Die> {
+        ctx.stop();
+        Self::empty()
+    }
+}
+
+type Results = Arc<Mutex<Vec<Result<u32, actix::CallError<&'static str>>>>>;
+
+struct Requester {
+    doubler: Address<Doubler>,
+    results: Results,
+}
+
+impl Actor for Requester {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Context<Self>) {
+        ctx.request(&self.doubler, Double(2))
+            .and_then(|item, act: &mut Self, ctx: &mut Context<Self>| {
+                act.results.lock().unwrap().push(Ok(item));
+                ctx.request(&act.doubler, Double(item + 1))
+            })
+            .then(|res, act: &mut Self, _: &mut Context<Self>| {
+                act.results.lock().unwrap().push(res);
+                act.doubler.send(Die);
+                Timeout::new(Duration::from_millis(50), Arbiter::handle()).unwrap()
+                    .actfuture()
+                    .then(|_, act: &mut Self, ctx: &mut Context<Self>|
+                          ctx.request(&act.doubler, Double(2)))
+            })
+            .then(|res, act: &mut Self, _: &mut Context<Self>| {
+                act.results.lock().unwrap().push(res);
+                Arbiter::system().send(SystemExit(0));
+                actix::fut::ok(())
+            })
+            .wait(ctx);
+    }
+}
+
+#[test]
+fn test_request() {
+    let sys = System::new("test");
+    let results = Arc::new(Mutex::new(Vec::new()));
+
+    let _: () = Requester{doubler: Doubler.start(), results: Arc::clone(&results)}.start();
+    sys.run();
+
+    assert_eq!(*results.lock().unwrap(),
+               vec![Ok(4), Err(actix::CallError::Handler("odd")),
+                    Err(actix::CallError::MailboxClosed)]);
+}
+
+struct Add(u32);
+
+impl DynamicMessage for Add {
+    type Item = u32;
+    type Error = ();
+}
+
+struct AddOnce(u32);
+
+impl DynamicMessage for AddOnce {
+    type Item = u32;
+    type Error = ();
+}
+
+struct Plugin(u32);
+
+impl Actor for Plugin {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Context<Self>) {
+        ctx.handle(|act: &mut Plugin, msg: Add, _: &mut Context<Plugin>| {
+                act.0 += msg.0;
+                Ok(act.0)
+            })
+            .handle(|act: &mut Plugin, msg: AddOnce, ctx: &mut Context<Plugin>| {
+                // handler deregisters itself
+                assert!(ctx.unhandle::<AddOnce>());
+                act.0 += msg.0;
+                Ok(act.0)
+            });
+    }
+}
+
+#[test]
+fn test_dynamic_handlers() {
+    let sys = System::new("test");
+    let addr: SyncAddress<_> = Plugin(0).start();
+
+    addr.send_dynamic(Add(1));
+    let addr2 = addr.clone();
+    let addr3 = addr.clone();
+    Arbiter::handle().spawn(
+        addr.call_dynamic(Add(2))
+            .then(move |res| {
+                assert_eq!(res.unwrap(), Ok(3));
+                addr2.call_dynamic(AddOnce(10))
+            })
+            .then(move |res| {
+                assert_eq!(res.unwrap(), Ok(13));
+                addr3.call_dynamic(AddOnce(10))
+            })
+            .then(|res| {
+                // handler is removed, response is canceled
+                assert!(res.is_err());
+                Arbiter::system().send(SystemExit(0));
+                Ok(())
+            }));
+    sys.run();
+}
+
+struct Limited {
+    running: usize,
+    peak: usize,
+    log: Arc<Mutex<Vec<u32>>>,
+}
+
+fn sleep(ms: u64) -> Box<ActorFuture<Item=(), Error=(), Actor=Limited>> {
+    Box::new(
+        fut::wrap_future::<_, Limited>(
+            Timeout::new(Duration::from_millis(ms), Arbiter::handle()).unwrap())
+            .map_err(|_, _, _| ()))
+}
+
+impl Actor for Limited {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Context<Self>) {
+        for n in 0..5 {
+            ctx.spawn_limited(
+                fut::ok(()).map(move |_, act: &mut Self, _: &mut Context<Self>| {
+                    act.log.lock().unwrap().push(n);
+                    act.running += 1;
+                    act.peak = std::cmp::max(act.peak, act.running);
+                })
+                .and_then(|_, _, _| sleep(20))
+                .map(|_, act: &mut Self, _: &mut Context<Self>| act.running -= 1),
+                "io", 2);
+        }
+        // other key is not limited by "io" futures
+        ctx.spawn_limited(
+            sleep(5).map(|_, act: &mut Self, _: &mut Context<Self>| {
+                act.log.lock().unwrap().push(10);
+            }),
+            "other", 1);
+
+        ctx.run_later(Duration::from_millis(200), |act, _| {
+            assert_eq!(act.peak, 2);
+            assert_eq!(act.running, 0);
+            Arbiter::system().send(SystemExit(0));
+        });
+    }
+}
+
+#[test]
+fn test_spawn_limited() {
+    let sys = System::new("test");
+    let log = Arc::new(Mutex::new(Vec::new()));
+
+    let _: () = Limited{running: 0, peak: 0, log: Arc::clone(&log)}.start();
+    sys.run();
+
+    // queued futures are started in order
+    assert_eq!(*log.lock().unwrap(), vec![0, 1, 10, 2, 3, 4]);
+}
@@ -0,0 +1,234 @@
+extern crate actix;
+extern crate futures;
+extern crate tokio_core;
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use futures::{future, Future};
+use tokio_core::reactor::Timeout;
+use actix::prelude::*;
+use actix::actors::events::{LifecycleEvent, Subscribe, SystemEvents};
+use actix::actors::subscription::SubscribeTarget;
+
+struct Watcher(Arc<Mutex<Vec<LifecycleEvent>>>);
+
+impl Actor for Watcher {
+    type Context = Context<Self>;
+}
+
+impl ResponseType<LifecycleEvent> for Watcher {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<LifecycleEvent> for Watcher {
+    fn handle(&mut self, msg: LifecycleEvent, _: &mut Context<Self>)
+              -> Response<Self, LifecycleEvent>
+    {
+        self.0.lock().unwrap().push(msg);
+        Self::empty()
+    }
+}
+
+struct Die;
+
+struct Worker;
+
+impl Actor for Worker {
+    type Context = Context<Self>;
+}
+
+impl Supervised for Worker {}
+
+impl ResponseType<Die> for Worker {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<Die> for Worker {
+    fn handle(&mut self, _: Die, ctx: &mut Context<Self>) -> Response<Self, Die> {
+        ctx.stop();
+        Self::empty()
+    }
+}
+
+struct Named;
+
+impl Actor for Named {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Context<Self>) {
+        ctx.stop();
+    }
+
+    fn name() -> Option<&'static str> {
+        Some("named")
+    }
+}
+
+#[test]
+fn test_lifecycle_events() {
+    let sys = System::new("test");
+    let events = Arc::new(Mutex::new(Vec::new()));
+
+    let watcher: SyncAddress<_> = Watcher(Arc::clone(&events)).start();
+    Arbiter::handle().spawn(
+        Arbiter::system_registry().get::<SystemEvents>()
+            .call_fut(Subscribe(watcher.subscriber()))
+            .then(|_| {
+                let (addr, _) = Supervisor::start(false, |_| Worker);
+                addr.send(Die);
+
+                let worker: Address<_> = Worker.start();
+                worker.send(Die);
+
+                let _: () = Named.start();
+
+                let arb = Arbiter::new(Some("events".to_owned()));
+                arb.send(msgs::StopArbiter(0));
+
+                // supervisor stops once all its addresses are dropped
+                Timeout::new(Duration::from_millis(100), Arbiter::handle()).unwrap()
+                    .map(move |_| drop(addr))
+            })
+            .then(|_| {
+                Arbiter::system().send(msgs::SystemExit(0));
+                Ok(())
+            }));
+    sys.run();
+
+    let events = events.lock().unwrap();
+    let count = |ev: LifecycleEvent| events.iter().filter(|e| **e == ev).count();
+    let name = "test_events::Worker";
+
+    // supervised actor keeps running after restart
+    assert_eq!(count(LifecycleEvent::ActorStarted(name)), 2);
+    assert_eq!(count(LifecycleEvent::ActorRestarted(name)), 1);
+    assert_eq!(count(LifecycleEvent::ActorStopped(name)), 1);
+
+    // actor name is preferred over type name
+    assert_eq!(count(LifecycleEvent::ActorStarted("named")), 1);
+    assert_eq!(count(LifecycleEvent::ActorStopped("named")), 1);
+
+    let arbiter = events.iter().filter_map(|ev| match *ev {
+        LifecycleEvent::ArbiterRegistered{ref id, ref name} => Some((id.clone(), name.clone())),
+        _ => None,
+    }).next().unwrap();
+    assert_eq!(arbiter.1, Some("events".to_owned()));
+    assert_eq!(count(LifecycleEvent::ArbiterUnregistered{id: arbiter.0, name: arbiter.1}), 1);
+}
+
+#[test]
+fn test_subscribe_all() {
+    let sys = System::new("test");
+    let events = Arc::new(Mutex::new(Vec::new()));
+
+    let watcher: SyncAddress<_> = Watcher(Arc::clone(&events)).start();
+    let subscription = watcher.subscribe_all(vec![SubscribeTarget::events()]);
+    assert_eq!(subscription.len(), 1);
+
+    Arbiter::handle().spawn(
+        Timeout::new(Duration::from_millis(100), Arbiter::handle()).unwrap()
+            .then(|_| {
+                let _: () = Named.start();
+                Timeout::new(Duration::from_millis(100), Arbiter::handle()).unwrap()
+            })
+            .then(move |_| {
+                subscription.unsubscribe();
+                let _: () = Named.start();
+                Timeout::new(Duration::from_millis(100), Arbiter::handle()).unwrap()
+            })
+            .then(|_| {
+                Arbiter::system().send(msgs::SystemExit(0));
+                Ok(())
+            }));
+    sys.run();
+
+    // events after unsubscribe are not delivered
+    let events = events.lock().unwrap();
+    assert_eq!(events.iter().filter(|e| **e == LifecycleEvent::ActorStarted("named")).count(), 1);
+    assert_eq!(events.iter().filter(|e| **e == LifecycleEvent::ActorStopped("named")).count(), 1);
+}
+
+#[test]
+fn test_shutdown_sequence() {
+    let sys = System::new("test");
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let hook_done = Arc::new(Mutex::new(false));
+
+    let done = Arc::clone(&hook_done);
+    System::add_shutdown_hook(move |code| {
+        assert_eq!(code, 5);
+        Timeout::new(Duration::from_millis(50), Arbiter::handle()).unwrap()
+            .then(move |_| {
+                *done.lock().unwrap() = true;
+                Ok(())
+            })
+    });
+
+    let watcher: SyncAddress<_> = Watcher(Arc::clone(&events)).start();
+    Arbiter::handle().spawn(
+        Arbiter::system_registry().get::<SystemEvents>()
+            .call_fut(Subscribe(watcher.subscriber()))
+            .then(|_| {
+                Arbiter::system().send(msgs::SystemExit(5));
+                Ok(())
+            }));
+    assert_eq!(sys.run(), 5);
+
+    // subscribers handle shutdown event and hooks complete before exit
+    assert!(events.lock().unwrap().contains(&LifecycleEvent::SystemShutdown(5)));
+    assert!(*hook_done.lock().unwrap());
+}
+
+#[test]
+fn test_shutdown_timeout() {
+    let sys = System::new("test");
+
+    System::set_shutdown_timeout(Duration::from_millis(50));
+    System::add_shutdown_hook(|_| future::empty::<(), ()>());
+
+    Arbiter::system().send(msgs::SystemExit(0));
+    assert_eq!(sys.run(), 0);
+}
+
+/// Actor that records how it is identified in user facing output
+struct Reporter(Arc<Mutex<Vec<String>>>);
+
+impl Actor for Reporter {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Context<Self>) {
+        let addr: Address<Self> = ctx.address();
+        let sync_addr: SyncAddress<Self> = ctx.address();
+        let mut names = self.0.lock().unwrap();
+        names.push(ctx.log_context()["actor"].clone());
+        names.push(format!("{:?}", addr));
+        names.push(format!("{:?}", sync_addr));
+        Arbiter::system().send(msgs::SystemExit(0));
+    }
+
+    fn name() -> Option<&'static str> {
+        Some("reporter")
+    }
+}
+
+#[test]
+fn test_actor_name() {
+    let sys = System::new("test");
+    let names = Arc::new(Mutex::new(Vec::new()));
+
+    let worker: Address<_> = Worker.start();
+    let _: () = Reporter(Arc::clone(&names)).start();
+    sys.run();
+
+    // name is used in log fields and address formatting
+    let names = names.lock().unwrap();
+    assert_eq!(names[0], "reporter");
+    assert!(names[1].starts_with("Address<reporter>(id: "));
+    assert!(names[2].starts_with("SyncAddress<reporter>(id: "));
+
+    // actor without name falls back to type name
+    assert_eq!(Worker::name(), None);
+    assert!(format!("{:?}", worker).starts_with("Address<test_events::Worker>"));
+}
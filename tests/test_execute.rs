@@ -1,9 +1,20 @@
 extern crate actix;
 extern crate futures;
-use futures::{future, Future};
+extern crate tokio_core;
+#[cfg(feature="serde")]
+extern crate serde_json;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use futures::{future, Future, Stream};
+use tokio_core::reactor::{Core, Interval, Timeout};
 
 use actix::prelude::*;
-use actix::msgs::{Execute, SystemExit};
+use actix::{ArbiterStartError, CallError, SpawnError};
+use actix::msgs::{ActorCount, DeferSystemExit, Execute, ExecuteAsync, ForwardTo, FreezeSystem,
+                  GetRemote, StopArbiter, SystemExit, SystemArbiterUpdated, ThawSystem};
 
 
 #[test]
@@ -29,6 +40,45 @@ fn test_execute() {
     sys.run();
 }
 
+#[test]
+fn test_execute_error() {
+    let sys = System::new("test");
+
+    let addr = Arbiter::new(None);
+
+    Arbiter::handle().spawn(
+        addr.call_fut(Execute::new(|| -> Result<(), _> {
+            Err("failed")
+        })).then(|res| {
+            Arbiter::system().send(SystemExit(0));
+
+            assert_eq!(res.unwrap(), Err("failed"));
+            future::result(Ok(()))
+        }));
+
+    sys.run();
+}
+
+#[test]
+fn test_execute_future() {
+    let sys = System::new("test");
+
+    let addr = Arbiter::new(None);
+
+    Arbiter::handle().spawn(
+        addr.call_fut(Execute::new(|| {
+            Timeout::new(Duration::from_millis(10), Arbiter::handle()).unwrap()
+                .then(|_| Ok::<_, ()>(Arbiter::name()))
+        })).then(|res| {
+            Arbiter::system().send(SystemExit(0));
+
+            assert_ne!(res.unwrap().unwrap(), "test");
+            future::result(Ok(()))
+        }));
+
+    sys.run();
+}
+
 #[test]
 fn test_system_execute() {
     let sys = System::new("test");
@@ -53,3 +103,1000 @@ fn test_system_execute() {
 
     sys.run();
 }
+
+#[test]
+fn test_get_remote() {
+    let sys = System::new("test");
+
+    let addr = Arbiter::new(Some("remote".to_owned()));
+    let name = addr.call_fut(Execute::new(|| -> Result<String, ()> { Ok(Arbiter::name()) }));
+    Arbiter::handle().spawn(
+        addr.call_fut(GetRemote).join(name)
+            .then(|res| {
+                let (remote, name) = res.unwrap();
+                let (remote, name) = (remote.unwrap(), name.unwrap());
+
+                // spawn future onto arbiter's event loop from other thread
+                std::thread::spawn(move || {
+                    remote.spawn(move |_| {
+                        let code = if Arbiter::name() == name { 0 } else { 1 };
+                        Arbiter::system().send(SystemExit(code));
+                        Ok(())
+                    });
+                });
+                Ok(())
+            }));
+
+    assert_eq!(sys.run(), 0);
+}
+
+#[test]
+fn test_shutdown_all() {
+    let sys = System::new("test");
+
+    let addr = Arbiter::new(None);
+    addr.send(Execute::new(|| -> Result<(), ()> {
+        Arbiter::shutdown_all(3);
+        Ok(())
+    }));
+
+    assert_eq!(sys.run(), 3);
+}
+
+#[test]
+fn test_arbiter_with_handle() {
+    let sys = System::new("test");
+
+    let system = Arbiter::system_info();
+    let (tx, rx) = std::sync::mpsc::channel();
+    let (stop_tx, stop_rx) = futures::sync::oneshot::channel::<()>();
+    let thread = std::thread::spawn(move || {
+        // bare event loop, thread does not belong to any system
+        let mut core = Core::new().unwrap();
+        let addr = Arbiter::builder()
+            .name("embedded").start_with_handle(core.handle(), system);
+        tx.send((addr, Arbiter::name(), Arbiter::system_name())).unwrap();
+        let _ = core.run(stop_rx);
+    });
+    let (addr, name, sys_name) = rx.recv().unwrap();
+    assert_ne!(name, "test");
+    assert_eq!(sys_name, "test");
+
+    Arbiter::handle().spawn(
+        Timeout::new(Duration::from_millis(200), Arbiter::handle()).unwrap()
+            .then(move |_| {
+                // arbiter is registered within system
+                match System::arbiter_for_name("embedded") {
+                    Some(arb) => assert_eq!(arb.id(), addr.id()),
+                    None => assert!(false, "something is wrong"),
+                }
+                addr.call_fut(Execute::new(|| -> Result<_, ()> { Ok(Arbiter::name()) }))
+            })
+            .then(move |res| {
+                match res {
+                    Ok(Ok(arb_name)) => assert_eq!(arb_name, name),
+                    _ => assert!(false, "something is wrong"),
+                }
+                Arbiter::system().send(SystemExit(0));
+                Ok(())
+            }));
+    sys.run();
+
+    let _ = stop_tx.send(());
+    thread.join().unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Arbiter is running in current thread already")]
+fn test_arbiter_with_handle_in_arbiter() {
+    let _sys = System::new("test");
+
+    let core = Core::new().unwrap();
+    Arbiter::new_with_handle(core.handle(), Arbiter::system_info());
+}
+
+#[test]
+#[should_panic(expected = "Execute::exec() blocks arbiter's event loop")]
+fn test_execute_exec_in_arbiter() {
+    let _sys = System::new("test");
+
+    // future would wait for event loop of this thread forever
+    let _ = Execute::new(|| Timeout::new(Duration::from_millis(10), Arbiter::handle()).unwrap())
+        .exec();
+}
+
+#[test]
+#[should_panic(expected = "Arbiter is torn down")]
+fn test_arbiter_torn_down() {
+    let sys = System::new("test");
+
+    Arbiter::handle().spawn_fn(|| {
+        Arbiter::system().send(SystemExit(0));
+        future::ok(())
+    });
+    sys.run();
+
+    Arbiter::handle();
+}
+
+#[test]
+fn test_arbiter_pool_weights() {
+    let sys = System::new("test");
+
+    let mut pool = ArbiterPool::with_weights(vec![3, 1]);
+    assert_eq!(pool.len(), 2);
+
+    let mut calls = Vec::new();
+    for _ in 0..40 {
+        calls.push(pool.next().call_fut(Execute::new(|| -> Result<_, ()> {
+            Ok(Arbiter::name())
+        })));
+    }
+
+    Arbiter::handle().spawn(
+        future::join_all(calls).then(|res| {
+            let mut counts = HashMap::new();
+            for name in res.unwrap() {
+                *counts.entry(name.unwrap()).or_insert(0) += 1;
+            }
+            let mut counts: Vec<_> = counts.values().cloned().collect();
+            counts.sort();
+            assert_eq!(counts, vec![10, 30]);
+
+            Arbiter::system().send(SystemExit(0));
+            future::result(Ok(()))
+        }));
+
+    sys.run();
+}
+
+#[test]
+#[should_panic(expected = "non zero weight")]
+fn test_arbiter_pool_zero_weights() {
+    let _sys = System::new("test");
+    ArbiterPool::with_weights(vec![0, 0]);
+}
+
+#[test]
+fn test_system_arbiter_updated() {
+    let sys = System::new("test");
+
+    let child = Arbiter::new(None);
+    let child2 = child.clone();
+    let new_sys_arb = Arbiter::new(Some("new".to_owned()));
+
+    Arbiter::handle().spawn(
+        child.call_fut(Execute::new(|| -> Result<(), ()> { Ok(()) }))
+            .then(move |_| {
+                Arbiter::system().send(SystemArbiterUpdated(new_sys_arb));
+                Timeout::new(Duration::from_millis(50), Arbiter::handle()).unwrap()
+            })
+            .then(move |_| {
+                child2.call_fut(Execute::new(|| -> Result<_, ()> {
+                    Ok(Arbiter::system_arbiter())
+                }))
+            })
+            .then(|res| {
+                res.unwrap().unwrap().call_fut(Execute::new(|| -> Result<_, ()> {
+                    Ok(Arbiter::name())
+                }))
+            })
+            .then(|res| {
+                assert!(res.unwrap().unwrap().contains("new"));
+
+                Arbiter::system().send(SystemExit(0));
+                future::result(Ok(()))
+            }));
+
+    sys.run();
+}
+
+#[test]
+fn test_arbiter_run_in() {
+    let sys = System::new("test");
+
+    let addr = Arbiter::new(Some("worker".to_owned()));
+    Arbiter::handle().spawn(
+        Arbiter::run_in(&addr, future::lazy(|| future::ok::<_, ()>(Arbiter::name())))
+            .then(|res| {
+                match res {
+                    Ok(name) => assert!(name.ends_with("\"worker\"")),
+                    _ => assert!(false, "something is wrong"),
+                }
+                Arbiter::system().send(SystemExit(0));
+                Ok(())
+            }));
+
+    sys.run();
+}
+
+#[test]
+fn test_arbiter_for_name() {
+    let sys = System::new("test");
+
+    let _ = Arbiter::new(Some("io-pool".to_owned()));
+    assert!(System::arbiter_for_name("unknown").is_none());
+
+    // arbiter registers itself asynchronously
+    Arbiter::handle().spawn(
+        Timeout::new(Duration::from_millis(100), Arbiter::handle()).unwrap()
+            .map_err(|_| ())
+            .and_then(|_| {
+                let addr = System::arbiter_for_name("io-pool").unwrap();
+                addr.call_fut(Execute::new(|| -> Result<_, ()> {
+                    Arbiter::arbiter().send(StopArbiter(0));
+                    Ok(Arbiter::name())
+                })).map_err(|_| ())
+            })
+            .and_then(|res| {
+                assert!(res.unwrap().ends_with("\"io-pool\""));
+                Timeout::new(Duration::from_millis(100), Arbiter::handle()).unwrap()
+                    .map_err(|_| ())
+            })
+            .then(|res| {
+                assert!(res.is_ok());
+                assert!(System::arbiter_for_name("io-pool").is_none());
+                Arbiter::system().send(SystemExit(0));
+                Ok(())
+            }));
+
+    sys.run();
+}
+
+#[test]
+fn test_defer_system_exit() {
+    let sys = System::new("test");
+
+    let (tx, rx) = futures::sync::oneshot::channel();
+    Arbiter::system().send(DeferSystemExit(rx));
+    Arbiter::system().send(SystemExit(3));
+    Arbiter::system().send(SystemExit(4));
+
+    let start = Instant::now();
+    Arbiter::handle().spawn(
+        Timeout::new(Duration::from_millis(100), Arbiter::handle()).unwrap()
+            .then(move |_| {
+                let _ = tx.send(());
+                Ok(())
+            }));
+
+    assert_eq!(sys.run(), 3);
+    assert!(start.elapsed() >= Duration::from_millis(100));
+}
+
+struct Counter(Arc<AtomicUsize>);
+
+impl Actor for Counter {
+    type Context = Context<Self>;
+}
+
+struct Ping;
+
+impl ResponseType<Ping> for Counter {
+    type Item = usize;
+    type Error = ();
+}
+
+impl Handler<Ping> for Counter {
+    fn handle(&mut self, _: Ping, _: &mut Context<Self>) -> Response<Self, Ping> {
+        Self::reply(self.0.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+}
+
+#[test]
+fn test_freeze_system() {
+    let sys = System::new("test");
+
+    let count = Arc::new(AtomicUsize::new(0));
+    let count2 = Arc::clone(&count);
+    let addr: SyncAddress<_> = Counter(Arc::clone(&count)).start();
+
+    Arbiter::handle().spawn(
+        Arbiter::system().call_fut(FreezeSystem)
+            .map_err(|_| ())
+            .and_then(move |_| {
+                let res = addr.call_fut(Ping);
+                Timeout::new(Duration::from_millis(50), Arbiter::handle()).unwrap()
+                    .map(|_| res)
+                    .map_err(|_| ())
+            })
+            .and_then(move |res| {
+                // message is buffered while system is frozen
+                assert_eq!(count2.load(Ordering::Relaxed), 0);
+                Arbiter::system().send(ThawSystem);
+                res.map_err(|_| ())
+            })
+            .then(|res| {
+                assert_eq!(res.unwrap().unwrap(), 1);
+                Arbiter::system().send(SystemExit(0));
+                Ok(())
+            }));
+
+    sys.run();
+    assert_eq!(count.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+#[cfg(feature="pause")]
+fn test_pause_resume() {
+    let sys = System::new("test");
+
+    let count = Arc::new(AtomicUsize::new(0));
+    let count2 = Arc::clone(&count);
+    let addr: SyncAddress<_> = Counter(Arc::clone(&count)).start();
+
+    Arbiter::handle().spawn(
+        System::pause()
+            .and_then(move |_| {
+                let res = addr.call_fut(Ping);
+                addr.send(Ping);
+                Timeout::new(Duration::from_millis(50), Arbiter::handle()).unwrap()
+                    .map(|_| res)
+                    .map_err(|_| ())
+            })
+            .and_then(move |res| {
+                // messages are queued while system is paused
+                assert_eq!(count2.load(Ordering::Relaxed), 0);
+                System::resume();
+                res.map_err(|_| ())
+            })
+            .then(|res| {
+                assert_eq!(res.unwrap().unwrap(), 1);
+                Arbiter::system().send(SystemExit(0));
+                Ok(())
+            }));
+
+    sys.run();
+    assert_eq!(count.load(Ordering::Relaxed), 2);
+}
+
+struct ArbiterName;
+
+impl ResponseType<ArbiterName> for Counter {
+    type Item = String;
+    type Error = ();
+}
+
+impl Handler<ArbiterName> for Counter {
+    fn handle(&mut self, _: ArbiterName, _: &mut Context<Self>) -> Response<Self, ArbiterName> {
+        Self::reply(Arbiter::name())
+    }
+}
+
+#[test]
+fn test_spawn_actor_in() {
+    let sys = System::new("test");
+
+    let _ = Arbiter::new(Some("workers".to_owned()));
+
+    // arbiter registers itself asynchronously
+    Arbiter::handle().spawn(
+        Timeout::new(Duration::from_millis(100), Arbiter::handle()).unwrap()
+            .map_err(|_| ())
+            .and_then(|_| {
+                System::spawn_actor_in("unknown", Counter(Arc::new(AtomicUsize::new(0))))
+                    .then(|res| {
+                        assert_eq!(res.err(), Some(SpawnError::UnknownArbiter("unknown".to_owned())));
+                        System::spawn_actor_in("workers", Counter(Arc::new(AtomicUsize::new(0))))
+                    })
+                    .map_err(|_| ())
+            })
+            .and_then(|addr| addr.call_fut(ArbiterName).map_err(|_| ()))
+            .then(|res| {
+                assert!(res.unwrap().unwrap().ends_with("\"workers\""));
+                Arbiter::system().send(SystemExit(0));
+                Ok(())
+            }));
+
+    sys.run();
+}
+
+#[test]
+fn test_new_with_arbiters() {
+    let sys = System::new("owner");
+    let shared = Arbiter::new(Some("shared".to_owned()));
+
+    let arbiters = vec![shared.clone()];
+    let tenant = std::thread::spawn(move || {
+        let sys = System::new_with_arbiters("tenant", arbiters);
+        Arbiter::handle().spawn(
+            System::spawn_actor(Counter(Arc::new(AtomicUsize::new(0))))
+                .map_err(|_| ())
+                .and_then(|addr| addr.call_fut(ArbiterName).map_err(|_| ()))
+                .then(|res| {
+                    assert!(res.unwrap().unwrap().ends_with("\"shared\""));
+                    // tenant's registry does not see owner's arbiters
+                    assert!(System::arbiter_for_name("shared").is_none());
+                    Arbiter::system().send(SystemExit(0));
+                    Ok(())
+                }));
+        sys.run();
+    });
+
+    Arbiter::handle().spawn(
+        System::spawn_actor(Counter(Arc::new(AtomicUsize::new(0))))
+            .then(move |res| {
+                assert_eq!(res.err(), Some(SpawnError::NoArbiters));
+                tenant.join().unwrap();
+
+                // shared arbiter keeps running after tenant exits
+                shared.call_fut(Execute::new(|| Ok::<_, ()>(Arbiter::name())))
+            })
+            .then(|res| {
+                assert!(res.unwrap().unwrap().ends_with("\"shared\""));
+                Arbiter::system().send(SystemExit(0));
+                Ok(())
+            }));
+
+    sys.run();
+}
+
+#[test]
+fn test_actor_limit() {
+    let sys = System::new("test");
+
+    let _ = Arbiter::new(Some("limited".to_owned()));
+
+    Arbiter::handle().spawn(
+        Timeout::new(Duration::from_millis(100), Arbiter::handle()).unwrap()
+            .map_err(|_| ())
+            .and_then(|_| Arbiter::system().call_fut(ActorCount).map_err(|_| ()))
+            .and_then(|res| {
+                let count = res.unwrap();
+                assert!(count > 0);
+
+                // no room for new actor
+                System::set_actor_limit(Some(count));
+                System::spawn_actor_in("limited", Counter(Arc::new(AtomicUsize::new(0))))
+                    .then(move |res| {
+                        assert_eq!(res.err(), Some(SpawnError::ActorLimit));
+                        System::set_actor_limit(Some(count + 1));
+                        System::spawn_actor_in("limited", Counter(Arc::new(AtomicUsize::new(0))))
+                    })
+                    .map_err(|_| ())
+                    .and_then(|addr| {
+                        // actor stops once its address is dropped
+                        Arbiter::system().call_fut(ActorCount)
+                            .map(move |res| (addr, res))
+                            .map_err(|_| ())
+                    })
+                    .map(move |(_, res)| assert_eq!(res.unwrap(), count + 1))
+            })
+            .then(|res| {
+                res.unwrap();
+                Arbiter::system().send(SystemExit(0));
+                Ok(())
+            }));
+
+    sys.run();
+}
+
+#[test]
+fn test_forward_to() {
+    let sys = System::new("test");
+
+    let count = Arc::new(AtomicUsize::new(0));
+    let addr: Address<_> = Counter(Arc::clone(&count)).start();
+    Arbiter::arbiter().send(ForwardTo::new(addr.clone(), Ping));
+    Arbiter::arbiter().send(ForwardTo::new(addr.clone(), Ping));
+
+    Arbiter::handle().spawn(
+        Timeout::new(Duration::from_millis(50), Arbiter::handle()).unwrap()
+            .then(|_| {
+                Arbiter::system().send(SystemExit(0));
+                Ok(())
+            }));
+    sys.run();
+
+    assert_eq!(count.load(Ordering::Relaxed), 2);
+}
+
+struct Migrating(usize);
+
+impl Actor for Migrating {
+    type Context = Context<Self>;
+}
+
+impl ResponseType<Ping> for Migrating {
+    type Item = (usize, String);
+    type Error = ();
+}
+
+impl Handler<Ping> for Migrating {
+    fn handle(&mut self, _: Ping, _: &mut Context<Self>) -> Response<Self, Ping> {
+        self.0 += 1;
+        Self::reply((self.0, Arbiter::name()))
+    }
+}
+
+#[test]
+fn test_migrate_to() {
+    let sys = System::new("test");
+
+    let arb = Arbiter::new(Some("target".to_owned()));
+    let addr: SyncAddress<_> = Migrating(0).start();
+    let addr2 = addr.clone();
+
+    Arbiter::handle().spawn(
+        addr.call_fut(Ping)
+            .map_err(|_| ())
+            .and_then(move |res| {
+                assert_eq!(res.unwrap(), (1, "test".to_owned()));
+                let res = addr.migrate_to(&arb);
+                // message sent before migration completes is not lost
+                let next = addr.call_fut(Ping);
+                res.and_then(|_| next.map_err(|_| ()))
+            })
+            .and_then(move |res| {
+                let (count, name) = res.unwrap();
+                assert_eq!(count, 2);
+                assert!(name.ends_with("\"target\""));
+                addr2.call_fut(Ping).map_err(|_| ())
+            })
+            .then(|res| {
+                assert_eq!(res.unwrap().unwrap().0, 3);
+                Arbiter::system().send(SystemExit(0));
+                Ok(())
+            }));
+
+    sys.run();
+}
+
+#[test]
+fn test_migrate_to_stopped() {
+    let sys = System::new("test");
+
+    let arb = Arbiter::new(Some("target".to_owned()));
+    arb.send(StopArbiter(0));
+    let addr: SyncAddress<_> = Migrating(0).start();
+
+    Arbiter::handle().spawn(
+        addr.migrate_to(&arb)
+            .then(move |res| {
+                // actor is restarted in current arbiter
+                assert!(res.is_err());
+                addr.call_fut(Ping).map_err(|_| ())
+            })
+            .then(|res| {
+                let (count, name) = res.unwrap().unwrap();
+                assert_eq!(count, 1);
+                assert_eq!(name, "test");
+                Arbiter::system().send(SystemExit(0));
+                Ok(())
+            }));
+
+    sys.run();
+}
+
+#[test]
+fn test_run_future() {
+    let sys = System::new("test");
+
+    let arb = Arbiter::new(None);
+    let code = sys.run_future(
+        arb.call_fut(Execute::new(|| -> Result<_, ()> { Ok(Arbiter::name()) }))
+            .map_err(|_| ())
+            .and_then(|res| {
+                assert_ne!(res.unwrap(), "test");
+                // event loop runs until future resolves
+                Arbiter::system().send(SystemExit(0));
+                Timeout::new(Duration::from_millis(50), Arbiter::handle()).unwrap()
+                    .map_err(|_| ())
+            })
+            .map(|_| 5));
+
+    assert_eq!(code, 5);
+}
+
+struct Draining(Arc<AtomicUsize>);
+
+impl Actor for Draining {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Context<Self>) {
+        Timeout::new(Duration::from_millis(50), Arbiter::handle()).unwrap()
+            .actfuture()
+            .then(|_, act: &mut Draining, _: &mut Context<Self>| {
+                act.0.fetch_add(1, Ordering::Relaxed);
+                fut::ok(())
+            })
+            .spawn(ctx);
+    }
+}
+
+#[test]
+fn test_arbiter_drain_timeout() {
+    let sys = System::new("test");
+
+    let completed = Arc::new(AtomicUsize::new(0));
+    let completed2 = Arc::clone(&completed);
+    let arb = Arbiter::new(Some("drain".to_owned()));
+    arb.send(Execute::new(move || -> Result<(), ()> {
+        Arbiter::set_drain_timeout(Duration::from_secs(1));
+        let _: () = Draining(completed2).start();
+        Arbiter::arbiter().send(StopArbiter(0));
+        Ok(())
+    }));
+
+    Arbiter::handle().spawn(
+        Timeout::new(Duration::from_millis(200), Arbiter::handle()).unwrap()
+            .then(move |_| {
+                // future completed before arbiter stopped
+                assert_eq!(completed.load(Ordering::Relaxed), 1);
+                assert!(System::arbiter_for_name("drain").is_none());
+                Arbiter::system().send(SystemExit(0));
+                Ok(())
+            }));
+
+    sys.run();
+}
+
+struct Listening;
+
+impl Actor for Listening {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Context<Self>) {
+        // long-lived stream
+        ctx.add_stream(Interval::new(Duration::from_secs(3600), Arbiter::handle()).unwrap());
+    }
+}
+
+impl ResponseType<()> for Listening {
+    type Item = ();
+    type Error = ();
+}
+
+impl StreamHandler<(), std::io::Error> for Listening {}
+
+impl Handler<(), std::io::Error> for Listening {
+    fn handle(&mut self, _: (), _: &mut Context<Self>) -> Response<Self, ()> {
+        Self::empty()
+    }
+}
+
+#[test]
+fn test_arbiter_drain_skips_streams() {
+    let sys = System::new("test");
+
+    let arb = Arbiter::new(Some("listening".to_owned()));
+    arb.send(Execute::new(|| -> Result<(), ()> {
+        Arbiter::set_drain_timeout(Duration::from_secs(5));
+        let _: () = Listening.start();
+        Arbiter::arbiter().send(StopArbiter(0));
+        Ok(())
+    }));
+
+    Arbiter::handle().spawn(
+        Timeout::new(Duration::from_millis(200), Arbiter::handle()).unwrap()
+            .then(|_| {
+                // arbiter does not wait for registered stream
+                assert!(System::arbiter_for_name("listening").is_none());
+                Arbiter::system().send(SystemExit(0));
+                Ok(())
+            }));
+
+    sys.run();
+}
+
+#[test]
+fn test_spawn_interval() {
+    let sys = System::new("test");
+
+    let ticks = Arc::new(AtomicUsize::new(0));
+    let ticks2 = Arc::clone(&ticks);
+    let ticks3 = Arc::clone(&ticks);
+    let handle = Arbiter::spawn_interval(Duration::from_millis(20), move || {
+        ticks2.fetch_add(1, Ordering::Relaxed);
+    });
+
+    Arbiter::handle().spawn(
+        Timeout::new(Duration::from_millis(110), Arbiter::handle()).unwrap()
+            .and_then(move |_| {
+                let count = ticks3.load(Ordering::Relaxed);
+                assert!(count >= 3 && count <= 6, "unexpected ticks: {}", count);
+                assert!(Arbiter::cancel_interval(handle));
+                assert!(!Arbiter::cancel_interval(handle));
+                Timeout::new(Duration::from_millis(60), Arbiter::handle()).unwrap()
+                    .map(move |_| (ticks3, count))
+            })
+            .then(|res| {
+                // no ticks after cancellation
+                let (ticks, count) = res.unwrap();
+                assert_eq!(ticks.load(Ordering::Relaxed), count);
+                Arbiter::system().send(SystemExit(0));
+                Ok(())
+            }));
+
+    sys.run();
+    assert!(ticks.load(Ordering::Relaxed) >= 3);
+}
+
+#[test]
+fn test_arbiter_builder() {
+    let sys = System::new("test");
+    let inits = Arc::new(AtomicUsize::new(0));
+    let panics = Arc::new(AtomicUsize::new(0));
+
+    let cnt = Arc::clone(&inits);
+    let addr = Arbiter::builder()
+        .name("builder")
+        .thread_name("builder-thread")
+        .register(false)
+        .init(move || { cnt.fetch_add(1, Ordering::Relaxed); })
+        .spawn().unwrap();
+    assert_eq!(inits.load(Ordering::Relaxed), 1);
+
+    let cnt = Arc::clone(&panics);
+    let panicking = Arbiter::builder()
+        .panic_handler(move |_| { cnt.fetch_add(1, Ordering::Relaxed); })
+        .spawn().unwrap();
+
+    Arbiter::handle().spawn(
+        addr.call_fut(Execute::new(|| -> Result<_, ()> {
+            Ok(std::thread::current().name().map(|n| n.to_owned()))
+        }))
+            .then(move |res| {
+                assert_eq!(res.unwrap().unwrap(), Some("builder-thread".to_owned()));
+                panicking.call_fut(Execute::new(|| -> Result<(), ()> { panic!("test") }))
+            })
+            .then(|res| {
+                assert!(res.is_err());
+                Timeout::new(Duration::from_millis(100), Arbiter::handle()).unwrap()
+            })
+            .then(|_| {
+                // unregistered arbiter is not available by name
+                assert!(System::arbiter_for_name("builder").is_none());
+                Arbiter::system().send(SystemExit(0));
+                Ok(())
+            }));
+
+    sys.run();
+    assert_eq!(panics.load(Ordering::Relaxed), 1);
+}
+
+struct Tagged(&'static str, Arc<std::sync::Mutex<Vec<&'static str>>>);
+
+impl Actor for Tagged {
+    type Context = Context<Self>;
+}
+
+impl ResponseType<u32> for Tagged {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<u32> for Tagged {
+    fn handle(&mut self, _: u32, _: &mut Context<Self>) -> Response<Self, u32> {
+        self.1.lock().unwrap().push(self.0);
+        Self::empty()
+    }
+}
+
+#[test]
+fn test_arbiter_throughput() {
+    let sys = System::new("test");
+    let order = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+    let o = Arc::clone(&order);
+    let _ = Arbiter::builder()
+        .throughput(1)
+        .mailbox_capacity(16)
+        .init(move || {
+            let a: Address<_> = Tagged("a", Arc::clone(&o)).start();
+            let b: Address<_> = Tagged("b", o).start();
+            for i in 0..3 {
+                a.send(i);
+            }
+            for i in 0..3 {
+                b.send(i);
+            }
+        })
+        .spawn().unwrap();
+
+    Arbiter::handle().spawn(
+        Timeout::new(Duration::from_millis(100), Arbiter::handle()).unwrap()
+            .then(|_| {
+                Arbiter::system().send(SystemExit(0));
+                Ok(())
+            }));
+    sys.run();
+
+    // actors yield after each message
+    assert_eq!(*order.lock().unwrap(), vec!["a", "b", "a", "b", "a", "b"]);
+}
+
+#[test]
+fn test_arbiter_stop_waits_for_jobs() {
+    let sys = System::new("test");
+
+    let addr = Arbiter::new(Some("jobs".to_owned()));
+    let (tx, rx) = futures::sync::oneshot::channel();
+    let job = addr.call_fut(ExecuteAsync::new(rx.map_err(|_| ())));
+    addr.send(StopArbiter(0));
+
+    Arbiter::handle().spawn(
+        addr.call_fut(Execute::new(|| -> Result<(), ()> { Ok(()) }))
+            .then(move |res| {
+                // new jobs are rejected
+                assert!(res.is_err());
+                Timeout::new(Duration::from_millis(50), Arbiter::handle()).unwrap()
+            })
+            .then(move |_| {
+                // arbiter keeps running until job completes
+                assert!(System::arbiter_for_name("jobs").is_some());
+                let _ = tx.send(5);
+                job
+            })
+            .then(|res| {
+                // in-flight job completes
+                assert_eq!(res.unwrap(), Ok(5));
+                Timeout::new(Duration::from_millis(100), Arbiter::handle()).unwrap()
+            })
+            .then(|_| {
+                // arbiter stopped after job completion
+                assert!(System::arbiter_for_name("jobs").is_none());
+                Arbiter::system().send(SystemExit(0));
+                Ok(())
+            }));
+
+    sys.run();
+}
+
+#[test]
+fn test_arbiter_stop_jobs_timeout() {
+    let sys = System::new("test");
+
+    let addr = Arbiter::new(Some("stuck".to_owned()));
+    // clock of arbiter's thread jumps forward every 10 milliseconds
+    addr.send(Execute::new(|| -> Result<(), ()> {
+        let clock = TestClock::new();
+        Arbiter::set_clock(clock.clone());
+        Arbiter::handle().spawn(
+            Interval::new(Duration::from_millis(10), Arbiter::handle()).unwrap()
+                .for_each(move |_| {
+                    clock.advance(Duration::from_secs(60));
+                    Ok(())
+                })
+                .map_err(|_| ()));
+        Ok(())
+    }));
+    addr.send(ExecuteAsync::new(future::empty::<(), ()>()));
+    addr.send(StopArbiter(0));
+
+    Arbiter::handle().spawn(
+        Timeout::new(Duration::from_millis(200), Arbiter::handle()).unwrap()
+            .then(|_| {
+                // job never completes, arbiter stops after timeout
+                assert!(System::arbiter_for_name("stuck").is_none());
+                Arbiter::system().send(SystemExit(0));
+                Ok(())
+            }));
+
+    sys.run();
+}
+
+#[test]
+fn test_arbiter_builder_jobs_timeout() {
+    let sys = System::new("test");
+
+    let addr = Arbiter::builder()
+        .name("short-jobs").jobs_timeout(Duration::from_millis(200)).spawn().unwrap();
+    addr.send(ExecuteAsync::new(future::empty::<(), ()>()));
+    addr.send(StopArbiter(0));
+
+    Arbiter::handle().spawn(
+        Timeout::new(Duration::from_millis(50), Arbiter::handle()).unwrap()
+            .then(|_| {
+                // arbiter waits for the job
+                assert!(System::arbiter_for_name("short-jobs").is_some());
+                Timeout::new(Duration::from_millis(400), Arbiter::handle()).unwrap()
+            })
+            .then(|_| {
+                // job never completes, arbiter stops after configured timeout
+                assert!(System::arbiter_for_name("short-jobs").is_none());
+                Arbiter::system().send(SystemExit(0));
+                Ok(())
+            }));
+
+    sys.run();
+}
+
+#[test]
+fn test_arbiter_panic() {
+    let sys = System::new("test");
+
+    let addr = Arbiter::new(Some("panicky".to_owned()));
+
+    Arbiter::handle().spawn(
+        Timeout::new(Duration::from_millis(50), Arbiter::handle()).unwrap()
+            .then(move |_| {
+                assert!(System::arbiter_for_name("panicky").is_some());
+                addr.call_fut(Execute::new(|| -> Result<(), ()> { panic!("test") }))
+            })
+            .then(|res| {
+                assert!(res.is_err());
+                Timeout::new(Duration::from_millis(100), Arbiter::handle()).unwrap()
+            })
+            .then(|_| {
+                // system removes dead arbiter
+                assert!(System::arbiter_for_name("panicky").is_none());
+                Arbiter::system().send(SystemExit(0));
+                Ok(())
+            }));
+
+    assert_eq!(sys.run(), 0);
+}
+
+#[test]
+fn test_arbiter_start_timeout() {
+    let sys = System::new("test");
+
+    let res = Arbiter::builder()
+        .name("slow")
+        .start_timeout(Duration::from_millis(50))
+        .init(|| std::thread::sleep(Duration::from_millis(200)))
+        .spawn();
+    match res {
+        Err(ArbiterStartError::Timeout) => (),
+        _ => panic!("should time out"),
+    }
+
+    let addr = Arbiter::builder()
+        .start_timeout(Duration::from_secs(5))
+        .spawn().unwrap();
+
+    Arbiter::handle().spawn(
+        addr.call_fut(Execute::new(|| -> Result<(), ()> { Ok(()) }))
+            .then(|res| {
+                assert_eq!(res.unwrap(), Ok(()));
+                Timeout::new(Duration::from_millis(300), Arbiter::handle()).unwrap()
+            })
+            .then(|_| {
+                // orphaned arbiter never starts
+                assert!(System::arbiter_for_name("slow").is_none());
+                Arbiter::system().send(SystemExit(0));
+                Ok(())
+            }));
+
+    sys.run();
+}
+
+#[test]
+fn test_arbiter_run_in_stopped() {
+    let sys = System::new("test");
+
+    let addr = Arbiter::new(Some("worker".to_owned()));
+    addr.send(StopArbiter(0));
+    Arbiter::handle().spawn(
+        Arbiter::run_in(&addr, future::empty::<(), ()>())
+            .then(|res| {
+                match res {
+                    Err(CallError::MailboxClosed) => (),
+                    _ => assert!(false, "something is wrong"),
+                }
+                Arbiter::system().send(SystemExit(0));
+                Ok(())
+            }));
+
+    sys.run();
+}
+
+#[test]
+#[cfg(feature="serde")]
+fn test_system_messages_serde() {
+    let sys = System::new("test");
+
+    // message received from outside of the process
+    let json = serde_json::to_string(&SystemExit(7)).unwrap();
+    let msg: SystemExit = serde_json::from_str(&json).unwrap();
+
+    let json = serde_json::to_string(&StopArbiter(3)).unwrap();
+    let stop: StopArbiter = serde_json::from_str(&json).unwrap();
+    assert_eq!(stop.0, 3);
+    let _: FreezeSystem = serde_json::from_str(&serde_json::to_string(&FreezeSystem).unwrap())
+        .unwrap();
+
+    Arbiter::system().send(msg);
+    assert_eq!(sys.run(), 7);
+}
@@ -0,0 +1,182 @@
+extern crate actix;
+extern crate futures;
+extern crate tokio_core;
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+use futures::Future;
+use actix::prelude::*;
+use actix::actors::fanout::{FanOut, FanOutConfig, FanOutMode};
+use common::sleep;
+
+#[derive(Clone)]
+struct Event(u32);
+
+type Log = Arc<Mutex<Vec<(&'static str, u32)>>>;
+
+struct Listener {
+    name: &'static str,
+    log: Log,
+}
+
+impl Actor for Listener {
+    type Context = Context<Self>;
+}
+
+impl ResponseType<Event> for Listener {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<Event> for Listener {
+    fn handle(&mut self, msg: Event, _: &mut Context<Self>) -> Response<Self, Event> {
+        self.log.lock().unwrap().push((self.name, msg.0));
+        Self::empty()
+    }
+}
+
+struct Stopped;
+
+impl Actor for Stopped {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Context<Self>) {
+        ctx.stop();
+    }
+}
+
+impl ResponseType<Event> for Stopped {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<Event> for Stopped {
+    fn handle(&mut self, _: Event, _: &mut Context<Self>) -> Response<Self, Event> {
+        Self::empty()
+    }
+}
+
+fn listener(name: &'static str, log: &Log) -> Recipient<Event> {
+    let addr: SyncAddress<_> = Listener{name: name, log: Arc::clone(log)}.start();
+    addr.recipient()
+}
+
+fn fanout(mode: FanOutMode, log: &Log) -> Address<FanOut<Event>> {
+    FanOut::new(FanOutConfig{mode: mode})
+        .add_recipient(listener("a", log))
+        .add_recipient(listener("b", log))
+        .add_recipient(listener("c", log))
+        .start()
+}
+
+#[test]
+fn test_sequential() {
+    let sys = System::new("test");
+    let log = Arc::new(Mutex::new(Vec::new()));
+
+    let addr = fanout(FanOutMode::Sequential, &log);
+    addr.send(Event(1));
+    Arbiter::handle().spawn(
+        addr.call_fut(Event(2))
+            .then(|res| {
+                assert_eq!(res.unwrap(), Ok(3));
+                Arbiter::system().send(msgs::SystemExit(0));
+                Ok(())
+            }));
+    sys.run();
+
+    // every recipient handles message before next one gets it
+    assert_eq!(*log.lock().unwrap(),
+               vec![("a", 1), ("b", 1), ("c", 1), ("a", 2), ("b", 2), ("c", 2)]);
+}
+
+#[test]
+fn test_concurrent() {
+    let sys = System::new("test");
+    let log = Arc::new(Mutex::new(Vec::new()));
+
+    let addr = fanout(FanOutMode::Concurrent, &log);
+    addr.send(Event(1));
+    Arbiter::handle().spawn(
+        addr.call_fut(Event(2))
+            .then(|res| {
+                assert_eq!(res.unwrap(), Ok(3));
+                sleep()
+            })
+            .then(|_| {
+                Arbiter::system().send(msgs::SystemExit(0));
+                Ok(())
+            }));
+    sys.run();
+
+    // each recipient gets messages in the order fan-out received them
+    let log = log.lock().unwrap();
+    assert_eq!(log.len(), 6);
+    for name in &["a", "b", "c"] {
+        let received: Vec<_> = log.iter().filter(|e| e.0 == *name).map(|e| e.1).collect();
+        assert_eq!(received, vec![1, 2]);
+    }
+}
+
+#[test]
+fn test_all_or_nothing() {
+    let sys = System::new("test");
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let dead = Arc::new(Mutex::new(Vec::new()));
+
+    let stopped: SyncAddress<_> = Stopped.start();
+    let addr: Address<_> = FanOut::new(FanOutConfig{mode: FanOutMode::AllOrNothing})
+        .add_recipient(listener("a", &log))
+        .add_recipient(stopped.recipient())
+        .add_recipient(listener("c", &log))
+        .dead_letters(listener("dead", &dead))
+        .start();
+
+    let addr2 = addr.clone();
+    Arbiter::handle().spawn(
+        sleep()
+            .then(move |_| addr.call_fut(Event(1)))
+            .then(move |res| {
+                // message is rolled back, stopped recipient gets removed
+                assert_eq!(res.unwrap(), Err(()));
+                addr2.call_fut(Event(2))
+            })
+            .then(|res| {
+                assert_eq!(res.unwrap(), Ok(2));
+                sleep()
+            })
+            .then(|_| {
+                Arbiter::system().send(msgs::SystemExit(0));
+                Ok(())
+            }));
+    sys.run();
+
+    assert_eq!(*log.lock().unwrap(), vec![("a", 2), ("c", 2)]);
+    assert_eq!(*dead.lock().unwrap(), vec![("dead", 1)]);
+}
+
+#[test]
+fn test_sequential_skips_stopped() {
+    let sys = System::new("test");
+    let log = Arc::new(Mutex::new(Vec::new()));
+
+    let stopped: SyncAddress<_> = Stopped.start();
+    let addr: Address<_> = FanOut::new(FanOutConfig::default())
+        .add_recipient(listener("a", &log))
+        .add_recipient(stopped.recipient())
+        .add_recipient(listener("c", &log))
+        .start();
+
+    Arbiter::handle().spawn(
+        sleep()
+            .then(move |_| addr.call_fut(Event(1)))
+            .then(|res| {
+                assert_eq!(res.unwrap(), Ok(2));
+                Arbiter::system().send(msgs::SystemExit(0));
+                Ok(())
+            }));
+    sys.run();
+
+    assert_eq!(*log.lock().unwrap(), vec![("a", 1), ("c", 1)]);
+}
@@ -4,7 +4,7 @@ extern crate actix;
 extern crate futures;
 extern crate tokio_core;
 
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 use futures::{future, Future};
@@ -235,3 +235,237 @@ fn test_stop_restore_after_stopping() {
     assert!(stopping.load(Ordering::Relaxed), "Not stopping");
     assert!(!stopped.load(Ordering::Relaxed), "Stopped");
 }
+
+struct Record(usize);
+
+struct Versioned {
+    version: usize,
+    log: Arc<Mutex<Vec<String>>>,
+}
+
+impl Actor for Versioned {
+    type Context = Context<Self>;
+
+    fn started(&mut self, _: &mut Context<Self>) {
+        self.log.lock().unwrap().push(format!("started {}", self.version));
+    }
+
+    fn stopped(&mut self, _: &mut Context<Self>) {
+        self.log.lock().unwrap().push(format!("stopped {}", self.version));
+    }
+}
+
+impl ResponseType<Record> for Versioned {
+    type Item = usize;
+    type Error = ();
+}
+
+impl Handler<Record> for Versioned {
+    fn handle(&mut self, msg: Record, _: &mut Context<Self>) -> Response<Self, Record> {
+        self.log.lock().unwrap().push(format!("{} by {}", msg.0, self.version));
+        Self::reply(self.version)
+    }
+}
+
+struct Quit;
+
+impl ResponseType<Quit> for Versioned {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<Quit> for Versioned {
+    fn handle(&mut self, _: Quit, ctx: &mut Context<Self>) -> Response<Self, Quit> {
+        ctx.stop();
+        Self::empty()
+    }
+}
+
+#[test]
+fn test_take_over() {
+    let sys = System::new("test");
+    let log = Arc::new(Mutex::new(Vec::new()));
+
+    let (addr, sync_addr): (Address<_>, SyncAddress<_>) =
+        Versioned{version: 1, log: Arc::clone(&log)}.start();
+    addr.send(Record(1));
+    sync_addr.send(Record(2));
+
+    let log2 = Arc::clone(&log);
+    Arbiter::handle().spawn(
+        Timeout::new(Duration::from_millis(50), Arbiter::handle()).unwrap()
+            .then(move |_| {
+                addr.send(Record(3));
+                assert!(Versioned{version: 2, log: log2}.take_over(&addr).is_ok());
+                sync_addr.send(Record(4));
+                addr.call_fut(Record(5))
+            })
+            .then(|res| {
+                assert_eq!(res.unwrap(), Ok(2));
+                Arbiter::system().send(SystemExit(0));
+                Ok(())
+            }));
+    sys.run();
+
+    // queued messages are handled by new actor
+    assert_eq!(*log.lock().unwrap(),
+               vec!["started 1", "1 by 1", "2 by 1", "stopped 1", "started 2",
+                    "3 by 2", "4 by 2", "5 by 2", "stopped 2"]);
+}
+
+#[test]
+fn test_take_over_stopped() {
+    let sys = System::new("test");
+    let log = Arc::new(Mutex::new(Vec::new()));
+
+    let addr: Address<_> = Versioned{version: 1, log: Arc::clone(&log)}.start();
+    addr.send(Quit);
+
+    let log2 = Arc::clone(&log);
+    Arbiter::handle().spawn(
+        Timeout::new(Duration::from_millis(50), Arbiter::handle()).unwrap()
+            .then(move |_| {
+                let act = Versioned{version: 2, log: log2}.take_over(&addr).err().unwrap();
+                assert_eq!(act.version, 2);
+                Arbiter::system().send(SystemExit(0));
+                Ok(())
+            }));
+    sys.run();
+}
+
+struct Starter(Arc<Mutex<Vec<String>>>);
+
+impl Actor for Starter {
+    type Context = Context<Self>;
+
+    fn started(&mut self, _: &mut Context<Self>) {
+        self.0.lock().unwrap().push("started".to_owned());
+
+        // system is running, hook is called immediately
+        let log = Arc::clone(&self.0);
+        System::on_start(move || log.lock().unwrap().push("late".to_owned()));
+        Arbiter::system().send(SystemExit(0));
+    }
+}
+
+#[test]
+fn test_system_on_start() {
+    let sys = System::new("startup");
+    let log = Arc::new(Mutex::new(Vec::new()));
+
+    let _: () = Starter(Arc::clone(&log)).start();
+    let log1 = Arc::clone(&log);
+    System::on_start(move || {
+        log1.lock().unwrap().push(format!("hook1 {}", Arbiter::system_name()))
+    });
+    let log2 = Arc::clone(&log);
+    System::on_start(move || log2.lock().unwrap().push("hook2".to_owned()));
+    assert!(log.lock().unwrap().is_empty());
+
+    sys.run();
+    assert_eq!(*log.lock().unwrap(), vec!["hook1 startup", "hook2", "started", "late"]);
+}
+
+struct Dropped(Arc<Mutex<Vec<String>>>);
+
+impl Actor for Dropped {
+    type Context = Context<Self>;
+}
+
+impl Drop for Dropped {
+    fn drop(&mut self) {
+        self.0.lock().unwrap().push("dropped".to_owned());
+    }
+}
+
+#[test]
+fn test_system_on_shutdown() {
+    let sys = System::new("test");
+    let log = Arc::new(Mutex::new(Vec::new()));
+
+    let log1 = Arc::clone(&log);
+    System::on_shutdown("flush", move || log1.lock().unwrap().push("flush".to_owned()));
+    let log2 = Arc::clone(&log);
+    System::on_shutdown("close", move || log2.lock().unwrap().push("close".to_owned()));
+    // hook with the same name replaces existing one
+    let log3 = Arc::clone(&log);
+    System::on_shutdown("flush", move || log3.lock().unwrap().push("flush2".to_owned()));
+
+    let log4 = Arc::clone(&log);
+    let arb = Arbiter::new(None);
+    Arbiter::handle().spawn(
+        arb.call_fut(msgs::Execute::new(move || -> Result<(), ()> {
+            let _: () = Dropped(log4).start();
+            Ok(())
+        })).then(|_| {
+            Arbiter::system().send(SystemExit(0));
+            Ok(())
+        }));
+    sys.run();
+
+    // hooks run after arbiter stopped
+    assert_eq!(*log.lock().unwrap(), vec!["dropped", "flush2", "close"]);
+}
+
+struct Counter {
+    version: usize,
+    count: u32,
+    log: Arc<Mutex<Vec<String>>>,
+}
+
+impl Actor for Counter {
+    type Context = Context<Self>;
+
+    fn stopped(&mut self, _: &mut Context<Self>) {
+        self.log.lock().unwrap().push(format!("stopped {} at {}", self.version, self.count));
+    }
+}
+
+impl Supervised for Counter {}
+
+impl Snapshotable for Counter {
+    fn snapshot(&self) -> Vec<u8> {
+        self.count.to_string().into_bytes()
+    }
+
+    fn restore(&mut self, data: &[u8]) {
+        self.count = String::from_utf8_lossy(data).parse().unwrap();
+    }
+}
+
+impl ResponseType<Record> for Counter {
+    type Item = (usize, u32);
+    type Error = ();
+}
+
+impl Handler<Record> for Counter {
+    fn handle(&mut self, msg: Record, _: &mut Context<Self>) -> Response<Self, Record> {
+        self.count += msg.0 as u32;
+        Self::reply((self.version, self.count))
+    }
+}
+
+#[test]
+fn test_reload() {
+    let sys = System::new("test");
+    let log = Arc::new(Mutex::new(Vec::new()));
+
+    let log1 = Arc::clone(&log);
+    let (addr, _) = Supervisor::start(
+        false, move |_| Counter{version: 1, count: 0, log: log1});
+    addr.send(Record(1));
+    addr.send(Record(2));
+    assert!(Counter{version: 2, count: 0, log: Arc::clone(&log)}.reload(&addr).is_ok());
+
+    Arbiter::handle().spawn(
+        addr.call_fut(Record(3))
+            .then(|res| {
+                // messages queued before reload are handled by old actor
+                assert_eq!(res.unwrap(), Ok((2, 6)));
+                Arbiter::system().send(SystemExit(0));
+                Ok(())
+            }));
+    sys.run();
+
+    assert_eq!(log.lock().unwrap()[0], "stopped 1 at 3");
+}
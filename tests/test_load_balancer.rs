@@ -0,0 +1,89 @@
+extern crate actix;
+extern crate futures;
+extern crate tokio_core;
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use futures::Future;
+use tokio_core::reactor::Timeout;
+use actix::prelude::*;
+use actix::actors::load_balancer::{AddWorker, LoadBalancer, RemoveWorker};
+
+struct Job;
+
+struct Worker(usize, Arc<Mutex<Vec<usize>>>);
+
+impl Actor for Worker {
+    type Context = Context<Self>;
+}
+
+impl ResponseType<Job> for Worker {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<Job> for Worker {
+    fn handle(&mut self, _: Job, _: &mut Context<Self>) -> Response<Self, Job> {
+        self.1.lock().unwrap()[self.0] += 1;
+        Self::empty()
+    }
+}
+
+fn run_jobs<F>(workers: usize, high_watermark: usize, f: F) -> Vec<usize>
+    where F: FnOnce(&Address<LoadBalancer<Job>>, &[SyncAddress<Worker>])
+{
+    let sys = System::new("test");
+    let handled = Arc::new(Mutex::new(vec![0; workers]));
+
+    let balancer: Address<_> = LoadBalancer::new(high_watermark).start();
+    let addrs: Vec<SyncAddress<_>> = (0..workers)
+        .map(|idx| Worker(idx, Arc::clone(&handled)).start())
+        .collect();
+    for addr in &addrs {
+        balancer.send(AddWorker(addr.recipient()));
+    }
+    f(&balancer, &addrs);
+
+    Arbiter::handle().spawn(
+        Timeout::new(Duration::from_millis(200), Arbiter::handle()).unwrap()
+            .then(move |_| {
+                drop(addrs);
+                Arbiter::system().send(msgs::SystemExit(0));
+                Ok(())
+            }));
+    sys.run();
+
+    let handled = handled.lock().unwrap();
+    handled.clone()
+}
+
+#[test]
+fn test_distribution() {
+    let handled = run_jobs(3, 100, |balancer, _| {
+        for _ in 0..30 {
+            balancer.send(Job);
+        }
+    });
+    assert_eq!(handled, vec![10, 10, 10]);
+}
+
+#[test]
+fn test_queue_when_busy() {
+    let handled = run_jobs(2, 1, |balancer, _| {
+        for _ in 0..20 {
+            balancer.send(Job);
+        }
+    });
+    assert_eq!(handled.iter().sum::<usize>(), 20);
+}
+
+#[test]
+fn test_remove_worker() {
+    let handled = run_jobs(2, 100, |balancer, addrs| {
+        balancer.send(RemoveWorker(addrs[0].recipient()));
+        for _ in 0..4 {
+            balancer.send(Job);
+        }
+    });
+    assert_eq!(handled, vec![0, 4]);
+}
@@ -0,0 +1,226 @@
+extern crate actix;
+extern crate futures;
+#[cfg(feature="serde")]
+extern crate serde_json;
+
+use std::time::{Duration, Instant};
+use futures::{future, Future};
+use actix::prelude::*;
+use actix::actors::metrics::{self, Instrumented, InstrumentedHandler, MetricsActor};
+
+struct Ping(bool);
+
+struct Other;
+
+struct MyActor;
+
+impl Actor for MyActor {
+    type Context = Context<Self>;
+}
+
+impl Instrumented for MyActor {
+    fn metrics_name() -> &'static str {
+        "my-actor"
+    }
+}
+
+impl ResponseType<Ping> for MyActor {
+    type Item = ();
+    type Error = ();
+}
+
+impl InstrumentedHandler<Ping> for MyActor {
+    fn handle(&mut self, msg: Ping, _: &mut Context<Self>) -> Response<Self, Ping> {
+        if msg.0 {
+            Self::empty()
+        } else {
+            Self::reply_error(())
+        }
+    }
+}
+
+impl ResponseType<Other> for MyActor {
+    type Item = ();
+    type Error = ();
+}
+
+// not instrumented
+impl Handler<Other> for MyActor {
+    fn handle(&mut self, _: Other, _: &mut Context<Self>) -> Response<Self, Other> {
+        Self::empty()
+    }
+}
+
+#[test]
+fn test_metrics() {
+    let sys = System::new("test");
+
+    let addr: Address<_> = MyActor.start();
+    addr.send(Ping(true));
+    addr.send(Ping(false));
+    addr.send(Other);
+
+    Arbiter::handle().spawn(
+        addr.call_fut(Ping(true))
+            .then(|_| {
+                Arbiter::system_registry().get::<MetricsActor>()
+                    .call_fut(metrics::GetMetrics)
+            })
+            .then(|res| {
+                let snapshot = res.unwrap().unwrap();
+                let m = &snapshot.actors["my-actor"];
+                assert_eq!(m.received, 3);
+                assert_eq!(m.success, 2);
+                assert_eq!(m.errors, 1);
+                assert_eq!(m.latency.count, 3);
+                assert_eq!(snapshot.actors.len(), 1);
+
+                Arbiter::system_registry().get::<MetricsActor>()
+                    .call_fut(metrics::PrometheusExport)
+            })
+            .then(|res| {
+                let text = res.unwrap().unwrap();
+                assert!(text.contains(
+                    "actix_messages_received_total{actor=\"my-actor\"} 3"));
+                assert!(text.contains(
+                    "actix_messages_processed_total{actor=\"my-actor\",result=\"error\"} 1"));
+                assert!(text.contains(
+                    "actix_handler_latency_seconds_count{actor=\"my-actor\"} 3"));
+
+                Arbiter::system().send(msgs::SystemExit(0));
+                future::result(Ok(()))
+            }));
+
+    sys.run();
+}
+
+#[test]
+fn test_runtime_metrics() {
+    let sys = System::new("test");
+
+    let addr = Arbiter::system_registry().get::<MetricsActor>();
+    let arbiter = metrics::Source::Arbiter("worker".to_owned());
+    let actor = metrics::Source::Actor("my-actor".to_owned());
+    addr.send(metrics::Report(arbiter.clone(), metrics::Metric::TickLag, 1500));
+    addr.send(metrics::Report(arbiter.clone(), metrics::Metric::TickLag, 500));
+    addr.send(metrics::Report(actor.clone(), metrics::Metric::Handled, 2));
+    addr.send(metrics::Report(actor.clone(), metrics::Metric::Handled, 3));
+    addr.send(metrics::Report(actor.clone(), metrics::Metric::MailboxDepth, 7));
+
+    Arbiter::handle().spawn(
+        addr.call_fut(metrics::GetMetrics)
+            .then(move |res| {
+                let snapshot = res.unwrap().unwrap();
+                assert_eq!(snapshot.runtime[&arbiter].tick_lag, 500);
+                assert_eq!(snapshot.runtime[&actor].handled, 5);
+                assert_eq!(snapshot.runtime[&actor].mailbox_depth, 7);
+                assert_eq!(snapshot.runtime[&actor].dropped, 0);
+
+                let text = snapshot.to_prometheus();
+                assert!(text.contains("actix_runtime_tick_lag_seconds{arbiter=\"worker\"} 0.0005"));
+                assert!(text.contains("actix_runtime_handled_total{actor=\"my-actor\"} 5"));
+
+                Arbiter::system().send(msgs::SystemExit(0));
+                future::result(Ok(()))
+            }));
+
+    sys.run();
+}
+
+#[test]
+#[cfg(feature="serde")]
+fn test_snapshot_serde() {
+    let sys = System::new("test");
+
+    let addr: Address<_> = MyActor.start();
+    addr.send(Ping(false));
+    let metrics = Arbiter::system_registry().get::<MetricsActor>();
+    let worker = metrics::Source::Arbiter("worker".to_owned());
+    metrics.send(metrics::Report(worker.clone(), metrics::Metric::TickLag, 1500));
+
+    Arbiter::handle().spawn(
+        addr.call_fut(Ping(true))
+            .then(move |_| metrics.call_fut(metrics::Snapshot))
+            .then(move |res| {
+                let snapshot = res.unwrap().unwrap();
+                let json = serde_json::to_string(&snapshot).unwrap();
+                let decoded: metrics::MetricsSnapshot = serde_json::from_str(&json).unwrap();
+                assert_eq!(decoded.actors["my-actor"].received, 2);
+                assert_eq!(decoded.actors["my-actor"].errors, 1);
+                assert_eq!(decoded.actors["my-actor"].latency.count, 2);
+                assert_eq!(decoded.runtime[&worker].tick_lag, 1500);
+                assert_eq!(serde_json::to_string(&decoded).unwrap(), json);
+
+                let report = metrics::Report(worker, metrics::Metric::Dropped, 3);
+                let json = serde_json::to_string(&report).unwrap();
+                let decoded: metrics::Report = serde_json::from_str(&json).unwrap();
+                assert_eq!((decoded.0, decoded.1, decoded.2), (report.0, report.1, report.2));
+
+                Arbiter::system().send(msgs::SystemExit(0));
+                future::result(Ok(()))
+            }));
+
+    sys.run();
+}
+
+struct Counted;
+
+struct Tick;
+
+impl Actor for Counted {
+    type Context = Context<Self>;
+}
+
+impl ResponseType<Tick> for Counted {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<Tick> for Counted {
+    fn handle(&mut self, _: Tick, ctx: &mut Context<Self>) -> Response<Self, Tick> {
+        ctx.stop();
+        Self::empty()
+    }
+}
+
+#[test]
+fn test_arbiter_metrics() {
+    let sys = System::new("test");
+    let before = Arbiter::metrics();
+
+    let addr: SyncAddress<_> = Counted.start();
+    addr.send_deadline(Tick, Instant::now() - Duration::from_secs(1));
+    addr.send(Tick);
+
+    let worker = Arbiter::new(None);
+    Arbiter::handle().spawn(
+        worker.call_fut(msgs::Execute::new(|| -> Result<(), ()> {
+            let addr: SyncAddress<_> = Counted.start();
+            addr.send(Tick);
+            Ok(())
+        }))
+        .then(move |_| addr.call_fut(Tick))
+        .then(move |_| {
+            let metrics = Arbiter::metrics();
+            assert_eq!(metrics.messages_dropped - before.messages_dropped, 1);
+            assert!(metrics.messages_processed - before.messages_processed >= 1);
+            assert_eq!(metrics.actors_started - before.actors_started, 1);
+            assert_eq!(metrics.actors_stopped - before.actors_stopped, 1);
+            assert!(metrics.uptime >= before.uptime);
+
+            Arbiter::system().call_fut(msgs::SystemMetrics)
+                .map(move |total| (metrics, total.unwrap()))
+        })
+        .then(|res| {
+            let (metrics, total) = res.unwrap();
+            // totals include worker arbiter
+            assert!(total.actors_started > metrics.actors_started);
+            assert!(total.messages_processed > metrics.messages_processed);
+            assert_eq!(total.messages_dropped, 1);
+            assert!(total.uptime >= metrics.uptime);
+            Arbiter::system().send(msgs::SystemExit(0));
+            Ok(())
+        }));
+
+    sys.run();
+}
@@ -0,0 +1,16 @@
+extern crate actix;
+
+use std::thread;
+use actix::prelude::*;
+
+#[test]
+fn test_panic_shutdown() {
+    let sys = System::new("test");
+    System::with_panic_shutdown_code(5);
+
+    thread::spawn(|| {
+        panic!("test panic");
+    });
+
+    assert_eq!(sys.run(), 5);
+}
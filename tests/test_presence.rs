@@ -0,0 +1,125 @@
+extern crate actix;
+extern crate futures;
+extern crate tokio_core;
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use futures::Future;
+use actix::prelude::*;
+use actix::actors::presence::{GetPresence, PresenceEvent, PresenceJoin, PresenceLeave,
+                              PresenceTracker};
+use common::sleep;
+
+struct Quit;
+
+struct User(Arc<Mutex<Vec<PresenceEvent>>>);
+
+impl Actor for User {
+    type Context = Context<Self>;
+}
+
+impl ResponseType<PresenceEvent> for User {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<PresenceEvent> for User {
+    fn handle(&mut self, msg: PresenceEvent, _: &mut Context<Self>)
+              -> Response<Self, PresenceEvent>
+    {
+        self.0.lock().unwrap().push(msg);
+        Self::empty()
+    }
+}
+
+impl ResponseType<Quit> for User {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<Quit> for User {
+    fn handle(&mut self, _: Quit, ctx: &mut Context<Self>) -> Response<Self, Quit> {
+        ctx.stop();
+        Self::empty()
+    }
+}
+
+#[test]
+fn test_join_leave() {
+    let sys = System::new("test");
+    let events = Arc::new(Mutex::new(Vec::new()));
+
+    let tracker = Arbiter::system_registry().get::<PresenceTracker>();
+    let alice: SyncAddress<_> = User(Arc::clone(&events)).start();
+    let bob: SyncAddress<_> = User(Arc::new(Mutex::new(Vec::new()))).start();
+
+    tracker.send(PresenceJoin{id: "alice".to_owned(), addr: alice.recipient()});
+    tracker.send(PresenceJoin{id: "bob".to_owned(), addr: bob.recipient()});
+    tracker.send(PresenceLeave("bob".to_owned()));
+
+    let tracker2 = tracker.clone();
+    Arbiter::handle().spawn(
+        tracker.call_fut(GetPresence("alice".to_owned()))
+            .then(move |res| {
+                let info = res.unwrap().unwrap().unwrap();
+                assert_eq!(info.id, "alice");
+                assert_eq!(info.actor, "test_presence::User");
+                tracker2.call_fut(GetPresence("bob".to_owned()))
+            })
+            .then(|res| {
+                assert!(res.unwrap().unwrap().is_none());
+                sleep()
+            })
+            .then(|_| {
+                Arbiter::system().send(msgs::SystemExit(0));
+                Ok(())
+            }));
+    sys.run();
+
+    assert_eq!(*events.lock().unwrap(),
+               vec![PresenceEvent::Joined("bob".to_owned()),
+                    PresenceEvent::Left("bob".to_owned())]);
+}
+
+#[test]
+fn test_disconnected() {
+    let sys = System::new("test");
+    let clock = TestClock::new();
+    Arbiter::set_clock(clock.clone());
+    let events = Arc::new(Mutex::new(Vec::new()));
+
+    let tracker = Arbiter::system_registry().get::<PresenceTracker>();
+    let alice: SyncAddress<_> = User(Arc::clone(&events)).start();
+    let bob: SyncAddress<_> = User(Arc::new(Mutex::new(Vec::new()))).start();
+
+    tracker.send(PresenceJoin{id: "alice".to_owned(), addr: alice.recipient()});
+    tracker.send(PresenceJoin{id: "bob".to_owned(), addr: bob.recipient()});
+
+    let events2 = Arc::clone(&events);
+    let events3 = Arc::clone(&events);
+    Arbiter::handle().spawn(
+        bob.call_fut(Quit)
+            .then(|_| sleep())
+            .then(move |_| {
+                assert_eq!(events2.lock().unwrap().len(), 1);
+                clock.advance(Duration::from_secs(1));
+                sleep()
+            })
+            .then(move |_| {
+                // tracker notices stopped actor on periodic check
+                assert_eq!(events3.lock().unwrap().len(), 2);
+                tracker.call_fut(GetPresence("bob".to_owned()))
+            })
+            .then(|res| {
+                assert!(res.unwrap().unwrap().is_none());
+                Arbiter::system().send(msgs::SystemExit(0));
+                Ok(())
+            }));
+    sys.run();
+
+    assert_eq!(*events.lock().unwrap(),
+               vec![PresenceEvent::Joined("bob".to_owned()),
+                    PresenceEvent::Disconnected("bob".to_owned())]);
+}
@@ -0,0 +1,157 @@
+extern crate actix;
+extern crate futures;
+extern crate tokio_core;
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use futures::{stream, Future, Stream};
+use tokio_core::reactor::Timeout;
+use actix::prelude::*;
+use actix::CallError;
+use actix::actors::rate_limiter::AdaptiveRateLimiter;
+
+struct Job(usize);
+
+struct Worker {
+    latency: Duration,
+    received: Arc<Mutex<Vec<Instant>>>,
+}
+
+impl Actor for Worker {
+    type Context = Context<Self>;
+}
+
+impl ResponseType<Job> for Worker {
+    type Item = usize;
+    type Error = ();
+}
+
+impl Handler<Job> for Worker {
+    fn handle(&mut self, msg: Job, _: &mut Context<Self>) -> Response<Self, Job> {
+        self.received.lock().unwrap().push(Arbiter::now());
+        if self.latency == Duration::from_millis(0) {
+            Self::reply(msg.0)
+        } else {
+            Self::reply_after(msg.0, self.latency)
+        }
+    }
+}
+
+type Replies = Arc<Mutex<Vec<Result<usize, CallError<()>>>>>;
+
+/// Send `jobs` messages through limiter, returns gaps between deliveries
+/// to the worker in milliseconds
+fn run_jobs(jobs: usize, latency: Duration, replies: &Replies) -> Vec<u64> {
+    let sys = System::new("test");
+
+    let clock = TestClock::new();
+    Arbiter::set_clock(clock.clone());
+
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let worker: SyncAddress<_> = Worker{latency: latency, received: Arc::clone(&received)}.start();
+    let limiter: Address<_> =
+        AdaptiveRateLimiter::new(worker, 100.0, Duration::from_millis(20))
+            .min_rate(10.0)
+            .start();
+
+    for i in 0..jobs {
+        let replies = Arc::clone(replies);
+        Arbiter::handle().spawn(
+            limiter.call_fut(Job(i)).then(move |res| {
+                replies.lock().unwrap().push(res.unwrap());
+                Ok(())
+            }));
+    }
+
+    // advance test clock by 10 milliseconds, let event loop run between steps
+    Arbiter::handle().spawn(
+        stream::iter_ok(0..200)
+            .for_each(move |_| {
+                let clock = clock.clone();
+                Timeout::new(Duration::from_millis(1), Arbiter::handle()).unwrap()
+                    .map(move |_| clock.advance(Duration::from_millis(10)))
+                    .map_err(|_| ())
+            })
+            .then(|_| {
+                Arbiter::system().send(msgs::SystemExit(0));
+                Ok(())
+            }));
+    sys.run();
+
+    let received = received.lock().unwrap();
+    received.windows(2)
+        .map(|w| {
+            let gap = w[1] - w[0];
+            gap.as_secs() * 1000 + u64::from(gap.subsec_millis())
+        })
+        .collect()
+}
+
+#[test]
+fn test_max_rate() {
+    let replies = Arc::new(Mutex::new(Vec::new()));
+    let gaps = run_jobs(10, Duration::from_millis(0), &replies);
+
+    // fast worker gets messages at max rate
+    assert_eq!(gaps, vec![10; 9]);
+
+    let mut replies: Vec<_> = replies.lock().unwrap().iter()
+        .map(|res| *res.as_ref().unwrap())
+        .collect();
+    replies.sort();
+    assert_eq!(replies, (0..10).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_slow_target() {
+    let replies = Arc::new(Mutex::new(Vec::new()));
+    let gaps = run_jobs(10, Duration::from_millis(50), &replies);
+
+    // first response comes after 50 milliseconds, then rate
+    // drops to min rate while worker is slow
+    assert_eq!(gaps.len(), 9);
+    assert_eq!(gaps[..4], [10; 4]);
+    assert!(gaps[5] > 10);
+    assert_eq!(gaps[7..], [100; 2]);
+    assert_eq!(replies.lock().unwrap().len(), 10);
+}
+
+struct Stopped;
+
+impl Actor for Stopped {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Context<Self>) {
+        ctx.stop();
+    }
+}
+
+impl ResponseType<Job> for Stopped {
+    type Item = usize;
+    type Error = ();
+}
+
+impl Handler<Job> for Stopped {
+    fn handle(&mut self, msg: Job, _: &mut Context<Self>) -> Response<Self, Job> {
+        Self::reply(msg.0)
+    }
+}
+
+#[test]
+fn test_stopped_target() {
+    let sys = System::new("test");
+
+    let worker: SyncAddress<_> = Stopped.start();
+    let limiter: Address<_> =
+        AdaptiveRateLimiter::new(worker, 100.0, Duration::from_millis(20)).start();
+
+    Arbiter::handle().spawn(
+        Timeout::new(Duration::from_millis(50), Arbiter::handle()).unwrap()
+            .then(move |_| limiter.call_fut(Job(1)))
+            .then(|res| {
+                assert_eq!(res.unwrap(), Err(CallError::MailboxClosed));
+                Arbiter::system().send(msgs::SystemExit(0));
+                Ok(())
+            }));
+    sys.run();
+}
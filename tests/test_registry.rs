@@ -0,0 +1,128 @@
+extern crate actix;
+extern crate futures;
+extern crate tokio_core;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering, ATOMIC_USIZE_INIT};
+use futures::{future, Future};
+use tokio_core::reactor::Core;
+use actix::prelude::*;
+
+static STARTED: AtomicUsize = ATOMIC_USIZE_INIT;
+
+#[derive(Default)]
+struct MyService;
+
+impl Actor for MyService {
+    type Context = Context<Self>;
+}
+
+impl Supervised for MyService {}
+
+impl SystemService for MyService {
+    fn service_started(&mut self, _: &mut Context<Self>) {
+        STARTED.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn test_system_service_started() {
+    let sys = System::new("test");
+
+    let _ = Arbiter::system_registry().get::<MyService>();
+
+    Arbiter::handle().spawn(
+        Arbiter::system_registry().get_started::<MyService>()
+            .then(|res| {
+                assert!(res.is_ok());
+                assert_eq!(STARTED.load(Ordering::SeqCst), 1);
+                Arbiter::system().send(msgs::SystemExit(0));
+                future::result(Ok(()))
+            }));
+
+    sys.run();
+    assert_eq!(STARTED.load(Ordering::SeqCst), 1);
+}
+
+#[derive(Default)]
+struct ProbedService;
+
+impl Actor for ProbedService {
+    type Context = Context<Self>;
+}
+
+impl Supervised for ProbedService {}
+
+impl SystemService for ProbedService {}
+
+#[test]
+fn test_is_service_running() {
+    let sys = System::new("test");
+
+    Arbiter::handle().spawn(
+        Arbiter::system().call_fut(msgs::IsServiceRunning::of::<ProbedService>())
+            .then(|res| {
+                // probe does not start service
+                assert!(!res.unwrap().unwrap());
+                let _ = Arbiter::system_registry().get::<ProbedService>();
+                Arbiter::system().call_fut(msgs::IsServiceRunning::of::<ProbedService>())
+            })
+            .then(|res| {
+                assert!(res.unwrap().unwrap());
+                Arbiter::system().send(msgs::SystemExit(0));
+                future::result(Ok(()))
+            }));
+
+    sys.run();
+}
+
+#[test]
+fn test_system_with_core() {
+    let sys = System::new("owner");
+    let service = Arbiter::system_registry().get::<ProbedService>();
+    let registry = Arbiter::system_registry().clone();
+
+    let embedded = std::thread::spawn(move || {
+        // future spawned on the reactor before system exists
+        let ran = Arc::new(AtomicBool::new(false));
+        let core = Core::new().unwrap();
+        let flag = Arc::clone(&ran);
+        core.handle().spawn(future::lazy(move || {
+            flag.store(true, Ordering::SeqCst);
+            Ok(())
+        }));
+
+        let sys = System::new_with_core("embedded", core, Some(registry));
+        assert_eq!(Arbiter::name(), "embedded");
+        // system services are shared with owner system
+        assert_eq!(Arbiter::system_registry().get::<ProbedService>().id(), service.id());
+
+        Arbiter::system().send(msgs::SystemExit(0));
+        assert_eq!(sys.run(), 0);
+        assert!(ran.load(Ordering::SeqCst));
+    });
+
+    Arbiter::handle().spawn_fn(move || {
+        embedded.join().unwrap();
+        Arbiter::system().send(msgs::SystemExit(0));
+        future::ok(())
+    });
+
+    sys.run();
+}
+
+#[test]
+fn test_try_system_registry() {
+    assert!(Arbiter::try_system_registry().is_none());
+
+    let sys = System::new("test");
+    let registry = Arbiter::try_system_registry().unwrap();
+    let service = registry.get::<ProbedService>();
+    assert_eq!(Arbiter::system_registry().get::<ProbedService>().id(), service.id());
+
+    Arbiter::system().send(msgs::SystemExit(0));
+    sys.run();
+
+    // registry is released after system is torn down
+    assert!(Arbiter::try_system_registry().is_none());
+}
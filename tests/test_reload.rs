@@ -0,0 +1,120 @@
+#![cfg(feature="signal")]
+extern crate actix;
+extern crate futures;
+extern crate tokio_core;
+
+use std::env;
+use std::fs::File;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use futures::Future;
+use tokio_core::reactor::Timeout;
+use actix::prelude::*;
+use actix::actors::signal::{ProcessSignals, Signal, SignalType};
+use actix::actors::reload::{self, ConfigReloaded, DebouncedReload, Reload, ReloadableConfig};
+
+fn write_config(path: &PathBuf, value: u32) {
+    let mut f = File::create(path).unwrap();
+    write!(f, "{}", value).unwrap();
+}
+
+/// Send `signals` after actors subscribe to `ProcessSignals`
+fn send_later(ms: u64, signals: Vec<SignalType>) {
+    let addr = Arbiter::system_registry().get::<ProcessSignals>();
+    Arbiter::handle().spawn(
+        Timeout::new(Duration::from_millis(ms), Arbiter::handle()).unwrap()
+            .then(move |_| {
+                for sig in signals {
+                    addr.send(Signal::new(sig));
+                }
+                Ok(())
+            }));
+}
+
+fn exit_later(ms: u64) {
+    Arbiter::handle().spawn(
+        Timeout::new(Duration::from_millis(ms), Arbiter::handle()).unwrap()
+            .then(|_| {
+                Arbiter::system().send(msgs::SystemExit(0));
+                Ok(())
+            }));
+}
+
+/// Actor that records reloaded configs
+struct Watcher(Arc<Mutex<Vec<u32>>>);
+
+impl Actor for Watcher {
+    type Context = Context<Self>;
+}
+
+impl ResponseType<ConfigReloaded<u32>> for Watcher {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<ConfigReloaded<u32>> for Watcher {
+    fn handle(&mut self, msg: ConfigReloaded<u32>, _: &mut Context<Self>)
+              -> Response<Self, ConfigReloaded<u32>>
+    {
+        self.0.lock().unwrap().push(*msg.0);
+        Self::empty()
+    }
+}
+
+#[test]
+fn test_reload_on_sighup() {
+    let sys = System::new("test");
+    let path = env::temp_dir().join(format!("actix-test-reload-{}.conf", std::process::id()));
+    write_config(&path, 1);
+    let received = Arc::new(Mutex::new(Vec::new()));
+
+    let config: SyncAddress<_> = ReloadableConfig::new(&path, |s| s.trim().parse::<u32>()).start();
+    let watcher: SyncAddress<_> = Watcher(Arc::clone(&received)).start();
+    config.send(reload::Subscribe(watcher.subscriber()));
+
+    // only SIGHUP triggers reload
+    let path2 = path.clone();
+    Arbiter::handle().spawn(
+        Timeout::new(Duration::from_millis(50), Arbiter::handle()).unwrap()
+            .then(move |_| {
+                write_config(&path2, 2);
+                Ok(())
+            }));
+    send_later(100, vec![SignalType::Other(10), SignalType::Hup]);
+    exit_later(300);
+    sys.run();
+
+    let _ = std::fs::remove_file(&path);
+    // subscriber gets current config, then reloaded one
+    assert_eq!(*received.lock().unwrap(), vec![1, 2]);
+}
+
+/// Reload target that counts requests
+struct Counter(Arc<Mutex<usize>>);
+
+impl Subscriber<Reload> for Counter {
+    fn send(&self, _: Reload) -> Result<(), Reload> {
+        *self.0.lock().unwrap() += 1;
+        Ok(())
+    }
+}
+
+#[test]
+fn test_debounced_reload() {
+    let sys = System::new("test");
+    let count = Arc::new(Mutex::new(0));
+
+    let _: () = DebouncedReload::new(Box::new(Counter(Arc::clone(&count))))
+        .window(Duration::from_millis(100))
+        .start();
+
+    // burst of signals is coalesced into one reload
+    send_later(50, vec![SignalType::Hup, SignalType::Hup, SignalType::Hup]);
+    send_later(300, vec![SignalType::Hup]);
+    exit_later(600);
+    sys.run();
+
+    assert_eq!(*count.lock().unwrap(), 2);
+}
@@ -0,0 +1,91 @@
+extern crate actix;
+extern crate futures;
+extern crate tokio_core;
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use futures::Future;
+use tokio_core::reactor::Timeout;
+use actix::prelude::*;
+use actix::actors::replay::{ReplayBuffer, Publish, Subscribe};
+
+#[derive(Clone)]
+struct Event(usize);
+
+struct Collector(Arc<Mutex<Vec<usize>>>);
+
+impl Actor for Collector {
+    type Context = Context<Self>;
+}
+
+impl ResponseType<Event> for Collector {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<Event> for Collector {
+    fn handle(&mut self, msg: Event, _: &mut Context<Self>) -> Response<Self, Event> {
+        self.0.lock().unwrap().push(msg.0);
+        Self::empty()
+    }
+}
+
+#[test]
+fn test_replay_buffer() {
+    let sys = System::new("test");
+    let early = Arc::new(Mutex::new(Vec::new()));
+    let late = Arc::new(Mutex::new(Vec::new()));
+
+    let buffer: SyncAddress<_> = ReplayBuffer::new(2).start();
+    let addr: SyncAddress<_> = Collector(Arc::clone(&early)).start();
+    buffer.send(Subscribe(addr.subscriber()));
+
+    buffer.send(Publish(Event(1)));
+    buffer.send(Publish(Event(2)));
+    buffer.send(Publish(Event(3)));
+
+    // late subscriber receives last two events before live ones
+    let addr: SyncAddress<_> = Collector(Arc::clone(&late)).start();
+    buffer.send(Subscribe(addr.subscriber()));
+    buffer.send(Publish(Event(4)));
+
+    Arbiter::handle().spawn(
+        Timeout::new(Duration::from_millis(50), Arbiter::handle()).unwrap()
+            .then(|_| {
+                Arbiter::system().send(msgs::SystemExit(0));
+                Ok(())
+            }));
+    sys.run();
+
+    assert_eq!(*early.lock().unwrap(), vec![1, 2, 3, 4]);
+    assert_eq!(*late.lock().unwrap(), vec![2, 3, 4]);
+}
+
+#[test]
+fn test_replay_buffer_max_age() {
+    let sys = System::new("test");
+    let received = Arc::new(Mutex::new(Vec::new()));
+
+    let buffer: SyncAddress<_> =
+        ReplayBuffer::with_max_age(10, Duration::from_millis(20)).start();
+    buffer.send(Publish(Event(1)));
+
+    let recv = Arc::clone(&received);
+    Arbiter::handle().spawn(
+        Timeout::new(Duration::from_millis(50), Arbiter::handle()).unwrap()
+            .then(move |_| {
+                // expired event is not replayed
+                buffer.send(Publish(Event(2)));
+                let addr: SyncAddress<_> = Collector(recv).start();
+                buffer.send(Subscribe(addr.subscriber()));
+
+                Timeout::new(Duration::from_millis(20), Arbiter::handle()).unwrap()
+            })
+            .then(|_| {
+                Arbiter::system().send(msgs::SystemExit(0));
+                Ok(())
+            }));
+    sys.run();
+
+    assert_eq!(*received.lock().unwrap(), vec![2]);
+}
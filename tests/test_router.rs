@@ -0,0 +1,87 @@
+extern crate actix;
+extern crate futures;
+extern crate tokio_core;
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use futures::Future;
+use tokio_core::reactor::Timeout;
+use actix::prelude::*;
+use actix::actors::router::Router;
+
+struct Num(u32);
+
+struct Collector(&'static str, Arc<Mutex<Vec<(&'static str, u32)>>>);
+
+impl Actor for Collector {
+    type Context = Context<Self>;
+}
+
+impl ResponseType<Num> for Collector {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<Num> for Collector {
+    fn handle(&mut self, msg: Num, _: &mut Context<Self>) -> Response<Self, Num> {
+        self.1.lock().unwrap().push((self.0, msg.0));
+        Self::empty()
+    }
+}
+
+#[test]
+fn test_router() {
+    let sys = System::new("test");
+    let received = Arc::new(Mutex::new(Vec::new()));
+
+    let even: SyncAddress<_> = Collector("even", Arc::clone(&received)).start();
+    let large: SyncAddress<_> = Collector("large", Arc::clone(&received)).start();
+    let other: SyncAddress<_> = Collector("other", Arc::clone(&received)).start();
+
+    let router: Address<_> = Router::new()
+        .add_route(|n: &Num| n.0 > 100, large)
+        .add_route(|n: &Num| n.0 % 2 == 0, even)
+        .default_route(other)
+        .start();
+    router.send(Num(2));
+    router.send(Num(3));
+    router.send(Num(102));
+
+    Arbiter::handle().spawn(
+        router.call_fut(Num(4))
+            .then(|res| {
+                assert_eq!(res.unwrap(), Ok(()));
+                Timeout::new(Duration::from_millis(50), Arbiter::handle()).unwrap()
+            })
+            .then(|_| {
+                Arbiter::system().send(msgs::SystemExit(0));
+                Ok(())
+            }));
+    sys.run();
+
+    let mut received = received.lock().unwrap().clone();
+    received.sort();
+    assert_eq!(received, vec![("even", 2), ("even", 4), ("large", 102), ("other", 3)]);
+}
+
+#[test]
+fn test_router_no_route() {
+    let sys = System::new("test");
+    let received = Arc::new(Mutex::new(Vec::new()));
+
+    let even: SyncAddress<_> = Collector("even", Arc::clone(&received)).start();
+    let router: Address<_> = Router::new()
+        .add_route(|n: &Num| n.0 % 2 == 0, even)
+        .start();
+
+    Arbiter::handle().spawn(
+        router.call_fut(Num(1)).then(|res| {
+            // message without route is rejected
+            assert_eq!(res.unwrap(), Err(()));
+            Arbiter::system().send(msgs::SystemExit(0));
+            Ok(())
+        }));
+    sys.run();
+
+    assert!(received.lock().unwrap().is_empty());
+}
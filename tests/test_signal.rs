@@ -0,0 +1,237 @@
+#![cfg(feature="signal")]
+extern crate actix;
+extern crate futures;
+extern crate tokio_core;
+
+use std::thread;
+use std::rc::Rc;
+use std::cell::Cell;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use futures::{future, Future};
+use tokio_core::reactor::Timeout;
+use actix::prelude::*;
+use actix::TrySendError;
+use actix::actors::signal::{self, AddSignals, DefaultSignalsHandler, ProcessSignals, Signal, SignalType, Subscribe};
+
+/// Subscriber that never handles signals
+struct Stuck;
+
+impl Actor for Stuck {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Context<Self>) {
+        AsyncContext::wait(ctx, fut::wrap_future(future::empty()));
+    }
+}
+
+impl ResponseType<Signal> for Stuck {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<Signal> for Stuck {
+    fn handle(&mut self, _: Signal, _: &mut Context<Self>) -> Response<Self, Signal> {
+        Self::empty()
+    }
+}
+
+/// Subscribe `Stuck` actor and send `signals` after default handler subscribes
+fn send_signals(signals: Vec<SignalType>) {
+    let _: () = DefaultSignalsHandler.start();
+    let stuck: SyncAddress<_> = Stuck.start();
+    let addr = Arbiter::system_registry().get::<ProcessSignals>();
+    addr.send(Subscribe(stuck.subscriber()));
+
+    Arbiter::handle().spawn(
+        Timeout::new(Duration::from_millis(100), Arbiter::handle()).unwrap()
+            .then(move |_| {
+                for sig in signals {
+                    addr.send(Signal::new(sig));
+                }
+                Ok(())
+            }));
+}
+
+#[test]
+fn test_stuck_subscriber_timeout() {
+    let sys = System::new("test");
+    System::set_shutdown_timeout(Duration::from_millis(300));
+
+    let start = Instant::now();
+    send_signals(vec![SignalType::Term]);
+
+    // exit waits for stuck subscriber until shutdown timeout
+    assert_eq!(sys.run(), 0);
+    assert!(start.elapsed() >= Duration::from_millis(400));
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn test_second_signal_forces_exit() {
+    let sys = System::new("test");
+    System::set_shutdown_timeout(Duration::from_secs(10));
+
+    let start = Instant::now();
+    send_signals(vec![SignalType::Term, SignalType::Int]);
+
+    assert_eq!(sys.run(), 0);
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+/// Subscriber with buffer that is full for first `busy` attempts
+struct Bounded {
+    name: &'static str,
+    busy: Mutex<usize>,
+    received: Arc<Mutex<Vec<&'static str>>>,
+}
+
+impl Subscriber<Signal> for Bounded {
+    fn send(&self, _: Signal) -> Result<(), Signal> {
+        self.received.lock().unwrap().push(self.name);
+        Ok(())
+    }
+
+    fn try_send(&self, msg: Signal) -> Result<(), TrySendError<Signal>> {
+        let mut busy = self.busy.lock().unwrap();
+        if *busy > 0 {
+            *busy -= 1;
+            Err(TrySendError::Full(msg))
+        } else {
+            self.send(msg).map_err(TrySendError::Closed)
+        }
+    }
+}
+
+#[test]
+fn test_full_subscriber_does_not_block_broadcast() {
+    let sys = System::new("test");
+    let received = Arc::new(Mutex::new(Vec::new()));
+
+    let addr = Arbiter::system_registry().get::<ProcessSignals>();
+    addr.send(Subscribe(Box::new(
+        Bounded{name: "slow", busy: Mutex::new(3), received: Arc::clone(&received)})));
+    addr.send(Subscribe(Box::new(
+        Bounded{name: "fast", busy: Mutex::new(0), received: Arc::clone(&received)})));
+    addr.send(Signal::new(SignalType::Hup));
+
+    Arbiter::handle().spawn(
+        Timeout::new(Duration::from_millis(200), Arbiter::handle()).unwrap()
+            .then(|_| {
+                Arbiter::system().send(msgs::SystemExit(0));
+                Ok(())
+            }));
+    sys.run();
+
+    // full subscriber gets signal after retries
+    assert_eq!(*received.lock().unwrap(), vec!["fast", "slow"]);
+}
+
+/// Subscriber that records received signals
+struct Recorder(Arc<Mutex<Vec<(SignalType, Instant, Duration)>>>);
+
+impl Actor for Recorder {
+    type Context = Context<Self>;
+}
+
+impl ResponseType<Signal> for Recorder {
+    type Item = ();
+    type Error = ();
+}
+
+impl Handler<Signal> for Recorder {
+    fn handle(&mut self, msg: Signal, _: &mut Context<Self>) -> Response<Self, Signal> {
+        self.0.lock().unwrap().push((msg.0, msg.received(), msg.delay()));
+        Arbiter::system().send(msgs::SystemExit(0));
+        Self::empty()
+    }
+}
+
+#[test]
+fn test_signal_delay() {
+    let sys = System::new("test");
+    let received = Arc::new(Mutex::new(Vec::new()));
+
+    let recorder: SyncAddress<_> = Recorder(Arc::clone(&received)).start();
+    let addr = Arbiter::system_registry().get::<ProcessSignals>();
+    addr.send(Subscribe(recorder.subscriber()));
+
+    // subscriber sees time when signal was received, not when it was handled
+    let signal = Signal::new(SignalType::Hup);
+    thread::sleep(Duration::from_millis(50));
+    addr.send(signal);
+    sys.run();
+
+    let received = received.lock().unwrap();
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].0, SignalType::Hup);
+    assert_eq!(received[0].1, signal.received());
+    assert!(received[0].2 >= Duration::from_millis(50));
+}
+
+#[test]
+fn test_signal_eq() {
+    let signal = Signal::new(SignalType::Term);
+    thread::sleep(Duration::from_millis(10));
+
+    // receive time is not compared
+    assert_eq!(signal, Signal::new(SignalType::Term));
+    assert_ne!(signal, Signal::new(SignalType::Int));
+    assert_ne!(Signal::new(SignalType::Other(28)), Signal::new(SignalType::Other(10)));
+}
+
+#[test]
+fn test_add_signals() {
+    let sys = System::new("test");
+
+    let addr = Arbiter::system_registry().get::<ProcessSignals>();
+    let addr2 = addr.clone();
+    Arbiter::handle().spawn(
+        addr.call_fut(AddSignals(vec![28, 10]))
+            .then(move |res| {
+                assert!(res.unwrap().is_ok());
+                // invalid signal number is reported to sender
+                addr2.call_fut(AddSignals(vec![12, -1]))
+            })
+            .then(|res| {
+                assert!(res.unwrap().is_err());
+                Arbiter::system().send(msgs::SystemExit(0));
+                Ok(())
+            }));
+
+    sys.run();
+}
+
+#[test]
+fn test_wait_for_signal() {
+    let sys = System::new("test");
+    let fired = Rc::new(Cell::new(false));
+
+    let fired2 = Rc::clone(&fired);
+    Arbiter::handle().spawn(
+        signal::wait_for_signal(SignalType::Hup)
+            .then(move |res| {
+                assert!(res.is_ok());
+                fired2.set(true);
+                Arbiter::system().send(msgs::SystemExit(0));
+                Ok(())
+            }));
+
+    // other signal types do not resolve future
+    let addr = Arbiter::system_registry().get::<ProcessSignals>();
+    let fired2 = Rc::clone(&fired);
+    Arbiter::handle().spawn(
+        Timeout::new(Duration::from_millis(100), Arbiter::handle()).unwrap()
+            .then(move |_| {
+                addr.send(Signal::new(SignalType::Other(10)));
+                Timeout::new(Duration::from_millis(100), Arbiter::handle()).unwrap()
+                    .then(move |_| {
+                        assert!(!fired2.get());
+                        addr.send(Signal::new(SignalType::Hup));
+                        Ok(())
+                    })
+            }));
+
+    sys.run();
+    assert!(fired.get());
+}
@@ -0,0 +1,173 @@
+extern crate actix;
+extern crate futures;
+extern crate tokio_core;
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use futures::Future;
+use tokio_core::reactor::Timeout;
+use actix::prelude::*;
+use actix::actors::supervision::{ActorGroup, AllForOneSupervisor, Child,
+                                 OneForOneSupervisor, RestartPolicy};
+
+type Log = Arc<Mutex<Vec<(usize, &'static str)>>>;
+
+struct Worker {
+    idx: usize,
+    log: Log,
+    fail: bool,
+}
+
+impl Actor for Worker {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Context<Self>) {
+        self.log.lock().unwrap().push((self.idx, "started"));
+        if self.fail {
+            ctx.stop();
+        }
+    }
+
+    fn stopped(&mut self, _: &mut Context<Self>) {
+        self.log.lock().unwrap().push((self.idx, "stopped"));
+    }
+}
+
+/// First worker fails `failures` times
+struct Workers {
+    log: Log,
+    failures: usize,
+}
+
+impl ActorGroup for Workers {
+    fn size(&self) -> usize {
+        2
+    }
+
+    fn start(&mut self, idx: usize) -> Child {
+        let fail = idx == 0 && self.failures > 0;
+        if fail {
+            self.failures -= 1;
+        }
+        let addr: Address<_> = Worker{idx: idx, log: Arc::clone(&self.log), fail: fail}.start();
+        Child::new(&addr)
+    }
+}
+
+fn run<F>(f: F) -> Vec<(usize, &'static str)> where F: FnOnce(Log) {
+    let sys = System::new("test");
+    let log = Arc::new(Mutex::new(Vec::new()));
+    f(Arc::clone(&log));
+
+    Arbiter::handle().spawn(
+        Timeout::new(Duration::from_millis(100), Arbiter::handle()).unwrap()
+            .then(|_| {
+                Arbiter::system().send(msgs::SystemExit(0));
+                Ok(())
+            }));
+    sys.run();
+
+    let log = log.lock().unwrap().clone();
+    log
+}
+
+fn count(log: &[(usize, &'static str)], item: (usize, &'static str)) -> usize {
+    log.iter().filter(|i| **i == item).count()
+}
+
+#[test]
+fn test_one_for_one() {
+    let log = run(|log| {
+        let _: () = OneForOneSupervisor::new(Workers{log: log, failures: 1}).start();
+    });
+
+    // only failed child is restarted
+    assert_eq!(count(&log, (0, "started")), 2);
+    assert_eq!(count(&log, (0, "stopped")), 1);
+    assert_eq!(count(&log, (1, "started")), 1);
+    assert_eq!(count(&log, (1, "stopped")), 0);
+}
+
+#[test]
+fn test_all_for_one() {
+    let log = run(|log| {
+        let _: () = AllForOneSupervisor::new(Workers{log: log, failures: 1}).start();
+    });
+
+    // whole group is restarted
+    assert_eq!(count(&log, (0, "started")), 2);
+    assert_eq!(count(&log, (0, "stopped")), 1);
+    assert_eq!(count(&log, (1, "started")), 2);
+    assert_eq!(count(&log, (1, "stopped")), 1);
+}
+
+#[test]
+fn test_restart_limit() {
+    let log = run(|log| {
+        let policy = RestartPolicy{max_restarts: 2, within: Duration::from_secs(5)};
+        let _: () = OneForOneSupervisor::with_policy(
+            Workers{log: log, failures: 10}, policy).start();
+    });
+
+    // third failure stops all children
+    assert_eq!(count(&log, (0, "started")), 3);
+    assert_eq!(count(&log, (0, "stopped")), 3);
+    assert_eq!(count(&log, (1, "started")), 1);
+    assert_eq!(count(&log, (1, "stopped")), 1);
+}
+
+/// Single worker under its own supervisor
+struct Inner {
+    log: Log,
+}
+
+impl ActorGroup for Inner {
+    fn size(&self) -> usize {
+        1
+    }
+
+    fn start(&mut self, _: usize) -> Child {
+        let addr: Address<_> = Worker{idx: 10, log: Arc::clone(&self.log), fail: false}.start();
+        Child::new(&addr)
+    }
+}
+
+/// Nested supervisor and worker that fails `failures` times
+struct Nested {
+    log: Log,
+    failures: usize,
+}
+
+impl ActorGroup for Nested {
+    fn size(&self) -> usize {
+        2
+    }
+
+    fn start(&mut self, idx: usize) -> Child {
+        if idx == 0 {
+            let addr: Address<_> = OneForOneSupervisor::new(
+                Inner{log: Arc::clone(&self.log)}).start();
+            Child::new(&addr)
+        } else {
+            let fail = self.failures > 0;
+            if fail {
+                self.failures -= 1;
+            }
+            let addr: Address<_> = Worker{idx: idx, log: Arc::clone(&self.log), fail: fail}.start();
+            Child::new(&addr)
+        }
+    }
+}
+
+#[test]
+fn test_stopped_supervisor_stops_children() {
+    let log = run(|log| {
+        let _: () = AllForOneSupervisor::new(Nested{log: log, failures: 1}).start();
+    });
+
+    // nested supervisor stops its worker, so whole group restarts
+    assert_eq!(count(&log, (10, "started")), 2);
+    assert_eq!(count(&log, (10, "stopped")), 1);
+    assert_eq!(count(&log, (1, "started")), 2);
+    assert_eq!(count(&log, (1, "stopped")), 1);
+}
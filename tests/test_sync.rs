@@ -4,7 +4,9 @@ extern crate tokio_core;
 
 use std::sync::{Arc, Condvar, Mutex};
 use std::sync::atomic::{AtomicUsize, Ordering};
-use futures::future;
+use std::thread;
+use std::time::{Duration, Instant};
+use futures::{future, Future};
 use actix::prelude::*;
 
 
@@ -100,3 +102,159 @@ fn test_sync() {
     assert_eq!(counter.load(Ordering::Relaxed), 2, "Not started");
     assert_eq!(messages.load(Ordering::Relaxed), 5, "Wrong number of messages");
 }
+
+#[test]
+fn test_spawn_blocking() {
+    let sys = System::new("test");
+
+    let start = Instant::now();
+    let main_thread = thread::current().id();
+    let jobs: Vec<_> = (0..4).map(|i| Arbiter::spawn_blocking(move || -> Result<_, ()> {
+        thread::sleep(Duration::from_millis(100));
+        Ok((i, thread::current().id()))
+    })).collect();
+
+    Arbiter::handle().spawn(
+        future::join_all(jobs).then(move |res| {
+            let res = res.unwrap();
+            assert_eq!(res.iter().map(|r| r.0).collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+            assert!(res.iter().all(|r| r.1 != main_thread));
+            // jobs run in parallel
+            assert!(start.elapsed() < Duration::from_millis(300));
+            Arbiter::system().send(msgs::SystemExit(0));
+            future::result(Ok(()))
+        }));
+
+    sys.run();
+}
+
+struct Hasher {
+    main_thread: thread::ThreadId,
+}
+
+impl Actor for Hasher {
+    type Context = Context<Self>;
+}
+
+impl ResponseType<Fibonacci> for Hasher {
+    type Item = u64;
+    type Error = actix::BlockingError;
+}
+
+impl Handler<Fibonacci> for Hasher {
+    fn handle(&mut self, msg: Fibonacci, ctx: &mut Context<Self>) -> Response<Self, Fibonacci> {
+        let main_thread = self.main_thread;
+        Self::async_reply(
+            ctx.spawn_blocking(move || {
+                assert!(thread::current().id() != main_thread);
+                let (mut a, mut b) = (0u64, 1u64);
+                for _ in 0..msg.0 {
+                    let next = a + b;
+                    a = b;
+                    b = next;
+                }
+                a
+            })
+            .map(|res, act: &mut Self, _: &mut Context<Self>| {
+                // result is delivered back in actor's thread
+                assert_eq!(thread::current().id(), act.main_thread);
+                res
+            }))
+    }
+}
+
+#[test]
+fn test_context_spawn_blocking() {
+    let sys = System::new("test");
+
+    let addr: Address<_> = Hasher{main_thread: thread::current().id()}.start();
+    Arbiter::handle().spawn(
+        addr.call_fut(Fibonacci(10))
+            .then(|res| {
+                assert_eq!(res.unwrap(), Ok(55));
+                Arbiter::system().send(msgs::SystemExit(0));
+                future::result(Ok(()))
+            }));
+
+    sys.run();
+}
+
+#[test]
+fn test_spawn_blocking_panic() {
+    let sys = System::new("test");
+
+    // more panics than pool threads, workers must survive
+    let jobs: Vec<_> = (0..8).map(|_| Arbiter::spawn_blocking(|| -> Result<(), ()> {
+        panic!("blocking job panic")
+    }).then(|res| match res {
+        Err(actix::CallError::MailboxClosed) => Ok(()),
+        _ => Err(()),
+    })).collect();
+
+    Arbiter::handle().spawn(
+        future::join_all(jobs)
+            .and_then(|_| Arbiter::spawn_blocking(|| -> Result<_, ()> { Ok(1) })
+                      .map_err(|_| ()))
+            .then(|res| {
+                assert_eq!(res, Ok(1));
+                Arbiter::system().send(msgs::SystemExit(0));
+                future::result(Ok(()))
+            }));
+
+    sys.run();
+}
+
+struct Stopped(Arc<AtomicUsize>);
+
+impl Actor for Stopped {
+    type Context = SyncContext<Self>;
+
+    fn stopped(&mut self, _: &mut Self::Context) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn test_sync_arbiter_stops_with_system() {
+    let stopped = Arc::new(AtomicUsize::new(0));
+    let st = Arc::clone(&stopped);
+
+    let sys = System::new("test");
+    // address is kept alive, workers still stop when system stops
+    let addr = SyncArbiter::start(2, move || Stopped(Arc::clone(&st)));
+    Arbiter::handle().spawn_fn(|| {
+        Arbiter::system().send(msgs::SystemExit(0));
+        future::result(Ok(()))
+    });
+    sys.run();
+
+    let start = Instant::now();
+    while stopped.load(Ordering::Relaxed) != 2 && start.elapsed() < Duration::from_secs(1) {
+        thread::sleep(Duration::from_millis(10));
+    }
+    assert_eq!(stopped.load(Ordering::Relaxed), 2);
+    drop(addr);
+}
+
+#[test]
+fn test_blocking_pool_not_counted() {
+    let sys = System::new("test");
+
+    Arbiter::handle().spawn(
+        Arbiter::system().call_fut(msgs::ActorCount)
+            .map_err(|_| ())
+            .and_then(|before| {
+                // first call starts thread pool
+                Arbiter::spawn_blocking(|| -> Result<_, ()> { Ok(()) })
+                    .map_err(|_| ())
+                    .and_then(|_| Arbiter::system().call_fut(msgs::ActorCount).map_err(|_| ()))
+                    .map(move |after| assert_eq!(before.unwrap(), after.unwrap()))
+            })
+            .then(|res| {
+                res.unwrap();
+                Arbiter::system().send(msgs::SystemExit(0));
+                future::result(Ok(()))
+            }));
+
+    sys.run();
+}